@@ -0,0 +1,248 @@
+//! Ethereum ABI encoding of integers as 32-byte words.
+//!
+//! An ABI `uintN` value is encoded as its big-endian bytes, padded with zeros at the start to 32 bytes, and an `intN` value is encoded as its two's complement big-endian bytes, sign-extended to 32 bytes. The width `N` must be a multiple of 8 between 8 and 256 inclusive, and may be smaller than the width of the bnum type, e.g. a `uint160` address can be held in a [`U256`](crate::types::U256).
+//!
+//! The encoding methods [`to_abi_uint`](crate::BUint::to_abi_uint) and [`to_abi_int`](crate::BInt::to_abi_int) return an error if the value is out of range for `N` bits. The decoding methods [`from_abi_uint`](crate::BUint::from_abi_uint) and [`from_abi_int`](crate::BInt::from_abi_int) return an error if the word is not a valid encoding of a `uintN` or `intN` value, i.e. if its padding bytes are not all zeros or not the sign extension of the value, or if the value is out of range for the bnum type.
+
+use crate::errors::TryFromIntError;
+use crate::ExpType;
+
+/// The number of bytes of an ABI word.
+pub const WORD_BYTES: usize = 32;
+
+/// Panics if `bits` isn't a valid ABI integer width.
+#[inline]
+const fn assert_abi_bits(bits: ExpType) {
+    assert!(
+        bits.is_multiple_of(8) && bits >= 8 && bits <= 256,
+        crate::errors::err_msg!("ABI integer width must be a multiple of 8 in range [8, 256]")
+    );
+}
+
+macro_rules! abi {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> $BUint<N> {
+            /// Returns the integer's big-endian bytes, padded at the start with `pad` or truncated to [`WORD_BYTES`] bytes.
+            #[inline]
+            const fn to_abi_word(self, pad: u8) -> [u8; WORD_BYTES] {
+                let mut word = [pad; WORD_BYTES];
+                let bytes = Self::BYTES as usize;
+                let len = if bytes < WORD_BYTES { bytes } else { WORD_BYTES };
+                let mut i = 0;
+                while i < len {
+                    word[WORD_BYTES - 1 - i] = self.byte(i);
+                    i += 1;
+                }
+                word
+            }
+
+            /// Encodes the integer as an ABI `uintN` word, where `N` is `bits`.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`TryFromIntError`](crate::errors::TryFromIntError) if the integer doesn't fit in `bits` bits.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `bits` isn't a multiple of 8 in the range `[8, 256]`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// let word = U256::from(0x1234u16).to_abi_uint(160).unwrap();
+            /// assert_eq!(&word[30..], &[0x12, 0x34]);
+            /// assert!(word[..30].iter().all(|&byte| byte == 0));
+            /// assert!(U256::MAX.to_abi_uint(160).is_err());
+            /// ```
+            pub const fn to_abi_uint(&self, bits: ExpType) -> Result<[u8; WORD_BYTES], TryFromIntError> {
+                assert_abi_bits(bits);
+                if self.bits() > bits {
+                    return Err(TryFromIntError::new(bits));
+                }
+                Ok(self.to_abi_word(0))
+            }
+
+            /// Decodes an ABI `uintN` word, where `N` is `bits`.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`TryFromIntError`](crate::errors::TryFromIntError) if the word's value doesn't fit in `bits` bits, or if it is too large to be represented by `Self`. The error's [`bits`](crate::errors::TryFromIntError::bits) method returns `bits` in the first case and `Self::BITS` in the second.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `bits` isn't a multiple of 8 in the range `[8, 256]`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::{U128, U256};
+            ///
+            /// let mut word = [0; 32];
+            /// word[31] = 0x2a;
+            /// assert_eq!(U256::from_abi_uint(&word, 8), Ok(U256::from(42u8)));
+            ///
+            /// word[0] = 1;
+            /// assert_eq!(U256::from_abi_uint(&word, 160).unwrap_err().bits(), Some(160));
+            /// assert_eq!(U128::from_abi_uint(&word, 256).unwrap_err().bits(), Some(128));
+            /// ```
+            pub fn from_abi_uint(word: &[u8; WORD_BYTES], bits: ExpType) -> Result<Self, TryFromIntError> {
+                assert_abi_bits(bits);
+                let (padding, value) = word.split_at(WORD_BYTES - bits as usize / 8);
+                if padding.iter().any(|&byte| byte != 0) {
+                    return Err(TryFromIntError::new(bits));
+                }
+                match Self::from_be_slice(value) {
+                    Some(out) => Ok(out),
+                    None => Err(TryFromIntError::new(Self::BITS)),
+                }
+            }
+        }
+
+        impl<const N: usize> $BInt<N> {
+            /// Encodes the integer as an ABI `intN` word, where `N` is `bits`.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`TryFromIntError`](crate::errors::TryFromIntError) if the integer doesn't fit in a signed integer of `bits` bits.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `bits` isn't a multiple of 8 in the range `[8, 256]`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// let word = I256::from(-2i8).to_abi_int(64).unwrap();
+            /// assert!(word[..31].iter().all(|&byte| byte == 0xff));
+            /// assert_eq!(word[31], 0xfe);
+            /// assert!(I256::from(128u8).to_abi_int(8).is_err());
+            /// ```
+            pub const fn to_abi_int(&self, bits: ExpType) -> Result<[u8; WORD_BYTES], TryFromIntError> {
+                assert_abi_bits(bits);
+                let negative = self.is_negative();
+                // the number of bits needed to represent the integer, apart from the sign bit
+                let magnitude_bits = if negative { Self::BITS - self.bits.leading_ones() } else { self.bits.bits() };
+                if magnitude_bits >= bits {
+                    return Err(TryFromIntError::new(bits));
+                }
+                Ok(self.bits.to_abi_word(if negative { u8::MAX } else { 0 }))
+            }
+
+            /// Decodes an ABI `intN` word, where `N` is `bits`.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`TryFromIntError`](crate::errors::TryFromIntError) if the word isn't the sign extension of a signed integer of `bits` bits, or if its value is out of range for `Self`. The error's [`bits`](crate::errors::TryFromIntError::bits) method returns `bits` in the first case and `Self::BITS` in the second.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `bits` isn't a multiple of 8 in the range `[8, 256]`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::{I128, I256};
+            ///
+            /// let mut word = [0xff; 32];
+            /// assert_eq!(I256::from_abi_int(&word, 16), Ok(I256::NEG_ONE));
+            ///
+            /// word[31] = 0x7f;
+            /// word[30] = 0x7f;
+            /// assert_eq!(I256::from_abi_int(&word, 16).unwrap_err().bits(), Some(16));
+            /// assert_eq!(I128::from_abi_int(&[0x80; 32], 256).unwrap_err().bits(), Some(128));
+            /// ```
+            pub fn from_abi_int(word: &[u8; WORD_BYTES], bits: ExpType) -> Result<Self, TryFromIntError> {
+                assert_abi_bits(bits);
+                let (padding, value) = word.split_at(WORD_BYTES - bits as usize / 8);
+                let pad = if value[0] >> 7 == 1 { u8::MAX } else { 0 };
+                if padding.iter().any(|&byte| byte != pad) {
+                    return Err(TryFromIntError::new(bits));
+                }
+                match Self::from_be_slice(word) {
+                    Some(out) => Ok(out),
+                    None => Err(TryFromIntError::new(Self::BITS)),
+                }
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::abi::WORD_BYTES;
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+                use crate::types::{I256, I512, U256, U512};
+
+                fn pad(bytes: &[u8], pad: u8) -> [u8; WORD_BYTES] {
+                    let mut word = [pad; WORD_BYTES];
+                    word[WORD_BYTES - bytes.len()..].copy_from_slice(bytes);
+                    word
+                }
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_abi_uint(u: utest) -> bool {
+                        let big_u = UTEST::from(u);
+                        let word = pad(&u.to_be_bytes(), 0);
+                        let fits_u64 = u64::try_from(u).is_ok();
+                        big_u.to_abi_uint(128) == Ok(word)
+                            && UTEST::from_abi_uint(&word, 128) == Ok(big_u)
+                            && U512::from_abi_uint(&word, 256) == Ok(U512::from(u))
+                            && big_u.to_abi_uint(64).is_ok() == fits_u64
+                            && UTEST::from_abi_uint(&word, 64).is_ok() == fits_u64
+                    }
+
+                    fn quickcheck_abi_int(i: itest) -> bool {
+                        let big_i = ITEST::from(i);
+                        let word = pad(&i.to_be_bytes(), if i < 0 { 0xff } else { 0 });
+                        let fits_i64 = i64::try_from(i).is_ok();
+                        big_i.to_abi_int(128) == Ok(word)
+                            && ITEST::from_abi_int(&word, 128) == Ok(big_i)
+                            && I512::from_abi_int(&word, 256) == Ok(I512::from(i))
+                            && big_i.to_abi_int(64).is_ok() == fits_i64
+                            && ITEST::from_abi_int(&word, 64).is_ok() == fits_i64
+                    }
+                }
+
+                #[test]
+                fn abi() {
+                    assert_eq!(UTEST::from(255u8).to_abi_uint(8), Ok(pad(&[0xff], 0)));
+                    assert_eq!(UTEST::from(256u16).to_abi_uint(8).unwrap_err().bits(), Some(8));
+                    assert_eq!(ITEST::from(-128i16).to_abi_int(8), Ok(pad(&[0x80], 0xff)));
+                    assert_eq!(ITEST::from(127i16).to_abi_int(8), Ok(pad(&[0x7f], 0)));
+                    assert!(ITEST::from(-129i16).to_abi_int(8).is_err());
+                    assert!(ITEST::from(128i16).to_abi_int(8).is_err());
+
+                    // sign extension must match the sign bit of the `intN` value
+                    assert!(ITEST::from_abi_int(&pad(&[0x80], 0), 8).is_err());
+                    assert!(ITEST::from_abi_int(&pad(&[0x7f], 0xff), 8).is_err());
+
+                    // out of range for the 128 bit type
+                    assert_eq!(UTEST::from_abi_uint(&pad(&[1; 17], 0), 256).unwrap_err().bits(), Some(128));
+                    assert_eq!(ITEST::from_abi_int(&pad(&[0xff; 16], 0), 256).unwrap_err().bits(), Some(128));
+                    assert_eq!(ITEST::from_abi_int(&pad(&[0x00; 16], 0xff), 256).unwrap_err().bits(), Some(128));
+
+                    assert_eq!(U256::from_abi_uint(&[0xff; 32], 256), Ok(U256::MAX));
+                    assert_eq!(I256::from_abi_int(&pad(&[0x80], 0xff), 8), Ok(I256::from(-128i16)));
+                    let min = I512::MIN >> 256u32;
+                    let mut min_word = [0; WORD_BYTES];
+                    min_word[0] = 0x80;
+                    assert_eq!(min.to_abi_int(256), Ok(min_word));
+                    assert_eq!(I512::from_abi_int(&min_word, 256), Ok(min));
+                    assert!((min - I512::ONE).to_abi_int(256).is_err());
+                }
+
+                #[test]
+                #[should_panic]
+                fn abi_invalid_bits() {
+                    let _ = UTEST::ONE.to_abi_uint(12);
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(abi);
@@ -0,0 +1,176 @@
+//! Alphabets for converting integers to and from strings of symbols, such as base 58 and base 64 encodings.
+
+use core::fmt::{self, Debug, Formatter};
+
+const INVALID: u8 = u8::MAX;
+const IGNORED: u8 = u8::MAX - 1;
+
+/// An alphabet of ASCII symbols, which assigns a symbol to each digit of a given radix.
+///
+/// The radix of the alphabet is the number of symbols it contains, so the first symbol represents the digit `0`, the second represents `1`, and so on. Alphabets are used with methods such as [`to_str_alphabet`](crate::BUint::to_str_alphabet) and [`from_str_alphabet`](crate::BUint::from_str_alphabet) of [`BUint`](crate::BUint).
+///
+/// Integers are encoded as numbers in the radix of the alphabet: the most significant digit comes first, there are no leading zero digits (except for the integer zero, which is encoded as the symbol for `0`), and there is no padding. This means that, for example, the base 64 encoding of an integer is in general different to the base 64 encoding of its bytes.
+///
+/// # Examples
+///
+/// ```
+/// use bnum::alphabet::Alphabet;
+///
+/// const BINARY: Alphabet = Alphabet::new(b"ox").case_insensitive();
+/// assert_eq!(BINARY.radix(), 2);
+/// assert_eq!(BINARY.digit(b'X'), Some(1));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: &'static [u8],
+    digits: [u8; 256],
+}
+
+impl Alphabet {
+    /// The base 58 alphabet used by Bitcoin addresses.
+    pub const BASE58_BITCOIN: Self =
+        Self::new(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
+    /// The base 58 alphabet used by Flickr short URLs.
+    pub const BASE58_FLICKR: Self =
+        Self::new(b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ");
+
+    /// The base 32 alphabet defined in section 6 of [RFC 4648](https://datatracker.ietf.org/doc/html/rfc4648#section-6).
+    pub const BASE32: Self = Self::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567");
+
+    /// The "extended hex" base 32 alphabet defined in section 7 of [RFC 4648](https://datatracker.ietf.org/doc/html/rfc4648#section-7).
+    pub const BASE32_HEX: Self = Self::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUV");
+
+    /// The base 32 alphabet defined by [Douglas Crockford](https://www.crockford.com/base32.html).
+    ///
+    /// Decoding is case-insensitive, `I` and `L` are decoded as `1`, `O` is decoded as `0`, and hyphens are ignored.
+    pub const BASE32_CROCKFORD: Self = Self::new(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ")
+        .case_insensitive()
+        .with_alias(b'I', b'1')
+        .with_alias(b'i', b'1')
+        .with_alias(b'L', b'1')
+        .with_alias(b'l', b'1')
+        .with_alias(b'O', b'0')
+        .with_alias(b'o', b'0')
+        .with_ignored(b'-');
+
+    /// The base 64 alphabet defined in section 4 of [RFC 4648](https://datatracker.ietf.org/doc/html/rfc4648#section-4).
+    pub const BASE64: Self =
+        Self::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+
+    /// The URL and filename safe base 64 alphabet defined in section 5 of [RFC 4648](https://datatracker.ietf.org/doc/html/rfc4648#section-5).
+    pub const BASE64_URL: Self =
+        Self::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+
+    /// Creates an alphabet from its symbols, where the symbol at index `i` represents the digit `i`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there are fewer than 2 symbols, or if the symbols are not distinct ASCII characters.
+    pub const fn new(symbols: &'static [u8]) -> Self {
+        if symbols.len() < 2 {
+            panic!(crate::errors::err_msg!("alphabet must contain at least 2 symbols"));
+        }
+        let mut digits = [INVALID; 256];
+        let mut i = 0;
+        while i < symbols.len() {
+            let symbol = symbols[i];
+            if !symbol.is_ascii() {
+                panic!(crate::errors::err_msg!("alphabet symbols must be ASCII characters"));
+            }
+            if digits[symbol as usize] != INVALID {
+                panic!(crate::errors::err_msg!("alphabet symbols must be distinct"));
+            }
+            digits[symbol as usize] = i as u8;
+            i += 1;
+        }
+        Self { symbols, digits }
+    }
+
+    /// Returns a copy of the alphabet which, when decoding, also accepts `alias` in place of `symbol`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `symbol` is not a symbol of the alphabet, or if `alias` is already a symbol or alias.
+    pub const fn with_alias(mut self, alias: u8, symbol: u8) -> Self {
+        let digit = self.digits[symbol as usize];
+        if digit == INVALID || digit == IGNORED {
+            panic!(crate::errors::err_msg!("aliased symbol is not in the alphabet"));
+        }
+        if self.digits[alias as usize] != INVALID {
+            panic!(crate::errors::err_msg!("alias is already used by the alphabet"));
+        }
+        self.digits[alias as usize] = digit;
+        self
+    }
+
+    /// Returns a copy of the alphabet which, when decoding, accepts each ASCII letter symbol in either case.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the alphabet contains both the uppercase and lowercase forms of a letter.
+    pub const fn case_insensitive(mut self) -> Self {
+        let mut i = 0;
+        while i < self.symbols.len() {
+            let symbol = self.symbols[i];
+            let other = if symbol.is_ascii_lowercase() {
+                symbol.to_ascii_uppercase()
+            } else {
+                symbol.to_ascii_lowercase()
+            };
+            if other != symbol {
+                self = self.with_alias(other, symbol);
+            }
+            i += 1;
+        }
+        self
+    }
+
+    /// Returns a copy of the alphabet which, when decoding, skips every occurrence of `byte`. This can be used to allow separators.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `byte` is already a symbol or alias.
+    pub const fn with_ignored(mut self, byte: u8) -> Self {
+        if self.digits[byte as usize] != INVALID {
+            panic!(crate::errors::err_msg!("ignored byte is already used by the alphabet"));
+        }
+        self.digits[byte as usize] = IGNORED;
+        self
+    }
+
+    /// Returns the radix of the alphabet, which is its number of symbols.
+    #[inline]
+    pub const fn radix(&self) -> u32 {
+        self.symbols.len() as u32
+    }
+
+    /// Returns the symbols of the alphabet, in order of the digits they represent.
+    #[inline]
+    pub const fn symbols(&self) -> &'static [u8] {
+        self.symbols
+    }
+
+    /// Returns the digit represented by the given byte, or `None` if the byte is not a symbol or alias of the alphabet.
+    #[inline]
+    pub const fn digit(&self, byte: u8) -> Option<u8> {
+        match self.digits[byte as usize] {
+            INVALID | IGNORED => None,
+            digit => Some(digit),
+        }
+    }
+
+    /// Returns whether the given byte is skipped when decoding.
+    #[inline]
+    pub const fn is_ignored(&self, byte: u8) -> bool {
+        self.digits[byte as usize] == IGNORED
+    }
+}
+
+impl Debug for Alphabet {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // the symbols are checked to be ASCII on creation
+        let symbols = unsafe { core::str::from_utf8_unchecked(self.symbols) };
+        f.debug_tuple("Alphabet").field(&symbols).finish()
+    }
+}
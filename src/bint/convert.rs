@@ -54,7 +54,7 @@ macro_rules! int_try_from_bint {
 						let small = int.bits.digits[i] as $int;
 						let trunc = small as $Digit;
 						if int.bits.digits[i] != trunc {
-							return Err(TryFromIntError(()));
+							return Err(TryFromIntError::new(<$int>::BITS as ExpType));
 						}
 						out = small;
 						i = 1;
@@ -82,13 +82,13 @@ macro_rules! int_try_from_bint {
 
 					while i < N {
 						if int.bits.digits[i] != padding {
-							return Err(TryFromIntError(()));
+							return Err(TryFromIntError::new(<$int>::BITS as ExpType));
 						}
 						i += 1;
 					}
 
 					if out.is_negative() != neg {
-						return Err(TryFromIntError(()));
+						return Err(TryFromIntError::new(<$int>::BITS as ExpType));
 					}
 
 					Ok(out)
@@ -107,7 +107,7 @@ macro_rules! uint_try_from_bint {
 				#[inline]
 				fn try_from(int: $BInt<N>) -> Result<$uint, Self::Error> {
 					if int.is_negative() {
-						Err(TryFromIntError(()))
+						Err(TryFromIntError::new(<$uint>::BITS as ExpType))
 					} else {
 						<$uint>::try_from(int.bits)
 					}
@@ -122,6 +122,7 @@ use crate::errors::{ParseIntError, TryFromIntError};
 use crate::nightly::impl_const;
 use core::str::FromStr;
 use crate::digit;
+use crate::ExpType;
 
 macro_rules! convert {
 	($BUint: ident, $BInt: ident, $Digit: ident) => {
@@ -157,7 +158,7 @@ macro_rules! convert {
 				#[inline]
 				fn try_from(u: $BUint<N>) -> Result<Self, Self::Error> {
 					if u.leading_ones() != 0 {
-						Err(TryFromIntError(()))
+						Err(TryFromIntError::new(Self::BITS))
 					} else {
 						Ok(Self::from_bits(u))
 					}
@@ -1,5 +1,8 @@
 use crate::digit;
 use crate::doc;
+use crate::errors::BufferTooSmallError;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::mem::MaybeUninit;
 
 macro_rules! endian {
@@ -50,6 +53,146 @@ macro_rules! endian {
 				Self::from_le(self)
 			}
 
+			/// Returns the shortest two's complement big-endian bytes of the integer. This is the inverse of [`from_signed_bytes_be`](#method.from_signed_bytes_be).
+			///
+			/// Unlike [`to_be_bytes`](#method.to_be_bytes), this method doesn't require the `nightly` feature.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::I256;
+			///
+			/// assert_eq!(I256::from(-129i16).to_signed_bytes_be(), [0xff, 0x7f]);
+			/// assert_eq!(I256::from(128u8).to_signed_bytes_be(), [0x00, 0x80]);
+			/// assert_eq!(I256::NEG_ONE.to_signed_bytes_be(), [0xff]);
+			/// assert_eq!(I256::ZERO.to_signed_bytes_be(), [0]);
+			/// ```
+			#[cfg(feature = "alloc")]
+			#[must_use = doc::must_use_op!()]
+			#[inline]
+			pub fn to_signed_bytes_be(&self) -> Vec<u8> {
+				let mut out = vec![0; self.bits.twos_complement_len(self.is_negative())];
+				let _ = self.write_be_bytes(&mut out);
+				out
+			}
+
+			/// Returns the shortest two's complement little-endian bytes of the integer. This is the inverse of [`from_signed_bytes_le`](#method.from_signed_bytes_le).
+			///
+			/// Unlike [`to_le_bytes`](#method.to_le_bytes), this method doesn't require the `nightly` feature.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::I256;
+			///
+			/// assert_eq!(I256::from(-129i16).to_signed_bytes_le(), [0x7f, 0xff]);
+			/// assert_eq!(I256::from(127u8).to_signed_bytes_le(), [0x7f]);
+			/// ```
+			#[cfg(feature = "alloc")]
+			#[must_use = doc::must_use_op!()]
+			#[inline]
+			pub fn to_signed_bytes_le(&self) -> Vec<u8> {
+				let mut out = vec![0; self.bits.twos_complement_len(self.is_negative())];
+				let _ = self.write_le_bytes(&mut out);
+				out
+			}
+
+			/// Creates an integer from its two's complement big-endian bytes, which can have any length. The value is wrapped in an `Option` as the bytes may represent an integer which is out of range of the type. An empty slice represents zero.
+			///
+			/// This is equivalent to [`from_be_slice`](#method.from_be_slice).
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::{I128, I256};
+			///
+			/// assert_eq!(I256::from_signed_bytes_be(&[0xff, 0x7f]), Some(I256::from(-129i16)));
+			/// assert_eq!(I256::from_signed_bytes_be(&[0x80]), Some(I256::from(-128i16)));
+			/// assert_eq!(I128::from_signed_bytes_be(&I256::MIN.to_signed_bytes_be()), None);
+			/// ```
+			#[must_use]
+			#[inline]
+			pub fn from_signed_bytes_be(bytes: &[u8]) -> Option<Self> {
+				Self::from_be_slice(bytes)
+			}
+
+			/// Creates an integer from its two's complement little-endian bytes, which can have any length. The value is wrapped in an `Option` as the bytes may represent an integer which is out of range of the type. An empty slice represents zero.
+			///
+			/// This is equivalent to [`from_le_slice`](#method.from_le_slice).
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::I256;
+			///
+			/// assert_eq!(I256::from_signed_bytes_le(&[0x7f, 0xff]), Some(I256::from(-129i16)));
+			/// assert_eq!(I256::from_signed_bytes_le(&[]), Some(I256::ZERO));
+			/// ```
+			#[must_use]
+			#[inline]
+			pub fn from_signed_bytes_le(bytes: &[u8]) -> Option<Self> {
+				Self::from_le_slice(bytes)
+			}
+
+			/// Writes the two's complement big-endian bytes of the integer into the whole of `out`, sign-extended at the start, and returns the length of the integer's shortest two's complement representation. `out` can have any length, including more than `Self::BYTES`.
+			///
+			/// # Errors
+			///
+			/// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `out` is shorter than the integer's shortest two's complement representation. In this case, `out` isn't modified.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::I256;
+			///
+			/// let mut out = [0; 4];
+			/// assert_eq!(I256::from(-2i8).write_be_bytes(&mut out), Ok(1));
+			/// assert_eq!(out, [0xff, 0xff, 0xff, 0xfe]);
+			/// assert!(I256::from(128u8).write_be_bytes(&mut [0; 1]).is_err());
+			/// ```
+			#[inline]
+			pub fn write_be_bytes(&self, out: &mut [u8]) -> Result<usize, BufferTooSmallError> {
+				let negative = self.is_negative();
+				self.bits.write_padded_bytes(out, self.bits.twos_complement_len(negative), negative, true)
+			}
+
+			/// Writes the two's complement little-endian bytes of the integer into the whole of `out`, sign-extended at the end, and returns the length of the integer's shortest two's complement representation. `out` can have any length, including more than `Self::BYTES`.
+			///
+			/// # Errors
+			///
+			/// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `out` is shorter than the integer's shortest two's complement representation. In this case, `out` isn't modified.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::I256;
+			///
+			/// let mut out = [0; 4];
+			/// assert_eq!(I256::from(-2i8).write_le_bytes(&mut out), Ok(1));
+			/// assert_eq!(out, [0xfe, 0xff, 0xff, 0xff]);
+			/// ```
+			#[inline]
+			pub fn write_le_bytes(&self, out: &mut [u8]) -> Result<usize, BufferTooSmallError> {
+				let negative = self.is_negative();
+				self.bits.write_padded_bytes(out, self.bits.twos_complement_len(negative), negative, false)
+			}
+
+			/// Returns a view of the memory of the integer as a byte slice of length `Self::BYTES`, without copying. The slice consists of the digits of the integer's two's complement representation, least significant first, each in native byte order. On little-endian targets, this is the integer's little-endian bytes.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::I256;
+			///
+			/// let bytes = I256::NEG_ONE.as_ne_bytes();
+			/// assert_eq!(bytes, &[0xff; 32]);
+			/// ```
+			#[must_use]
+			#[inline]
+			pub const fn as_ne_bytes(&self) -> &[u8] {
+				self.bits.as_ne_bytes()
+			}
+
 			crate::nightly::const_fns! {
 				/// Create an integer value from a slice of bytes in big endian. The value is wrapped in an [`Option`](https://doc.rust-lang.org/core/option/enum.Option.html) as the integer represented by the slice of bytes may represent an integer too large to be represented by the type.
 				///
@@ -219,6 +362,54 @@ macro_rules! endian {
 				use crate::test::types::itest;
 
 				crate::int::endian::tests!($Digit; itest);
+
+				/// The shortest two's complement big-endian bytes of a primitive integer, for comparison.
+				fn signed_bytes(i: itest) -> alloc::vec::Vec<u8> {
+					let be = i.to_be_bytes();
+					let mut start = 0;
+					while start + 1 < be.len() && ((be[start] == 0 && be[start + 1] >> 7 == 0) || (be[start] == 0xff && be[start + 1] >> 7 == 1)) {
+						start += 1;
+					}
+					be[start..].to_vec()
+				}
+
+				quickcheck::quickcheck! {
+					fn quickcheck_signed_bytes(i: itest) -> bool {
+						let big_i = ITEST::from(i);
+						let be = signed_bytes(i);
+						let mut le = be.clone();
+						le.reverse();
+
+						let pad = if i < 0 { 0xff } else { 0 };
+						let mut padded = [0xaa; 20];
+						let written = big_i.write_be_bytes(&mut padded);
+						let mut padded_le = [0xaa; 20];
+						let written_le = big_i.write_le_bytes(&mut padded_le);
+						big_i.to_signed_bytes_be() == be
+							&& big_i.to_signed_bytes_le() == le
+							&& ITEST::from_signed_bytes_be(&be) == Some(big_i)
+							&& ITEST::from_signed_bytes_le(&le) == Some(big_i)
+							&& ITEST::from_signed_bytes_be(&padded) == Some(big_i)
+							&& written == Ok(be.len())
+							&& padded[..4] == [pad; 4]
+							&& padded[4..] == i.to_be_bytes()
+							&& written_le == Ok(be.len())
+							&& padded_le[16..] == [pad; 4]
+							&& big_i.write_le_bytes(&mut [0; 16][..be.len() - 1]).is_err()
+							&& (cfg!(target_endian = "big") || big_i.as_ne_bytes() == i.to_le_bytes())
+					}
+				}
+
+				#[test]
+				fn signed_bytes_out_of_range() {
+					let mut bytes = [0u8; 17];
+					bytes[0] = 0xfe;
+					assert_eq!(ITEST::from_signed_bytes_be(&bytes), None);
+					bytes[0] = 0xff;
+					assert_eq!(ITEST::from_signed_bytes_be(&bytes), None);
+					bytes[1] = 0x80;
+					assert_eq!(ITEST::from_signed_bytes_be(&bytes), Some(ITEST::MIN));
+				}
 			}
 		}
 	};
@@ -1,3 +1,4 @@
+use crate::fmt::{FixedHex, Grouped, Grouping, Scaled, SignMagnitude};
 use core::fmt::{Binary, Debug, Display, Formatter, LowerExp, LowerHex, Octal, UpperExp, UpperHex};
 
 macro_rules! fmt_method {
@@ -11,6 +12,145 @@ macro_rules! fmt_method {
 
 macro_rules! fmt {
     ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> $BInt<N> {
+            /// Returns a wrapper around the integer which, when formatted, writes its two's complement representation in hexadecimal with all `Self::BITS / 4` digits, including leading zeros.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I128;
+            ///
+            /// let n = I128::from(-2i8);
+            /// assert_eq!(format!("{}", n.to_hex_fixed()), "fffffffffffffffffffffffffffffffe");
+            /// assert_eq!(format!("{:#X}", I128::from(0x1f).to_hex_fixed()), "0x0000000000000000000000000000001F");
+            /// ```
+            #[inline]
+            pub const fn to_hex_fixed(self) -> FixedHex<$BUint<N>> {
+                self.to_bits().to_hex_fixed()
+            }
+
+            /// Returns a wrapper around the integer which, when formatted in binary, octal or hexadecimal, writes a `-` sign followed by the magnitude of the integer if it is negative, instead of its two's complement representation.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// let n = I256::from(-0x1f);
+            /// assert_eq!(format!("{:#x}", n.sign_magnitude()), "-0x1f");
+            /// assert_eq!(format!("{:X}", n.sign_magnitude()), "-1F");
+            /// assert_eq!(format!("{:b}", I256::from(5).sign_magnitude()), "101");
+            /// ```
+            #[inline]
+            pub const fn sign_magnitude(self) -> SignMagnitude<Self> {
+                SignMagnitude { value: self }
+            }
+
+            /// Returns a wrapper around the integer which, when formatted, writes it divided by `10^decimals` as a decimal number with exactly `precision` digits after the decimal point.
+            ///
+            /// The magnitude is rounded in the same way as by
+            #[doc = concat!("[`", stringify!($BUint), "::format_scaled`](crate::", stringify!($BUint), "::format_scaled).")]
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// let n = I256::from(-12345000);
+            /// assert_eq!(format!("{}", n.format_scaled(6, 3)), "-12.345");
+            /// assert_eq!(format!("{:+}", (-n).format_scaled(6, 1)), "+12.3");
+            /// ```
+            #[inline]
+            pub const fn format_scaled(self, decimals: u32, precision: u32) -> Scaled<Self> {
+                Scaled {
+                    value: self,
+                    decimals,
+                    precision,
+                }
+            }
+
+            /// Returns a wrapper around the integer which, when formatted, separates its digits into groups of `size` digits with `separator`, counting from the least significant digit.
+            ///
+            /// The sign is not part of any group. As for the integer itself, binary, octal and hexadecimal formatting use the two's complement representation.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `size` is zero.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// let n = I256::from(-1234567i32);
+            /// assert_eq!(format!("{}", n.grouped(3, ',')), "-1,234,567");
+            /// ```
+            #[inline]
+            pub const fn grouped(self, size: usize, separator: char) -> Grouped<Self> {
+                self.grouped_with(Grouping::new(size, separator))
+            }
+
+            /// Returns a wrapper around the integer which, when formatted, separates its digits into groups as described by `grouping`.
+            ///
+            /// For examples, see the [`Grouping`](crate::fmt::Grouping) documentation.
+            #[inline]
+            pub const fn grouped_with(self, grouping: Grouping) -> Grouped<Self> {
+                Grouped {
+                    value: self,
+                    grouping,
+                }
+            }
+        }
+
+        impl<const N: usize> Display for Grouped<$BInt<N>> {
+            #[inline]
+            fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+                let is_nonnegative = !self.value.is_negative();
+                self.value.unsigned_abs().fmt_radix_grouped(10, false, is_nonnegative, "", self.grouping, f)
+            }
+        }
+
+        impl<const N: usize> Display for Scaled<$BInt<N>> {
+            #[inline]
+            fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+                let is_nonnegative = !self.value.is_negative();
+                self.value.unsigned_abs().fmt_scaled(self.decimals, self.precision, is_nonnegative, f)
+            }
+        }
+
+        macro_rules! grouped_bits_fmt {
+            ($trait: ident) => {
+                impl<const N: usize> $trait for Grouped<$BInt<N>> {
+                    #[inline]
+                    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+                        $trait::fmt(&self.value.to_bits().grouped_with(self.grouping), f)
+                    }
+                }
+            };
+        }
+
+        grouped_bits_fmt!(Binary);
+        grouped_bits_fmt!(LowerHex);
+        grouped_bits_fmt!(Octal);
+        grouped_bits_fmt!(UpperHex);
+
+        macro_rules! sign_magnitude_fmt {
+            ($trait: ident, $radix: literal, $uppercase: literal, $prefix: literal) => {
+                impl<const N: usize> $trait for SignMagnitude<$BInt<N>> {
+                    #[inline]
+                    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+                        let is_nonnegative = !self.value.is_negative();
+                        self.value.unsigned_abs().fmt_radix($radix, $uppercase, is_nonnegative, $prefix, f)
+                    }
+                }
+            };
+        }
+
+        sign_magnitude_fmt!(Binary, 2, false, "0b");
+        sign_magnitude_fmt!(LowerHex, 16, false, "0x");
+        sign_magnitude_fmt!(Octal, 8, false, "0o");
+        sign_magnitude_fmt!(UpperHex, 16, true, "0x");
+
         impl<const N: usize> Binary for $BInt<N> {
             fmt_method!("{:b}{:0pad$b}", Self::BITS, "0b", Binary);
         }
@@ -18,7 +158,7 @@ macro_rules! fmt {
         impl<const N: usize> Display for $BInt<N> {
             #[inline]
             fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-                f.pad_integral(!self.is_negative(), "", &format!("{}", self.unsigned_abs()))
+                self.unsigned_abs().fmt_radix(10, false, !self.is_negative(), "", f)
             }
         }
 
@@ -32,8 +172,7 @@ macro_rules! fmt {
         impl<const N: usize> LowerExp for $BInt<N> {
             #[inline]
             fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-                let uint = self.unsigned_abs();
-                f.pad_integral(!self.is_negative(), "", &format!("{:e}", uint))
+                self.unsigned_abs().fmt_exp(!self.is_negative(), 'e', f)
             }
         }
 
@@ -48,8 +187,7 @@ macro_rules! fmt {
         impl<const N: usize> UpperExp for $BInt<N> {
             #[inline]
             fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-                let uint = self.unsigned_abs();
-                f.pad_integral(!self.is_negative(), "", &format!("{:E}", uint))
+                self.unsigned_abs().fmt_exp(!self.is_negative(), 'E', f)
             }
         }
 
@@ -62,6 +200,13 @@ macro_rules! fmt {
             mod [<$Digit _digit_tests>] {
                 use crate::test::types::big_types::$Digit::*;
                 crate::int::fmt::tests!(itest);
+
+                #[test]
+                fn grouped_zero_pad() {
+                    assert_eq!(format!("{:012}", ITEST::from(-1234567).grouped(3, ',')), "-001,234,567");
+                    assert_eq!(format!("{:013}", ITEST::from(-1234567).grouped(3, ',')), "-0,001,234,567");
+                    assert_eq!(format!("{:+08}", ITEST::from(1234).grouped(3, ',')), "+001,234");
+                }
             }
         }
     };
@@ -25,9 +25,6 @@ use crate::digit;
 use crate::ExpType;
 use crate::{doc, errors};
 
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
-
 use core::default::Default;
 
 use core::iter::{Iterator, Product, Sum};
@@ -41,12 +38,16 @@ macro_rules! mod_impl {
 		/// are stored in little endian (least significant digit first). This integer type aims to exactly replicate the behaviours of Rust's built-in signed integer types: [`i8`], [`i16`], [`i32`], [`i64`], [`i128`] and [`isize`]. The const generic parameter `N` is the number of digits that are stored in the underlying
 		#[doc = concat!("[`", stringify!($BUint), "`].")]
 		///
+		/// This type is `#[repr(transparent)]`, so has the same memory layout as the underlying
+		#[doc = concat!("[`", stringify!($BUint), "`].")]
+		///
 		#[doc = doc::arithmetic_doc!($BInt)]
 
 		// Clippy: we can allow derivation of `Hash` and manual implementation of `PartialEq` as the derived `PartialEq` would be the same except we make our implementation const.
 		#[allow(clippy::derive_hash_xor_eq)]
 		#[derive(Clone, Copy, Hash)]
-		#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+		#[cfg_attr(feature = "zerocopy", derive(zerocopy::FromBytes, zerocopy::IntoBytes, zerocopy::KnownLayout, zerocopy::Immutable))]
+		#[repr(transparent)]
 		pub struct $BInt<const N: usize> {
 			pub(crate) bits: $BUint<N>,
 		}
@@ -1,7 +1,9 @@
 use crate::doc;
-use crate::errors::ParseIntError;
+use crate::errors::{BufferTooSmallError, ParseIntError};
 use crate::int::radix::assert_range;
+#[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use core::num::IntErrorKind;
 
@@ -55,52 +57,204 @@ macro_rules! radix {
             #[inline]
             pub fn from_str_radix(mut src: &str, radix: u32) -> Result<Self, ParseIntError> {
                 assert_range!(radix, 36);
+                let len = src.len();
+                if src.starts_with("-+") {
+                    return Err(ParseIntError::invalid_digit(src.as_bytes(), 1, Self::BITS));
+                }
+                if src == "-" || src == "+" {
+                    return Err(ParseIntError::invalid_digit(src.as_bytes(), 0, Self::BITS));
+                }
 
                 let mut negative = false;
                 if src.starts_with('-') {
                     src = &src[1..];
                     negative = true;
-                    if src.starts_with('+') {
-                        return Err(ParseIntError {
-                            kind: IntErrorKind::InvalidDigit,
-                        });
-                    }
                 } else if src.starts_with('+') {
                     src = &src[1..];
                 }
-                match $BUint::from_str_radix(src, radix) {
+                let result = $BUint::from_str_radix(src, radix).map_err(|mut err| {
+                    // the index of an invalid digit is relative to the source with its sign removed
+                    err.index = err.index.map(|index| index + len - src.len());
+                    err
+                });
+                Self::from_parsed_magnitude(result, negative)
+            }
+
+            /// Converts the result of parsing the magnitude of an integer to a signed integer with the given sign, checking that it is in range.
+            const fn from_parsed_magnitude(result: Result<$BUint<N>, ParseIntError>, negative: bool) -> Result<Self, ParseIntError> {
+                match result {
                     Ok(uint) => {
                         if negative {
-                            if uint > Self::MIN.to_bits() {
-                                Err(ParseIntError {
-                                    kind: IntErrorKind::NegOverflow,
-                                })
+                            // the magnitude is at most `2^(BITS - 1)` exactly when its negation, if nonzero, has its sign bit set
+                            let out = Self::from_bits($BUint::ZERO.wrapping_sub(uint));
+                            if uint.is_zero() || out.is_negative() {
+                                Ok(out)
                             } else {
-                                Ok(Self::from_bits(uint).wrapping_neg())
+                                Err(ParseIntError::new(IntErrorKind::NegOverflow, Self::BITS))
                             }
                         } else {
                             let out = Self::from_bits(uint);
                             if out.is_negative() {
-                                Err(ParseIntError {
-                                    kind: IntErrorKind::PosOverflow,
-                                })
+                                Err(ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS))
                             } else {
                                 Ok(out)
                             }
                         }
                     }
-                    Err(err) => {
-                        if err.kind() == &IntErrorKind::PosOverflow && negative {
-                            Err(ParseIntError {
-                                kind: IntErrorKind::NegOverflow,
-                            })
-                        } else {
-                            Err(err)
-                        }
-                    }
+                    Err(ParseIntError {
+                        kind: IntErrorKind::PosOverflow,
+                        ..
+                    }) if negative => Err(ParseIntError::new(IntErrorKind::NegOverflow, Self::BITS)),
+                    Err(err) => Err(err),
                 }
             }
 
+            /// Converts a string slice in a given base to an integer, in a const context.
+            ///
+            /// This method accepts the same input as [`from_str_radix`](#method.from_str_radix), and returns the same result, but is `const` (without needing the `nightly` feature), so can be used to define constants.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 36 inclusive.
+            ///
+            /// For examples, see the
+			#[doc = concat!("[`parse_const`](crate::", stringify!($BUint), "::parse_const) method documentation for [`", stringify!($BUint), "`](crate::", stringify!($BUint), ").")]
+            pub const fn parse_const(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+                if radix < 2 || radix > 36 {
+                    panic!(crate::errors::err_msg!("Radix must be in range [2, 36]"));
+                }
+                let src = src.as_bytes();
+                let (negative, start) = match src {
+                    [b'-', b'+', ..] => {
+                        return Err(ParseIntError::invalid_digit(src, 1, Self::BITS))
+                    }
+                    [b'-' | b'+'] => return Err(ParseIntError::invalid_digit(src, 0, Self::BITS)),
+                    [b'-', ..] => (true, 1),
+                    [b'+', ..] => (false, 1),
+                    _ => (false, 0),
+                };
+                let result = $BUint::parse_digits(src, start, src.len(), radix, false);
+                Self::from_parsed_magnitude(result, negative)
+            }
+
+            /// Converts a hexadecimal string slice, written as a sign followed by the magnitude of the integer, to an integer.
+            ///
+            /// The string is expected to be an optional `+` or `-` sign, followed by an optional `0x` or `0X` prefix, followed by hexadecimal digits of either case. Any number of leading zeros is accepted. To parse the two's complement representation of an integer instead, e.g. the output of [`to_hex_fixed`](#method.to_hex_fixed), use
+			#[doc = concat!("[`", stringify!($BUint), "::from_hex`](crate::", stringify!($BUint), "::from_hex) and [`from_bits`](#method.from_bits).")]
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::{I128, U128};
+            ///
+            /// let n = I128::from(-0x1f);
+            /// assert_eq!(I128::from_hex("-0x001f"), Ok(n));
+            /// assert_eq!(I128::from_hex(&format!("{:x}", n.sign_magnitude())), Ok(n));
+            /// assert_eq!(U128::from_hex(&format!("{}", n.to_hex_fixed())).map(I128::from_bits), Ok(n));
+            /// ```
+            pub const fn from_hex(src: &str) -> Result<Self, ParseIntError> {
+                let src = src.as_bytes();
+                let (negative, sign_len) = match src {
+                    [b'-', ..] => (true, 1),
+                    [b'+', ..] => (false, 1),
+                    _ => (false, 0),
+                };
+                let start = if src.len() >= sign_len + 2 && src[sign_len] == b'0' && (src[sign_len + 1] == b'x' || src[sign_len + 1] == b'X') {
+                    sign_len + 2
+                } else {
+                    sign_len
+                };
+                let result = $BUint::parse_digits(src, start, src.len(), 16, false);
+                Self::from_parsed_magnitude(result, negative)
+            }
+
+            /// Writes the integer's two's complement representation in hexadecimal with all `Self::BITS / 4` digits, including leading zeros, into the start of `buf`, and returns the written string slice.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is shorter than `Self::BITS / 4`.
+            ///
+            /// For examples, see the
+			#[doc = concat!("[`write_hex_fixed`](crate::", stringify!($BUint), "::write_hex_fixed) method documentation for [`", stringify!($BUint), "`](crate::", stringify!($BUint), ").")]
+            #[inline]
+            pub fn write_hex_fixed<'a>(&self, buf: &'a mut [u8], uppercase: bool) -> Result<&'a str, BufferTooSmallError> {
+                self.bits.write_hex_fixed(buf, uppercase)
+            }
+
+            /// Converts a string slice in decimal scientific notation, such as `-1.5e30`, to an integer.
+            ///
+            /// The string is expected to be an optional `+` or `-` sign, followed by a number in the format accepted by
+			#[doc = concat!("[`", stringify!($BUint), "::from_sci_str`](crate::", stringify!($BUint), "::from_sci_str).")]
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// assert_eq!(I256::from_sci_str("-2.5e3"), Ok(I256::from(-2500)));
+            /// assert!(I256::from_sci_str("-2.5e0").is_err());
+            /// ```
+            pub const fn from_sci_str(src: &str) -> Result<Self, ParseIntError> {
+                let src = src.as_bytes();
+                let (negative, start) = match src {
+                    [b'-', ..] => (true, 1),
+                    [b'+', ..] => (false, 1),
+                    _ => (false, 0),
+                };
+                Self::from_parsed_magnitude($BUint::parse_scaled(src, start, 0, true), negative)
+            }
+
+            /// Converts a string slice containing a decimal number with an optional fractional part, such as `-12.345`, to an integer equal to that number multiplied by `10^decimals`.
+            ///
+            /// The string is expected to be an optional `+` or `-` sign, followed by a number in the format accepted by
+			#[doc = concat!("[`", stringify!($BUint), "::from_decimal_str_scaled`](crate::", stringify!($BUint), "::from_decimal_str_scaled).")]
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// assert_eq!(I256::from_decimal_str_scaled("-12.345", 6), Ok(I256::from(-12345000)));
+            /// ```
+            pub const fn from_decimal_str_scaled(src: &str, decimals: u32) -> Result<Self, ParseIntError> {
+                let src = src.as_bytes();
+                let (negative, start) = match src {
+                    [b'-', ..] => (true, 1),
+                    [b'+', ..] => (false, 1),
+                    _ => (false, 0),
+                };
+                Self::from_parsed_magnitude($BUint::parse_scaled(src, start, decimals, false), negative)
+            }
+
+            /// Converts a string slice written in the style of a Rust integer literal to an integer.
+            ///
+            /// The string is expected to be an optional `+` or `-` sign, followed by an optional `0x`, `0o` or `0b` prefix which selects the radix (the default is decimal), followed by digits which may be separated by `_` characters, followed by an optional type suffix. The type suffix is `i` followed by the number of bits of the integer, e.g. `i256` for a 256-bit signed integer.
+            ///
+            /// If the string contains an invalid character, the error's [`index`](crate::errors::ParseIntError::index) method returns its byte index.
+            ///
+            /// This method is `const`, so can be used to define constants. The [`int`](crate::int) macro does this at compile time.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// assert_eq!(I256::from_str_prefixed("-0xff_ff"), Ok(I256::from(-0xffffi32)));
+            /// assert_eq!(I256::from_str_prefixed("1_000_000i256"), Ok(I256::from(1000000i32)));
+            /// assert_eq!(I256::from_str_prefixed("-0b1012").unwrap_err().index(), Some(6));
+            /// ```
+            pub const fn from_str_prefixed(src: &str) -> Result<Self, ParseIntError> {
+                let src = src.as_bytes();
+                let (negative, start) = match src {
+                    [b'-' | b'+'] => return Err(ParseIntError::invalid_digit(src, 0, Self::BITS)),
+                    [b'-', ..] => (true, 1),
+                    [b'+', ..] => (false, 1),
+                    _ => (false, 0),
+                };
+                let result = $BUint::parse_literal_magnitude(src, start, b'i', Self::BITS);
+                Self::from_parsed_magnitude(result, negative)
+            }
+
             /// Returns the integer as a string in the given radix.
             ///
             /// # Panics
@@ -109,6 +263,7 @@ macro_rules! radix {
             ///
             /// For examples, see the
 			#[doc = concat!("[`to_str_radix`](crate::", stringify!($BUint), "::to_str_radix) method documentation for [`", stringify!($BUint), "`](crate::", stringify!($BUint), ").")]
+            #[cfg(feature = "alloc")]
             #[inline]
             pub fn to_str_radix(&self, radix: u32) -> String {
                 if self.is_negative() {
@@ -118,6 +273,42 @@ macro_rules! radix {
                 }
             }
 
+            /// Writes the integer as a string in the given radix into the start of `buf`, and returns the written string slice. Unlike [`to_str_radix`](#method.to_str_radix), this method doesn't allocate.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is too short to hold the sign and every digit of the integer.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 36 inclusive.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I512;
+            ///
+            /// let n = I512::from(-0xfe9i16);
+            /// let mut buf = [0; 8];
+            /// assert_eq!(n.write_str_radix(&mut buf, 16), Ok("-fe9"));
+            /// assert_eq!(n.write_str_radix(&mut buf[..3], 16).unwrap_err().required(), 4);
+            /// ```
+            #[inline]
+            pub fn write_str_radix<'a>(&self, buf: &'a mut [u8], radix: u32) -> Result<&'a str, BufferTooSmallError> {
+                if !self.is_negative() {
+                    return self.bits.write_str_radix(buf, radix);
+                }
+                assert_range!(radix, 36);
+                let abs = self.unsigned_abs();
+                let len = abs.radix_digits_len(radix) + 1;
+                if buf.len() < len {
+                    return Err(BufferTooSmallError { required: len });
+                }
+                buf[0] = b'-';
+                abs.fill_str_radix(radix, false, &mut buf[1..len]);
+                Ok(unsafe { core::str::from_utf8_unchecked(&buf[..len]) })
+            }
+
             /// Returns the integer's underlying representation as an unsigned integer in the given base in big-endian digit order.
             ///
             /// # Panics
@@ -126,6 +317,7 @@ macro_rules! radix {
             ///
             /// For examples, see the
 			#[doc = concat!("[`to_radix_be`](crate::", stringify!($BUint), "::to_radix_be) method documentation for [`", stringify!($BUint), "`]")]
+            #[cfg(feature = "alloc")]
             #[inline]
             pub fn to_radix_be(&self, radix: u32) -> Vec<u8> {
                 self.bits.to_radix_be(radix)
@@ -139,10 +331,45 @@ macro_rules! radix {
             ///
             /// For examples, see the
 			#[doc = concat!("[`to_radix_le`](crate::", stringify!($BUint), "::to_radix_le) method documentation for [`", stringify!($BUint), "`](crate::", stringify!($BUint), ").")]
+            #[cfg(feature = "alloc")]
             #[inline]
             pub fn to_radix_le(&self, radix: u32) -> Vec<u8> {
                 self.bits.to_radix_le(radix)
             }
+
+            /// Writes the integer's underlying representation as an unsigned integer in the given base into the start of `buf` in big-endian digit order, and returns the written digits.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is too short to hold every digit.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 256 inclusive.
+            ///
+            /// For examples, see the
+			#[doc = concat!("[`write_radix_be`](crate::", stringify!($BUint), "::write_radix_be) method documentation for [`", stringify!($BUint), "`](crate::", stringify!($BUint), ").")]
+            #[inline]
+            pub fn write_radix_be<'a>(&self, buf: &'a mut [u8], radix: u32) -> Result<&'a [u8], BufferTooSmallError> {
+                self.bits.write_radix_be(buf, radix)
+            }
+
+            /// Writes the integer's underlying representation as an unsigned integer in the given base into the start of `buf` in little-endian digit order, and returns the written digits.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is too short to hold every digit.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 256 inclusive.
+            ///
+            /// For examples, see the
+			#[doc = concat!("[`write_radix_le`](crate::", stringify!($BUint), "::write_radix_le) method documentation for [`", stringify!($BUint), "`](crate::", stringify!($BUint), ").")]
+            #[inline]
+            pub fn write_radix_le<'a>(&self, buf: &'a mut [u8], radix: u32) -> Result<&'a [u8], BufferTooSmallError> {
+                self.bits.write_radix_le(buf, radix)
+            }
         }
 
         #[cfg(test)]
@@ -167,6 +394,113 @@ macro_rules! radix {
 				quickcheck_from_to_radix!(itest, radix_le, 255);
 				quickcheck_from_to_radix!(itest, str_radix, 36);
 
+				#[test]
+				fn from_str_prefixed() {
+					use core::num::IntErrorKind;
+
+					let n = ITEST::from(-0x1f2e3di32);
+					assert_eq!(ITEST::from_str_prefixed("-2043453"), Ok(n));
+					assert_eq!(ITEST::from_str_prefixed("-0x1f_2e_3d"), Ok(n));
+					assert_eq!(ITEST::from_str_prefixed("+0o7627075"), Ok(-n));
+					assert_eq!(ITEST::from_str_prefixed(&format!("-0b111110010111000111101_i{}", ITEST::BITS)), Ok(n));
+					assert_eq!(ITEST::from_str_prefixed(&format!("0x{:x}", ITEST::MAX)), Ok(ITEST::MAX));
+					assert_eq!(ITEST::from_str_prefixed(&format!("-0x{:x}", ITEST::MIN.unsigned_abs())), Ok(ITEST::MIN));
+
+					let err = |src: &str| ITEST::from_str_prefixed(src).unwrap_err();
+					assert_eq!(err("-").kind(), &IntErrorKind::InvalidDigit);
+					assert_eq!(err("-").index(), Some(0));
+					assert_eq!(err("+").index(), Some(0));
+					assert_eq!(err("--1").index(), Some(1));
+					assert_eq!(err("-0x-1").index(), Some(3));
+					assert_eq!(err(&format!("-12u{}", ITEST::BITS)).index(), Some(3));
+					assert_eq!(err(&format!("0x{:x}", ITEST::MIN.unsigned_abs())).kind(), &IntErrorKind::PosOverflow);
+					assert_eq!(err(&format!("-0x1{:x}", ITEST::MIN.unsigned_abs())).kind(), &IntErrorKind::NegOverflow);
+				}
+
+				quickcheck::quickcheck! {
+					fn quickcheck_parse_const(i: crate::test::types::itest, radix: u8) -> quickcheck::TestResult {
+						if !(2..=36).contains(&radix) {
+							return quickcheck::TestResult::discard();
+						}
+						let s = ITEST::from(i).to_str_radix(radix as u32);
+						quickcheck::TestResult::from_bool(ITEST::parse_const(&s, radix as u32) == ITEST::from_str_radix(&s, radix as u32))
+					}
+				}
+
+				#[test]
+				fn parse_const() {
+					use core::num::IntErrorKind;
+
+					const N: Result<ITEST, crate::errors::ParseIntError> = ITEST::parse_const("-1f2e3d", 16);
+					assert_eq!(N, Ok(ITEST::from(-0x1f2e3di32)));
+					assert_eq!(ITEST::parse_const("-+1", 10).unwrap_err().index(), Some(1));
+					for sign in ["-", "+"] {
+						assert_eq!(ITEST::parse_const(sign, 10).unwrap_err().kind(), &IntErrorKind::InvalidDigit);
+						assert_eq!(ITEST::parse_const(sign, 10).unwrap_err().index(), Some(0));
+						assert_eq!(ITEST::parse_const(sign, 10), ITEST::from_str_radix(sign, 10));
+					}
+					let min = format!("{}", ITEST::MIN);
+					assert_eq!(ITEST::parse_const(&min, 10), Ok(ITEST::MIN));
+					assert_eq!(ITEST::parse_const(&format!("{}0", min), 10).unwrap_err().kind(), &IntErrorKind::NegOverflow);
+					assert_eq!(ITEST::parse_const(&format!("{}", ITEST::MIN.unsigned_abs()), 10).unwrap_err().kind(), &IntErrorKind::PosOverflow);
+					let invalid = format!("{}0x", min);
+					assert_eq!(ITEST::parse_const(&invalid, 10).unwrap_err().kind(), &IntErrorKind::InvalidDigit);
+					assert_eq!(ITEST::parse_const(&invalid, 10), ITEST::from_str_radix(&invalid, 10));
+				}
+
+				#[test]
+				fn scaled() {
+					use core::num::IntErrorKind;
+
+					assert_eq!(ITEST::from_sci_str("-1.5e3"), Ok(ITEST::from(-1500)));
+					assert_eq!(ITEST::from_sci_str("+1.5e3"), Ok(ITEST::from(1500)));
+					assert_eq!(ITEST::from_sci_str("-1.55e1").unwrap_err().index(), Some(4));
+					assert_eq!(ITEST::from_sci_str(&format!("{:e}", ITEST::MIN)), Ok(ITEST::MIN));
+					assert_eq!(ITEST::from_sci_str(&format!("{:e}", ITEST::MAX)), Ok(ITEST::MAX));
+					assert_eq!(ITEST::from_sci_str(&format!("-{:e}", ITEST::MAX.to_bits().wrapping_add(ITEST::TWO.to_bits()))).unwrap_err().kind(), &IntErrorKind::NegOverflow);
+					assert_eq!(ITEST::from_decimal_str_scaled("-12.345", 3), Ok(ITEST::from(-12345)));
+					assert_eq!(ITEST::from_decimal_str_scaled("-", 3).unwrap_err().kind(), &IntErrorKind::Empty);
+
+					assert_eq!(format!("{}", ITEST::from(-12345).format_scaled(3, 2)), "-12.34");
+					assert_eq!(format!("{:+}", ITEST::from(12345).format_scaled(3, 3)), "+12.345");
+					assert_eq!(format!("{:<8}|", ITEST::from(-5).format_scaled(1, 1)), "-0.5    |");
+					assert_eq!(format!("{}", ITEST::from(-4).format_scaled(1, 0)), "0");
+					assert_eq!(format!("{}", ITEST::from(-5).format_scaled(1, 0)), "0");
+					assert_eq!(format!("{}", ITEST::from(-6).format_scaled(1, 0)), "-1");
+					assert_eq!(format!("{:+}", ITEST::from(-4).format_scaled(2, 1)), "+0.0");
+					let s = format!("{}", ITEST::MIN.format_scaled(20, 20));
+					assert_eq!(ITEST::from_decimal_str_scaled(&s, 20), Ok(ITEST::MIN));
+				}
+
+				#[test]
+				fn hex() {
+					use core::num::IntErrorKind;
+
+					let n = ITEST::from(-0x1f2e3di32);
+					assert_eq!(ITEST::from_hex("-1F2e3d"), Ok(n));
+					assert_eq!(ITEST::from_hex("+0x001f2e3d"), Ok(-n));
+					assert_eq!(ITEST::from_hex(&format!("{:#x}", ITEST::MIN.sign_magnitude())), Ok(ITEST::MIN));
+					assert_eq!(ITEST::from_hex(&format!("{:x}", ITEST::MAX.sign_magnitude())), Ok(ITEST::MAX));
+					assert_eq!(ITEST::from_hex("-0x").unwrap_err().kind(), &IntErrorKind::Empty);
+					assert_eq!(ITEST::from_hex("0x-1").unwrap_err().index(), Some(2));
+					assert_eq!(ITEST::from_hex(&format!("{:x}", ITEST::MIN.to_hex_fixed())).unwrap_err().kind(), &IntErrorKind::PosOverflow);
+
+					let mut buf = [0; 64];
+					let width = ITEST::BITS as usize / 4;
+					assert_eq!(n.write_hex_fixed(&mut buf, false), Ok(format!("{:0width$x}", -0x1f2e3di128 as crate::test::types::itest, width = width).as_str()));
+					assert_eq!(format!("{:#X}", n.sign_magnitude()), "-0x1F2E3D");
+					assert_eq!(format!("{:+o}", (-n).sign_magnitude()), "+7627075");
+					assert_eq!(format!("{:>10b}", ITEST::from(-5).sign_magnitude()), "      -101");
+				}
+
+				#[test]
+				fn int_macro() {
+					const N: ITEST = crate::int!(ITEST, "-0x1f_2e_3d");
+					const MIN: ITEST = crate::int!(ITEST, "-0x8000_0000_0000_0000");
+					assert_eq!(N, ITEST::from(-0x1f2e3di32));
+					assert_eq!(MIN, ITEST::from(i64::MIN));
+				}
+
 				#[test]
 				fn from_to_radix_le() {
 					let buf = &[
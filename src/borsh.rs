@@ -0,0 +1,63 @@
+use ::borsh::io::{Read, Result, Write};
+use ::borsh::{BorshDeserialize, BorshSerialize};
+
+macro_rules! borsh {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        // The digits are stored least significant first and borsh encodes each of them in little endian, so this is the integer's little-endian bytes, the same as borsh's encoding of `u128`.
+        impl<const N: usize> BorshSerialize for $BUint<N> {
+            #[inline]
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                self.digits.serialize(writer)
+            }
+        }
+
+        impl<const N: usize> BorshDeserialize for $BUint<N> {
+            #[inline]
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                <[$Digit; N]>::deserialize_reader(reader).map(Self::from_digits)
+            }
+        }
+
+        impl<const N: usize> BorshSerialize for $BInt<N> {
+            #[inline]
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                self.bits.serialize(writer)
+            }
+        }
+
+        impl<const N: usize> BorshDeserialize for $BInt<N> {
+            #[inline]
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                $BUint::deserialize_reader(reader).map(Self::from_bits)
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_borsh(u: utest, i: itest) -> bool {
+                        let (big_u, big_i) = (UTEST::from(u), ITEST::from(i));
+                        let (u_bytes, i_bytes) = (::borsh::to_vec(&u).unwrap(), ::borsh::to_vec(&i).unwrap());
+                        ::borsh::to_vec(&big_u).unwrap() == u_bytes
+                            && ::borsh::to_vec(&big_i).unwrap() == i_bytes
+                            && ::borsh::from_slice::<UTEST>(&u_bytes).unwrap() == big_u
+                            && ::borsh::from_slice::<ITEST>(&i_bytes).unwrap() == big_i
+                    }
+                }
+
+                #[test]
+                fn borsh_invalid_length() {
+                    let bytes = ::borsh::to_vec(&UTEST::MAX).unwrap();
+                    assert!(::borsh::from_slice::<UTEST>(&bytes[1..]).is_err());
+                    assert!(::borsh::from_slice::<ITEST>(&[bytes.as_slice(), &[0]].concat()).is_err());
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(borsh);
@@ -0,0 +1,166 @@
+use crate::alphabet::Alphabet;
+use crate::errors::{BufferTooSmallError, ParseIntError};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::num::IntErrorKind;
+
+macro_rules! alphabet {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> $BUint<N> {
+            /// Writes the integer as a string of symbols of the given alphabet into `buf`. The length of `buf` must be equal to `self.radix_digits_len(alphabet.radix())`.
+            fn fill_str_alphabet<'a>(self, alphabet: &Alphabet, buf: &'a mut [u8]) -> &'a str {
+                self.fill_radix_le(alphabet.radix(), buf);
+                buf.reverse();
+                let symbols = alphabet.symbols();
+                for byte in buf.iter_mut() {
+                    *byte = symbols[*byte as usize];
+                }
+                // the symbols of an alphabet are always ASCII
+                unsafe { core::str::from_utf8_unchecked(buf) }
+            }
+
+            /// Returns the integer as a string of symbols of the given alphabet, with the most significant digit first.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            /// use bnum::alphabet::Alphabet;
+            ///
+            /// let n = U256::from(0x48656c6c6f20576f726c64u128);
+            /// assert_eq!(n.to_str_alphabet(&Alphabet::BASE58_BITCOIN), "JxF12TrwUP45BMd");
+            /// assert_eq!(U256::from(64u8).to_str_alphabet(&Alphabet::BASE64), "BA");
+            /// ```
+            #[cfg(feature = "alloc")]
+            #[inline]
+            pub fn to_str_alphabet(&self, alphabet: &Alphabet) -> String {
+                let mut out = vec![0; self.radix_digits_len(alphabet.radix())];
+                self.fill_str_alphabet(alphabet, &mut out);
+                unsafe { String::from_utf8_unchecked(out) }
+            }
+
+            /// Writes the integer as a string of symbols of the given alphabet into the start of `buf`, and returns the written string slice. Unlike [`to_str_alphabet`](#method.to_str_alphabet), this method doesn't allocate.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is too short to hold every digit of the integer.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            /// use bnum::alphabet::Alphabet;
+            ///
+            /// let mut buf = [0; 8];
+            /// let n = U256::from(1234567u32);
+            /// assert_eq!(n.write_str_alphabet(&mut buf, &Alphabet::BASE32_CROCKFORD), Ok("15NM7"));
+            /// ```
+            #[inline]
+            pub fn write_str_alphabet<'a>(&self, buf: &'a mut [u8], alphabet: &Alphabet) -> Result<&'a str, BufferTooSmallError> {
+                let len = self.radix_digits_len(alphabet.radix());
+                if buf.len() < len {
+                    return Err(BufferTooSmallError { required: len });
+                }
+                Ok(self.fill_str_alphabet(alphabet, &mut buf[..len]))
+            }
+
+            /// Converts a string of symbols of the given alphabet, with the most significant digit first, to an integer.
+            ///
+            /// Bytes which the alphabet ignores are skipped, and aliases are decoded as the symbols they stand for. If the string contains a byte which is not part of the alphabet, the error's [`index`](crate::errors::ParseIntError::index) method returns its byte index.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            /// use bnum::alphabet::Alphabet;
+            ///
+            /// let n = U256::from(1234567u32);
+            /// assert_eq!(U256::from_str_alphabet("15nm7", &Alphabet::BASE32_CROCKFORD), Ok(n));
+            /// assert_eq!(U256::from_str_alphabet("15-NM7", &Alphabet::BASE32_CROCKFORD), Ok(n));
+            /// assert_eq!(U256::from_str_alphabet("15NMU", &Alphabet::BASE32_CROCKFORD).unwrap_err().index(), Some(4));
+            /// ```
+            pub const fn from_str_alphabet(src: &str, alphabet: &Alphabet) -> Result<Self, ParseIntError> {
+                let src = src.as_bytes();
+                let radix = alphabet.radix() as $Digit;
+                let mut out = Self::ZERO;
+                let mut empty = true;
+                let mut i = 0;
+                while i < src.len() {
+                    if !alphabet.is_ignored(src[i]) {
+                        let digit = match alphabet.digit(src[i]) {
+                            Some(digit) => digit,
+                            None => {
+                                return Err(ParseIntError::invalid_digit(src, i, Self::BITS))
+                            }
+                        };
+                        empty = false;
+                        out = match out.checked_mul_add_digit(radix, digit as $Digit) {
+                            Some(out) => out,
+                            None => {
+                                return Err(ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS))
+                            }
+                        };
+                    }
+                    i += 1;
+                }
+                if empty {
+                    return Err(ParseIntError::new(IntErrorKind::Empty, Self::BITS));
+                }
+                Ok(out)
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::alphabet::Alphabet;
+                use crate::test::types::big_types::$Digit::*;
+
+                const ALPHABETS: [Alphabet; 7] = [
+                    Alphabet::BASE58_BITCOIN,
+                    Alphabet::BASE58_FLICKR,
+                    Alphabet::BASE32,
+                    Alphabet::BASE32_HEX,
+                    Alphabet::BASE32_CROCKFORD,
+                    Alphabet::BASE64,
+                    Alphabet::BASE64_URL,
+                ];
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_from_to_str_alphabet(u: crate::test::types::utest) -> bool {
+                        let u = UTEST::from(u);
+                        ALPHABETS.iter().all(|alphabet| {
+                            let s = u.to_str_alphabet(alphabet);
+                            let mut buf = [0; 130];
+                            u.write_str_alphabet(&mut buf, alphabet) == Ok(s.as_str())
+                                && UTEST::from_str_alphabet(&s, alphabet) == Ok(u)
+                                && s.len() == u.to_radix_be(alphabet.radix()).len()
+                        })
+                    }
+                }
+
+                #[test]
+                fn str_alphabet() {
+                    use core::num::IntErrorKind;
+
+                    let n = UTEST::from(0x1f2e3du32);
+                    assert_eq!(UTEST::ZERO.to_str_alphabet(&Alphabet::BASE58_BITCOIN), "1");
+                    assert_eq!(UTEST::from(57u8).to_str_alphabet(&Alphabet::BASE58_BITCOIN), "z");
+                    assert_eq!(UTEST::from(58u8).to_str_alphabet(&Alphabet::BASE58_FLICKR), "21");
+                    assert_eq!(n.to_str_alphabet(&Alphabet::BASE64), "Hy49");
+                    assert_eq!(n.to_str_alphabet(&Alphabet::BASE32_HEX), "1UBHT");
+                    assert_eq!(UTEST::from_str_alphabet("1ubht", &Alphabet::BASE32_HEX).unwrap_err().index(), Some(1));
+                    assert_eq!(UTEST::from_str_alphabet("1-UBHT", &Alphabet::BASE32_HEX).unwrap_err().index(), Some(1));
+                    assert_eq!(UTEST::from_str_alphabet("1ojhT", &Alphabet::BASE32_CROCKFORD), UTEST::from_str_alphabet("10JHT", &Alphabet::BASE32_CROCKFORD));
+                    assert_eq!(UTEST::from_str_alphabet("--", &Alphabet::BASE32_CROCKFORD).unwrap_err().kind(), &IntErrorKind::Empty);
+
+                    let max = UTEST::MAX.to_str_alphabet(&Alphabet::BASE64);
+                    let overflow = format!("{}A", max);
+                    assert_eq!(UTEST::from_str_alphabet(&overflow, &Alphabet::BASE64).unwrap_err().kind(), &IntErrorKind::PosOverflow);
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(alphabet);
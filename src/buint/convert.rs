@@ -30,7 +30,7 @@ macro_rules! try_from_iint {
 				#[inline]
 				fn try_from(int: $int) -> Result<Self, Self::Error> {
 					if int.is_negative() {
-						return Err(TryFromIntError(()));
+						return Err(TryFromIntError::new(Self::BITS));
 					}
 					let bits = int as $uint;
 					Ok(Self::from(bits))
@@ -54,7 +54,7 @@ macro_rules! try_from_buint {
 						let small = u.digits[i] as $int;
 						let trunc = small as $Digit;
 						if u.digits[i] != trunc {
-							return Err(TryFromIntError(()));
+							return Err(TryFromIntError::new(<$int>::BITS as ExpType));
 						}
 						out = small;
 						i = 1;
@@ -71,12 +71,12 @@ macro_rules! try_from_buint {
 
 					#[allow(unused_comparisons)]
 					if out < 0 {
-						return Err(TryFromIntError(()));
+						return Err(TryFromIntError::new(<$int>::BITS as ExpType));
 					}
 
 					while i < N {
 						if u.digits[i] != 0 {
-							return Err(TryFromIntError(()));
+							return Err(TryFromIntError::new(<$int>::BITS as ExpType));
 						}
 						i += 1;
 					}
@@ -90,6 +90,7 @@ macro_rules! try_from_buint {
 
 use crate::cast::CastFrom;
 use crate::errors::TryFromIntError;
+use crate::ExpType;
 use crate::nightly::impl_const;
 
 macro_rules! convert {
@@ -153,6 +154,18 @@ macro_rules! convert {
 					function: <utest as TryInto>::try_into,
 					into_types: (u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize)
 				}
+
+				#[test]
+				fn try_from_error_bits() {
+					assert_eq!(UTEST::try_from(-1i8).unwrap_err().bits(), Some(UTEST::BITS));
+					assert_eq!(u16::try_from(UTEST::MAX).unwrap_err().bits(), Some(16));
+					assert_eq!(i128::try_from(UTEST::MAX).unwrap_err().bits(), Some(128));
+
+					let err = u8::try_from(UTEST::MAX).unwrap_err();
+					assert_eq!(format!("{}", err), "(bnum) out of range integral type conversion attempted (target type has 8 bits)");
+					let core_err = core::num::TryFromIntError::from(err);
+					assert_eq!(crate::errors::TryFromIntError::from(core_err).bits(), None);
+				}
 			}
 		}
 	};
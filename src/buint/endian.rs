@@ -1,5 +1,8 @@
 use crate::digit;
 use crate::doc;
+use crate::errors::BufferTooSmallError;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::mem::MaybeUninit;
 
 macro_rules! endian {
@@ -40,6 +43,163 @@ macro_rules! endian {
 				Self::from_le(self)
 			}
 
+			/// Returns the `index`-th least significant byte of the integer. `index` must be less than `Self::BITS / 8`.
+			#[inline]
+			pub(crate) const fn byte(&self, index: usize) -> u8 {
+				let bit = index << 3;
+				(self.digits[bit >> digit::$Digit::BIT_SHIFT] >> (bit & digit::$Digit::BITS_MINUS_1 as usize)) as u8
+			}
+
+			/// Returns the `index`-th least significant byte of the integer when it is extended to any length, with ones if `negative` is true (so that `self` is the bits of a negative signed integer) and with zeros otherwise.
+			#[inline]
+			pub(crate) const fn padded_byte(&self, index: usize, negative: bool) -> u8 {
+				if index < Self::BYTES as usize {
+					self.byte(index)
+				} else if negative {
+					u8::MAX
+				} else {
+					0
+				}
+			}
+
+			/// Returns the length of the shortest two's complement representation of the integer, where `negative` is whether `self` is the bits of a negative signed integer. This is always at least one byte, and includes a sign bit, so may be one byte longer than `Self::BYTES`.
+			#[inline]
+			pub(crate) const fn twos_complement_len(&self, negative: bool) -> usize {
+				let redundant = if negative { self.leading_ones() } else { self.leading_zeros() };
+				(Self::BITS - redundant) as usize / 8 + 1
+			}
+
+			/// Returns the length of the shortest big-endian or little-endian representation of the integer, which is one byte for zero.
+			#[inline]
+			const fn trimmed_len(&self) -> usize {
+				if self.is_zero() {
+					1
+				} else {
+					self.bits().div_ceil(8) as usize
+				}
+			}
+
+			/// Writes the integer into the whole of `out`, padded as by [`padded_byte`](#method.padded_byte), and returns `len`, or returns an error if `out` is shorter than `len` bytes.
+			pub(crate) fn write_padded_bytes(&self, out: &mut [u8], len: usize, negative: bool, big_endian: bool) -> Result<usize, BufferTooSmallError> {
+				if out.len() < len {
+					return Err(BufferTooSmallError { required: len });
+				}
+				let out_len = out.len();
+				for i in 0..out_len {
+					let index = if big_endian { out_len - 1 - i } else { i };
+					out[index] = self.padded_byte(i, negative);
+				}
+				Ok(len)
+			}
+
+			/// Returns the big-endian bytes of the integer without leading zeros. Zero is represented by a single zero byte.
+			///
+			/// Unlike [`to_be_bytes`](#method.to_be_bytes), this method doesn't require the `nightly` feature.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::U256;
+			///
+			/// assert_eq!(U256::from(0x1234u16).to_be_bytes_trimmed(), [0x12, 0x34]);
+			/// assert_eq!(U256::ZERO.to_be_bytes_trimmed(), [0]);
+			/// assert_eq!(U256::MAX.to_be_bytes_trimmed().len(), 32);
+			/// ```
+			#[cfg(feature = "alloc")]
+			#[must_use = doc::must_use_op!()]
+			#[inline]
+			pub fn to_be_bytes_trimmed(&self) -> Vec<u8> {
+				let mut out = vec![0; self.trimmed_len()];
+				let _ = self.write_be_bytes(&mut out);
+				out
+			}
+
+			/// Returns the little-endian bytes of the integer without trailing zeros. Zero is represented by a single zero byte.
+			///
+			/// Unlike [`to_le_bytes`](#method.to_le_bytes), this method doesn't require the `nightly` feature.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::U256;
+			///
+			/// assert_eq!(U256::from(0x1234u16).to_le_bytes_trimmed(), [0x34, 0x12]);
+			/// assert_eq!(U256::ZERO.to_le_bytes_trimmed(), [0]);
+			/// ```
+			#[cfg(feature = "alloc")]
+			#[must_use = doc::must_use_op!()]
+			#[inline]
+			pub fn to_le_bytes_trimmed(&self) -> Vec<u8> {
+				let mut out = vec![0; self.trimmed_len()];
+				let _ = self.write_le_bytes(&mut out);
+				out
+			}
+
+			/// Writes the big-endian bytes of the integer into the whole of `out`, padded with zeros at the start, and returns the length of the integer's big-endian bytes without leading zeros (which is one for zero). `out` can have any length, including more than `Self::BYTES`.
+			///
+			/// # Errors
+			///
+			/// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `out` is too short to hold the integer's bytes without leading zeros. In this case, `out` isn't modified.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::U256;
+			///
+			/// let mut out = [0xff; 4];
+			/// assert_eq!(U256::from(0x1234u16).write_be_bytes(&mut out), Ok(2));
+			/// assert_eq!(out, [0, 0, 0x12, 0x34]);
+			///
+			/// let mut out = [0; 40];
+			/// assert_eq!(U256::MAX.write_be_bytes(&mut out), Ok(32));
+			/// assert_eq!(out[..8], [0; 8]);
+			/// assert!(U256::MAX.write_be_bytes(&mut [0; 31]).is_err());
+			/// ```
+			#[inline]
+			pub fn write_be_bytes(&self, out: &mut [u8]) -> Result<usize, BufferTooSmallError> {
+				self.write_padded_bytes(out, self.trimmed_len(), false, true)
+			}
+
+			/// Writes the little-endian bytes of the integer into the whole of `out`, padded with zeros at the end, and returns the length of the integer's little-endian bytes without trailing zeros (which is one for zero). `out` can have any length, including more than `Self::BYTES`.
+			///
+			/// # Errors
+			///
+			/// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `out` is too short to hold the integer's bytes without trailing zeros. In this case, `out` isn't modified.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::U256;
+			///
+			/// let mut out = [0xff; 4];
+			/// assert_eq!(U256::from(0x1234u16).write_le_bytes(&mut out), Ok(2));
+			/// assert_eq!(out, [0x34, 0x12, 0, 0]);
+			/// ```
+			#[inline]
+			pub fn write_le_bytes(&self, out: &mut [u8]) -> Result<usize, BufferTooSmallError> {
+				self.write_padded_bytes(out, self.trimmed_len(), false, false)
+			}
+
+			/// Returns a view of the memory of the integer as a byte slice of length `Self::BYTES`, without copying. The slice consists of the integer's digits, least significant first, each in native byte order. On little-endian targets, this is the integer's little-endian bytes.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::U256;
+			///
+			/// let n = U256::from(0x1234u16);
+			/// let bytes = n.as_ne_bytes();
+			/// assert_eq!(bytes.len(), 32);
+			/// #[cfg(target_endian = "little")]
+			/// assert_eq!(bytes[..2], [0x34, 0x12]);
+			/// ```
+			#[must_use]
+			#[inline]
+			pub const fn as_ne_bytes(&self) -> &[u8] {
+				// SAFETY: `self.digits` is an array of `Self::BYTES` initialised bytes, which can be read as `u8`s
+				unsafe { core::slice::from_raw_parts(self.digits.as_ptr() as *const u8, Self::BYTES as usize) }
+			}
+
 			crate::nightly::const_fns! {
 				/// Create an integer value from a slice of bytes in big endian. The value is wrapped in an `Option` as the integer represented by the slice of bytes may represent an integer too large to be represented by the type.
 				///
@@ -299,6 +459,31 @@ macro_rules! endian {
 				use crate::test::{test_bignum, types::utest};
 
 				crate::int::endian::tests!($Digit; utest);
+
+				quickcheck::quickcheck! {
+					fn quickcheck_trimmed_bytes(u: utest) -> bool {
+						let big_u = UTEST::from(u);
+						let be = u.to_be_bytes();
+						let trimmed = &be[(u.leading_zeros() as usize / 8).min(15)..];
+						let mut le = trimmed.to_vec();
+						le.reverse();
+
+						let mut padded = [0xaa; 20];
+						let written = big_u.write_be_bytes(&mut padded);
+						let mut padded_le = [0xaa; 20];
+						let written_le = big_u.write_le_bytes(&mut padded_le);
+						big_u.to_be_bytes_trimmed() == trimmed
+							&& big_u.to_le_bytes_trimmed() == le
+							&& written == Ok(trimmed.len())
+							&& padded[..4] == [0; 4]
+							&& padded[4..] == be
+							&& written_le == Ok(trimmed.len())
+							&& padded_le[..16] == u.to_le_bytes()
+							&& big_u.write_be_bytes(&mut [0; 16][..trimmed.len() - 1]).is_err()
+							&& UTEST::from_be_slice(trimmed) == Some(big_u)
+							&& (cfg!(target_endian = "big") || big_u.as_ne_bytes() == u.to_le_bytes())
+					}
+				}
 			}
 		}
 	};
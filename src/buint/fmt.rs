@@ -1,39 +1,286 @@
-use crate::digit;
-use alloc::string::String;
+use crate::fmt::{FixedHex, GroupWriter, Grouped, Grouping, Scaled};
+use crate::int::fmt::{self, CHUNK_DIGITS};
+use crate::ExpType;
 use core::fmt::Write;
 use core::fmt::{Binary, Debug, Display, Formatter, LowerExp, LowerHex, Octal, UpperExp, UpperHex};
 
 macro_rules! fmt {
 	($BUint: ident, $BInt: ident, $Digit: ident) => {
-		macro_rules! fmt_method {
-			($format: expr, $format_pad: expr, $pad: expr, $prefix: expr) => {
-				#[inline]
-				fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-					let mut format_string = String::new();
-					for digit in self.digits.iter().rev() {
-						if format_string.is_empty() {
-							if digit != &0 {
-								write!(format_string, $format, digit)?;
+		impl<const N: usize> $BUint<N> {
+			/// Writes the digits of the integer in the given radix to `f`, most significant first, padded with leading zeros to at least `min_len` digits. Integers with more than `CHUNK_DIGITS` digits are written in chunks, so that only a fixed size stack buffer is needed.
+			fn write_digits<W: Write>(self, radix: u32, uppercase: bool, min_len: usize, f: &mut W) -> core::fmt::Result {
+				let mut buf = [0u8; CHUNK_DIGITS];
+				let len = self.radix_digits_len(radix);
+				if min_len > len {
+					fmt::write_repeated(f, '0', min_len - len)?;
+				}
+				if len <= CHUNK_DIGITS {
+					return f.write_str(self.fill_str_radix(radix, uppercase, &mut buf[..len]));
+				}
+				// each chunk except the first has exactly `CHUNK_DIGITS` digits, so `chunk_base` is at most `self` and can't overflow
+				let chunks = len.div_ceil(CHUNK_DIGITS);
+				let chunk_base = Self::from_digit(radix as $Digit).pow(CHUNK_DIGITS as ExpType);
+				let mut divisor = chunk_base.pow(chunks as ExpType - 1);
+				let mut rest = self;
+				for i in 0..chunks {
+					let (chunk, rem) = rest.div_rem(divisor);
+					let chunk_len = chunk.radix_digits_len(radix);
+					if i != 0 {
+						fmt::write_repeated(f, '0', CHUNK_DIGITS - chunk_len)?;
+					}
+					f.write_str(chunk.fill_str_radix(radix, uppercase, &mut buf[..chunk_len]))?;
+					rest = rem;
+					divisor = divisor.div_rem(chunk_base).0;
+				}
+				Ok(())
+			}
+
+			pub(crate) fn fmt_radix(self, radix: u32, uppercase: bool, is_nonnegative: bool, prefix: &str, f: &mut Formatter) -> core::fmt::Result {
+				let len = self.radix_digits_len(radix);
+				fmt::pad_integral(f, is_nonnegative, prefix, len, |f| self.write_digits(radix, uppercase, 0, f))
+			}
+
+			pub(crate) fn fmt_radix_grouped(self, radix: u32, uppercase: bool, is_nonnegative: bool, prefix: &str, grouping: Grouping, f: &mut Formatter) -> core::fmt::Result {
+				let len = self.radix_digits_len(radix);
+				// zero padding is written as leading zero digits, so that it is grouped too
+				let mut padded_len = len;
+				if let (Some(width), true) = (f.width(), f.sign_aware_zero_pad()) {
+					let sign_len = (!is_nonnegative || f.sign_plus()) as usize;
+					let prefix_len = if f.alternate() { prefix.len() } else { 0 };
+					while sign_len + prefix_len + grouping.grouped_len(padded_len) < width {
+						padded_len += 1;
+					}
+				}
+				fmt::pad_integral(f, is_nonnegative, prefix, grouping.grouped_len(padded_len), |f| {
+					let mut writer = GroupWriter::new(f, padded_len, grouping);
+					fmt::write_repeated(&mut writer, '0', padded_len - len)?;
+					self.write_digits(radix, uppercase, 0, &mut writer)
+				})
+			}
+
+			/// Writes the integer divided by `10^decimals` as a decimal number with exactly `precision` fractional digits, rounding ties to even. A minus sign is only written if the rounded value is nonzero.
+			pub(crate) fn fmt_scaled(self, decimals: u32, precision: u32, is_nonnegative: bool, f: &mut Formatter) -> core::fmt::Result {
+				let value = if precision < decimals {
+					let shift = (decimals - precision) as ExpType;
+					match Self::TEN.checked_pow(shift) {
+						Some(divisor) => {
+							let (quotient, rem) = self.div_rem(divisor);
+							let rest = divisor - rem;
+							if rem > rest || (rem == rest && quotient.digits[0] % 2 == 1) {
+								quotient + Self::ONE
+							} else {
+								quotient
 							}
-						} else {
-							write!(format_string, $format_pad, digit, $pad)?;
 						}
-					}
-					f.pad_integral(
-						true,
-						$prefix,
-						if format_string.is_empty() {
-							"0"
-						} else {
-							&format_string
+						// the quotient is zero, and it rounds up to one exactly when `self` is more than half of the divisor
+						None => match Self::TEN.checked_pow(shift - 1).and_then(|n| n.checked_mul(Self::FIVE)) {
+							Some(half) if self > half => Self::ONE,
+							_ => Self::ZERO,
 						},
-					)
+					}
+				} else {
+					self
+				};
+				let fraction_len = if precision < decimals { precision } else { decimals };
+				let (int, fraction) = match Self::TEN.checked_pow(fraction_len as ExpType) {
+					Some(divisor) => value.div_rem(divisor),
+					None => (Self::ZERO, value),
+				};
+				let len = int.radix_digits_len(10) + if precision == 0 { 0 } else { 1 + precision as usize };
+				let is_nonnegative = is_nonnegative || value.is_zero();
+
+				fmt::pad_integral(f, is_nonnegative, "", len, |f| {
+					int.write_digits(10, false, 0, f)?;
+					if precision != 0 {
+						f.write_char('.')?;
+						if fraction_len != 0 {
+							fraction.write_digits(10, false, fraction_len as usize, f)?;
+						}
+						fmt::write_repeated(f, '0', (precision - fraction_len) as usize)?;
+					}
+					Ok(())
+				})
+			}
+
+			/// Returns a wrapper around the integer which, when formatted, writes it divided by `10^decimals` as a decimal number with exactly `precision` digits after the decimal point.
+			///
+			/// If `precision` is less than `decimals`, the number is rounded to the nearest value with `precision` fractional digits, with ties rounded to even. If `precision` is zero, no decimal point is written. Formatting with `precision` equal to `decimals` is the inverse of [`from_decimal_str_scaled`](#method.from_decimal_str_scaled).
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::U256;
+			///
+			/// let n = U256::from(12345000000000000000u64);
+			/// assert_eq!(format!("{}", n.format_scaled(18, 2)), "12.34");
+			/// assert_eq!(format!("{}", n.format_scaled(18, 4)), "12.3450");
+			/// assert_eq!(format!("{}", U256::from(15u8).format_scaled(1, 0)), "2");
+			/// assert_eq!(format!("{:>8}", U256::from(5u8).format_scaled(3, 3)), "   0.005");
+			/// ```
+			#[inline]
+			pub const fn format_scaled(self, decimals: u32, precision: u32) -> Scaled<Self> {
+				Scaled {
+					value: self,
+					decimals,
+					precision,
+				}
+			}
+
+			pub(crate) fn fmt_hex_fixed(self, uppercase: bool, f: &mut Formatter) -> core::fmt::Result {
+				let len = Self::BITS as usize / 4;
+				fmt::pad_integral(f, true, "0x", len, |f| self.write_digits(16, uppercase, len, f))
+			}
+
+			/// Returns a wrapper around the integer which, when formatted, writes it in hexadecimal with all `Self::BITS / 4` digits, including leading zeros.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::U128;
+			///
+			/// let n = U128::from(0xbeefu16);
+			/// assert_eq!(format!("{}", n.to_hex_fixed()), "0000000000000000000000000000beef");
+			/// assert_eq!(format!("{:#X}", n.to_hex_fixed()), "0x0000000000000000000000000000BEEF");
+			/// ```
+			#[inline]
+			pub const fn to_hex_fixed(self) -> FixedHex<Self> {
+				FixedHex { value: self }
+			}
+
+			/// Returns a wrapper around the integer which, when formatted, separates its digits into groups of `size` digits with `separator`, counting from the least significant digit.
+			///
+			/// # Panics
+			///
+			/// This function panics if `size` is zero.
+			///
+			/// # Examples
+			///
+			/// ```
+			/// use bnum::types::U256;
+			///
+			/// let n = U256::from(0xdeadbeefu32);
+			/// assert_eq!(format!("{}", n.grouped(3, ',')), "3,735,928,559");
+			/// assert_eq!(format!("{:#x}", n.grouped(4, '_')), "0xdead_beef");
+			/// assert_eq!(format!("{:>16}", n.grouped(3, ',')), "   3,735,928,559");
+			/// ```
+			#[inline]
+			pub const fn grouped(self, size: usize, separator: char) -> Grouped<Self> {
+				self.grouped_with(Grouping::new(size, separator))
+			}
+
+			/// Returns a wrapper around the integer which, when formatted, separates its digits into groups as described by `grouping`.
+			///
+			/// For examples, see the [`Grouping`](crate::fmt::Grouping) documentation.
+			#[inline]
+			pub const fn grouped_with(self, grouping: Grouping) -> Grouped<Self> {
+				Grouped {
+					value: self,
+					grouping,
+				}
+			}
+
+			/// Writes the integer in scientific notation, following the formatting rules of the primitive integers.
+			///
+			/// If the formatter has a precision, the significand is rounded to that many fractional digits, with ties rounded to even. Otherwise, trailing zeros of the significand are omitted.
+			pub(crate) fn fmt_exp(self, is_nonnegative: bool, exp_char: char, f: &mut Formatter) -> core::fmt::Result {
+				let mut exp = self.radix_digits_len(10) - 1;
+				let (significand, extra_zeros) = match f.precision() {
+					Some(precision) if precision < exp => {
+						let divisor = Self::TEN.pow((exp - precision) as ExpType);
+						let (mut significand, rem) = self.div_rem(divisor);
+						let rest = divisor - rem;
+						if rem > rest || (rem == rest && significand.digits[0] % 2 == 1) {
+							significand += Self::ONE;
+							if significand.radix_digits_len(10) > precision + 1 {
+								// every kept digit was a 9, so the significand rounds up to the next power of ten
+								significand = significand.div_rem_digit(10).0;
+								exp += 1;
+							}
+						}
+						(significand, 0)
+					}
+					Some(precision) => (self, precision - exp),
+					None => {
+						let mut significand = self;
+						while !significand.is_zero() {
+							let (q, r) = significand.div_rem_digit(10);
+							if r != 0 {
+								break;
+							}
+							significand = q;
+						}
+						(significand, 0)
+					}
+				};
+				let significand_len = significand.radix_digits_len(10);
+				let fraction_len = significand_len - 1 + extra_zeros;
+				let len = significand_len + (fraction_len != 0) as usize + extra_zeros + 1 + fmt::decimal_len(exp);
+
+				fmt::pad_integral(f, is_nonnegative, "", len, |f| {
+					let (leading, rest) = significand.div_rem(Self::TEN.pow(significand_len as ExpType - 1));
+					write!(f, "{}", leading.digits[0])?;
+					if fraction_len != 0 {
+						f.write_char('.')?;
+						if significand_len > 1 {
+							rest.write_digits(10, false, significand_len - 1, f)?;
+						}
+						fmt::write_repeated(f, '0', extra_zeros)?;
+					}
+					write!(f, "{}{}", exp_char, exp)
+				})
+			}
+		}
+
+		macro_rules! grouped_fmt {
+			($trait: ident, $radix: literal, $uppercase: literal, $prefix: literal) => {
+				impl<const N: usize> $trait for Grouped<$BUint<N>> {
+					#[inline]
+					fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+						self.value.fmt_radix_grouped($radix, $uppercase, true, $prefix, self.grouping, f)
+					}
 				}
 			};
 		}
 
+		grouped_fmt!(Binary, 2, false, "0b");
+		grouped_fmt!(Display, 10, false, "");
+		grouped_fmt!(LowerHex, 16, false, "0x");
+		grouped_fmt!(Octal, 8, false, "0o");
+		grouped_fmt!(UpperHex, 16, true, "0x");
+
+		impl<const N: usize> Display for Scaled<$BUint<N>> {
+			#[inline]
+			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+				self.value.fmt_scaled(self.decimals, self.precision, true, f)
+			}
+		}
+
+		impl<const N: usize> Display for FixedHex<$BUint<N>> {
+			#[inline]
+			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+				self.value.fmt_hex_fixed(false, f)
+			}
+		}
+
+		impl<const N: usize> LowerHex for FixedHex<$BUint<N>> {
+			#[inline]
+			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+				self.value.fmt_hex_fixed(false, f)
+			}
+		}
+
+		impl<const N: usize> UpperHex for FixedHex<$BUint<N>> {
+			#[inline]
+			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+				self.value.fmt_hex_fixed(true, f)
+			}
+		}
+
 		impl<const N: usize> Binary for $BUint<N> {
-			fmt_method!("{:b}", "{:01$b}", digit::$Digit::BITS as usize, "0b");
+			#[inline]
+			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+				self.fmt_radix(2, false, true, "0b", f)
+			}
 		}
 
 		impl<const N: usize> Debug for $BUint<N> {
@@ -46,58 +293,43 @@ macro_rules! fmt {
 		impl<const N: usize> Display for $BUint<N> {
 			#[inline]
 			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-				write!(f, "{}", self.to_str_radix(10))
+				self.fmt_radix(10, false, true, "", f)
 			}
 		}
 
-		macro_rules! exp_fmt {
-			($e: expr) => {
-				#[inline]
-				fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-					let decimal_str = format!("{}", self);
-					if decimal_str == "0" {
-						return write!(f, "{}{}0", 0, $e);
-					}
-					let exp = decimal_str.len() - 1;
-					let decimal_str = decimal_str.trim_end_matches('0');
-					if decimal_str.len() == 1 {
-						write!(f, "{}{}{}", &decimal_str[0..1], $e, exp)
-					} else {
-						write!(
-							f,
-							"{}.{}{}{}",
-							&decimal_str[0..1],
-							&decimal_str[1..],
-							$e,
-							exp
-						)
-					}
-				}
-			};
-		}
-
 		impl<const N: usize> LowerExp for $BUint<N> {
-			exp_fmt!("e");
+			#[inline]
+			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+				self.fmt_exp(true, 'e', f)
+			}
 		}
 
 		impl<const N: usize> LowerHex for $BUint<N> {
-			fmt_method!("{:x}", "{:01$x}", digit::$Digit::HEX_PADDING, "0x");
+			#[inline]
+			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+				self.fmt_radix(16, false, true, "0x", f)
+			}
 		}
 
 		impl<const N: usize> Octal for $BUint<N> {
 			#[inline]
 			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
-				let string = self.to_str_radix(8);
-				f.pad_integral(true, "0o", &string)
+				self.fmt_radix(8, false, true, "0o", f)
 			}
 		}
 
 		impl<const N: usize> UpperExp for $BUint<N> {
-			exp_fmt!("E");
+			#[inline]
+			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+				self.fmt_exp(true, 'E', f)
+			}
 		}
 
 		impl<const N: usize> UpperHex for $BUint<N> {
-			fmt_method!("{:X}", "{:01$X}", digit::$Digit::HEX_PADDING, "0x");
+			#[inline]
+			fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+				self.fmt_radix(16, true, true, "0x", f)
+			}
 		}
 
 		#[cfg(test)]
@@ -105,6 +337,34 @@ macro_rules! fmt {
 			mod [<$Digit _digit_tests>] {
 				use crate::test::types::big_types::$Digit::*;
 				crate::int::fmt::tests!(utest);
+
+				#[test]
+				fn grouped_zero_pad() {
+					let n = UTEST::from(1234567u32);
+					assert_eq!(format!("{:012}", n.grouped(3, ',')), "0,001,234,567");
+					assert_eq!(format!("{:011}", n.grouped(3, ',')), "001,234,567");
+					assert_eq!(format!("{:09}", n.grouped(3, ',')), "1,234,567");
+					assert_eq!(format!("{:+#014x}", UTEST::from(0xabcdefu32).grouped(2, '_')), "+0x00_ab_cd_ef");
+					assert_eq!(format!("{:08}", UTEST::ZERO.grouped_with(crate::fmt::Grouping::indian(','))), "0,00,000");
+				}
+
+				#[test]
+				fn format_chunked() {
+					let src = "9347593745923745973982750198247027365123545423765432167543287658732465873465187326871543287654187263458716428734618726458172365123785123908561293856801398560139485691328";
+					let u = crate::$BUint::<{ 6400 / $Digit::BITS as usize }>::from_str_radix(src, 10).unwrap().pow(6);
+					for radix in [2, 8, 10, 16] {
+						assert!(u.to_str_radix(radix).len() > crate::int::fmt::CHUNK_DIGITS);
+					}
+					assert_eq!(format!("{}", u), u.to_str_radix(10));
+					assert_eq!(format!("{:b}", u), u.to_str_radix(2));
+					assert_eq!(format!("{:o}", u), u.to_str_radix(8));
+					assert_eq!(format!("{:x}", u), u.to_str_radix(16));
+					assert_eq!(format!("{:X}", u), u.to_str_radix(16).to_uppercase());
+					assert_eq!(format!("{:>1200}", u), format!("{:>1200}", u.to_str_radix(10)));
+
+					let u = crate::$BUint::<{ 6400 / $Digit::BITS as usize }>::from_str_radix(src, 10).unwrap() * crate::$BUint::<{ 6400 / $Digit::BITS as usize }>::TEN.pow(400);
+					assert_eq!(format!("{:e}", u), format!("{}e{}", src.replacen("9", "9.", 1).trim_end_matches('0'), src.len() + 399));
+				}
 			}
 		}
 	};
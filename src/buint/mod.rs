@@ -7,12 +7,6 @@ use crate::nightly::const_fn;
 use crate::ExpType;
 use core::mem::MaybeUninit;
 
-#[cfg(feature = "serde")]
-use ::{
-    serde::{Deserialize, Serialize},
-    serde_big_array::BigArray,
-};
-
 use core::default::Default;
 
 use core::iter::{Iterator, Product, Sum};
@@ -27,14 +21,16 @@ macro_rules! mod_impl {
 		#[doc = concat!("`", stringify!($Digit), "`")]
 		/// digits that are stored.
 		///
+		/// This type is `#[repr(transparent)]`, so has the same memory layout as an array of `N` of its digits.
+		///
 		#[doc = doc::arithmetic_doc!($BUint)]
 
 		// Clippy: we can allow derivation of `Hash` and manual implementation of `PartialEq` as the derived `PartialEq` would be the same except we make our implementation const.
 		#[allow(clippy::derive_hash_xor_eq)]
 		#[derive(Clone, Copy, Hash)]
-		#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+		#[cfg_attr(feature = "zerocopy", derive(zerocopy::FromBytes, zerocopy::IntoBytes, zerocopy::KnownLayout, zerocopy::Immutable))]
+		#[repr(transparent)]
 		pub struct $BUint<const N: usize> {
-			#[cfg_attr(feature = "serde", serde(with = "BigArray"))]
 			pub(crate) digits: [$Digit; N],
 		}
 
@@ -671,6 +667,7 @@ macro_rules! mod_impl {
 
 crate::main_impl!(mod_impl);
 
+mod alphabet;
 mod consts;
 mod bigint_helpers;
 mod cast;
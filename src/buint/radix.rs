@@ -9,10 +9,12 @@ The original license file and copyright notice for `num_bigint` can be found in
 
 use crate::digit;
 use crate::doc;
-use crate::errors::ParseIntError;
+use crate::errors::{BufferTooSmallError, ParseIntError};
 use crate::int::radix::assert_range;
 use crate::ExpType;
+#[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use core::iter::Iterator;
 use core::num::IntErrorKind;
@@ -23,13 +25,36 @@ const fn ilog2(a: u32) -> u8 {
     31 - a.leading_zeros() as u8
 }
 
-#[inline]
-const fn div_ceil(a: ExpType, b: ExpType) -> ExpType {
-    if a % b == 0 {
-        a / b
-    } else {
-        (a / b) + 1
+/// Returns whether `src[start..]` is `bits` written in decimal, without leading zeros.
+const fn is_bits_suffix(src: &[u8], start: usize, bits: ExpType) -> bool {
+    if start >= src.len() || src[start] == b'0' {
+        return false;
+    }
+    let mut value: ExpType = 0;
+    let mut i = start;
+    while i < src.len() {
+        if !src[i].is_ascii_digit() {
+            return false;
+        }
+        value = match value.checked_mul(10) {
+            Some(value) => match value.checked_add((src[i] - b'0') as ExpType) {
+                Some(value) => value,
+                None => return false,
+            },
+            None => return false,
+        };
+        i += 1;
     }
+    value == bits
+}
+
+/// Returns the index of the first byte of `src` at or after `start` which is not an ASCII decimal digit.
+const fn decimal_digits_end(src: &[u8], start: usize) -> usize {
+    let mut i = start;
+    while i < src.len() && src[i].is_ascii_digit() {
+        i += 1;
+    }
+    i
 }
 
 macro_rules! radix {
@@ -307,23 +332,26 @@ macro_rules! radix {
             /// ```
             pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
                 assert_range!(radix, 36);
+                let full_src = src.as_bytes();
                 let mut src = src;
+                let mut offset = 0;
                 if src.starts_with('+') {
                     src = &src[1..];
+                    offset = 1;
                 }
                 if src.is_empty() {
-                    return Err(ParseIntError {
-                        kind: IntErrorKind::Empty,
-                    });
+                    if offset == 1 {
+                        // a sign on its own is an invalid digit, as for the primitive integers
+                        return Err(ParseIntError::invalid_digit(full_src, 0, Self::BITS));
+                    }
+                    return Err(ParseIntError::new(IntErrorKind::Empty, Self::BITS));
                 }
                 let buf = src.as_bytes();
                 let validate_src = || -> Result<&[u8], ParseIntError> {
                     let radix = radix as u8;
-                    for &byte in buf {
+                    for (i, &byte) in buf.iter().enumerate() {
                         if Self::byte_to_digit(byte) >= radix {
-                            return Err(ParseIntError {
-                                kind: IntErrorKind::InvalidDigit,
-                            });
+                            return Err(ParseIntError::invalid_digit(full_src, offset + i, Self::BITS));
                         }
                     }
                     Ok(buf)
@@ -335,17 +363,13 @@ macro_rules! radix {
                         let iter = buf
                             .rchunks((digit::$Digit::BITS_U8 / bits) as usize)
                             .map(|chunk| chunk.iter().map(|byte| Self::byte_to_digit(*byte)));
-                        Self::from_bitwise_digits_le(iter, bits).ok_or(ParseIntError {
-                            kind: IntErrorKind::PosOverflow,
-                        })
+                        Self::from_bitwise_digits_le(iter, bits).ok_or(ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS))
                     }
                     8 | 32 => {
                         let bits = ilog2(radix);
                         let buf = validate_src()?;
                         let iter = buf.iter().rev().map(|byte| Self::byte_to_digit(*byte));
-                        Self::from_inexact_bitwise_digits_le(iter, bits).ok_or(ParseIntError {
-                            kind: IntErrorKind::PosOverflow,
-                        })
+                        Self::from_inexact_bitwise_digits_le(iter, bits).ok_or(ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS))
                     }
                     radix => {
                         let (base, power) = Self::radix_base(radix);
@@ -358,14 +382,353 @@ macro_rules! radix {
                             .chunks(power)
                             .map(|chunk| chunk.iter().map(|byte| Self::byte_to_digit(*byte)));
                         Self::from_radix_digits_be(head, tail, radix as u32, base).ok_or(
-                            ParseIntError {
-                                kind: IntErrorKind::PosOverflow,
-                            },
+                            ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS),
                         )
                     }
                 }
             }
 
+            /// Returns `self * mul + add`, or `None` if overflow occurred.
+            pub(crate) const fn checked_mul_add_digit(self, mul: $Digit, add: $Digit) -> Option<Self> {
+                let mut out = self;
+                let mut carry = add;
+                let mut i = 0;
+                while i < N {
+                    let (low, high) = digit::$Digit::carrying_mul(out.digits[i], mul, carry, 0);
+                    out.digits[i] = low;
+                    carry = high;
+                    i += 1;
+                }
+                if carry == 0 {
+                    Some(out)
+                } else {
+                    None
+                }
+            }
+
+            /// Parses the magnitude of a Rust-style integer literal that starts at byte index `start` of `src`. This is an optional `0x`, `0o` or `0b` prefix, followed by digits with optional `_` separators, followed by an optional type suffix made of the byte `suffix` and `bits` written in decimal.
+            pub(crate) const fn parse_literal_magnitude(src: &[u8], start: usize, suffix: u8, bits: ExpType) -> Result<Self, ParseIntError> {
+                let (radix, digits_start) = if src.len() >= start + 2 && src[start] == b'0' {
+                    match src[start + 1] {
+                        b'x' => (16, start + 2),
+                        b'o' => (8, start + 2),
+                        b'b' => (2, start + 2),
+                        _ => (10, start),
+                    }
+                } else {
+                    (10, start)
+                };
+                // `suffix` is never a valid digit in any of the supported radices, so its first occurrence marks the start of the type suffix
+                let mut end = digits_start;
+                while end < src.len() && src[end] != suffix {
+                    end += 1;
+                }
+                if end < src.len() && !is_bits_suffix(src, end + 1, bits) {
+                    return Err(ParseIntError::invalid_digit(src, end, Self::BITS));
+                }
+                if radix == 10 && digits_start < src.len() && src[digits_start] == b'_' {
+                    // as in Rust, an unprefixed literal can't start with a separator
+                    return Err(ParseIntError::invalid_digit(src, digits_start, Self::BITS));
+                }
+                Self::parse_digits(src, digits_start, end, radix, true)
+            }
+
+            /// Parses `src[start..end]` as digits in the given radix, one at a time, so that this can be done in a const context. If `separators` is true, `_` characters are skipped. As with `from_str_radix`, an invalid digit is reported even if it comes after the value has overflowed.
+            pub(crate) const fn parse_digits(src: &[u8], start: usize, end: usize, radix: u32, separators: bool) -> Result<Self, ParseIntError> {
+                let mut out = Self::ZERO;
+                let mut empty = true;
+                let mut overflow = false;
+                let mut i = start;
+                while i < end {
+                    let byte = src[i];
+                    if !(separators && byte == b'_') {
+                        let digit = Self::byte_to_digit(byte);
+                        if digit as u32 >= radix {
+                            return Err(ParseIntError::invalid_digit(src, i, Self::BITS));
+                        }
+                        empty = false;
+                        if !overflow {
+                            match out.checked_mul_add_digit(radix as $Digit, digit as $Digit) {
+                                Some(next) => out = next,
+                                None => overflow = true,
+                            }
+                        }
+                    }
+                    i += 1;
+                }
+                if empty {
+                    return Err(ParseIntError::new(IntErrorKind::Empty, Self::BITS));
+                }
+                if overflow {
+                    return Err(ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS));
+                }
+                Ok(out)
+            }
+
+            /// Converts a string slice in a given base to an integer, in a const context.
+            ///
+            /// This method accepts the same input as [`from_str_radix`](#method.from_str_radix), and returns the same result, but is `const` (without needing the `nightly` feature), so can be used to define constants. [`from_str_radix`](#method.from_str_radix) should be preferred outside of const contexts, as it is faster for large inputs.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 36 inclusive.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// const P: U256 = match U256::parse_const("115792089237316195423570985008687907853269984665640564039457584007908834671663", 10) {
+            ///     Ok(p) => p,
+            ///     Err(_) => panic!("invalid prime"),
+            /// };
+            /// assert_eq!(P, U256::MAX - U256::from(0x1000003d0u64));
+            /// assert!(U256::parse_const("12a", 10).is_err());
+            /// ```
+            pub const fn parse_const(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+                if radix < 2 || radix > 36 {
+                    panic!(crate::errors::err_msg!("Radix must be in range [2, 36]"));
+                }
+                let src = src.as_bytes();
+                let start = match src {
+                    [b'+'] => return Err(ParseIntError::invalid_digit(src, 0, Self::BITS)),
+                    [b'+', ..] => 1,
+                    _ => 0,
+                };
+                Self::parse_digits(src, start, src.len(), radix, false)
+            }
+
+            /// Parses the decimal number that starts at byte index `start` of `src` and returns it multiplied by `10^scale`. The number is digits with an optional fractional part after a `.`, followed, if `exponent` is true, by an optional exponent. Digits that can't be represented because they would be in the fractional part of the result must be zero.
+            pub(crate) const fn parse_scaled(src: &[u8], start: usize, scale: u32, exponent: bool) -> Result<Self, ParseIntError> {
+                if start == src.len() {
+                    return Err(ParseIntError::new(IntErrorKind::Empty, Self::BITS));
+                }
+                let int_end = decimal_digits_end(src, start);
+                let (frac_start, frac_end) = if int_end < src.len() && src[int_end] == b'.' {
+                    (int_end + 1, decimal_digits_end(src, int_end + 1))
+                } else {
+                    (int_end, int_end)
+                };
+                if int_end == start && frac_end == frac_start {
+                    return Err(ParseIntError::invalid_digit(src, start, Self::BITS));
+                }
+                // the value is the digits of the mantissa, with the decimal point moved right by `shift_up` and left by `shift_down` places
+                let mut shift_up = scale as usize;
+                let mut shift_down = 0;
+                let mut i = frac_end;
+                if exponent && i < src.len() && (src[i] == b'e' || src[i] == b'E') {
+                    let exp_char = i;
+                    i += 1;
+                    let negative = i < src.len() && src[i] == b'-';
+                    if i < src.len() && (src[i] == b'-' || src[i] == b'+') {
+                        i += 1;
+                    }
+                    let exp_end = decimal_digits_end(src, i);
+                    if exp_end == i {
+                        return Err(ParseIntError::invalid_digit(src, if i < src.len() { i } else { exp_char }, Self::BITS));
+                    }
+                    // an exponent this large either overflows or loses digits anyway, unless the mantissa is zero
+                    let mut exp: usize = 0;
+                    while i < exp_end {
+                        exp = exp.saturating_mul(10).saturating_add((src[i] - b'0') as usize);
+                        i += 1;
+                    }
+                    if negative {
+                        shift_down = exp;
+                    } else {
+                        shift_up = shift_up.saturating_add(exp);
+                    }
+                }
+                if i != src.len() {
+                    return Err(ParseIntError::invalid_digit(src, i, Self::BITS));
+                }
+
+                let int_len = int_end - start;
+                let len = int_len + frac_end - frac_start;
+                let kept = int_len.saturating_add(shift_up).saturating_sub(shift_down);
+                let mut out = Self::ZERO;
+                let mut k = 0;
+                while k < len {
+                    let index = if k < int_len { start + k } else { frac_start + k - int_len };
+                    let digit = src[index] - b'0';
+                    if k < kept {
+                        out = match out.checked_mul_add_digit(10, digit as $Digit) {
+                            Some(out) => out,
+                            None => {
+                                return Err(ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS))
+                            }
+                        };
+                    } else if digit != 0 {
+                        return Err(ParseIntError::invalid_digit(src, index, Self::BITS));
+                    }
+                    k += 1;
+                }
+                if !out.is_zero() {
+                    while k < kept {
+                        out = match out.checked_mul_add_digit(10, 0) {
+                            Some(out) => out,
+                            None => {
+                                return Err(ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS))
+                            }
+                        };
+                        k += 1;
+                    }
+                }
+                Ok(out)
+            }
+
+            /// Converts a string slice in decimal scientific notation, such as `1.5e30`, to an integer.
+            ///
+            /// The string is expected to be an optional `+` sign, followed by decimal digits with an optional fractional part after a `.`, followed by an optional exponent, which is `e` or `E` followed by an optional sign and decimal digits. The number it represents must be an integer: nonzero digits which would be after the decimal point once the exponent is applied are rejected, rather than being rounded or truncated.
+            ///
+            /// # Errors
+            ///
+            /// If a digit would be lost, the error has kind [`InvalidDigit`](core::num::IntErrorKind::InvalidDigit) and its [`index`](crate::errors::ParseIntError::index) method returns the byte index of the first such digit. The other errors are the same as for [`from_str_radix`](#method.from_str_radix).
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// assert_eq!(U256::from_sci_str("1.5e30"), Ok(U256::from(15u8) * U256::TEN.pow(29)));
+            /// assert_eq!(U256::from_sci_str("6.02214076E+23"), Ok(U256::from(602214076000000000000000u128)));
+            /// assert_eq!(U256::from_sci_str("1200e-2"), Ok(U256::from(12u8)));
+            /// assert_eq!(U256::from_sci_str("1.25e1").unwrap_err().index(), Some(3));
+            /// ```
+            pub const fn from_sci_str(src: &str) -> Result<Self, ParseIntError> {
+                let src = src.as_bytes();
+                let start = match src {
+                    [b'+', ..] => 1,
+                    _ => 0,
+                };
+                Self::parse_scaled(src, start, 0, true)
+            }
+
+            /// Converts a string slice containing a decimal number with an optional fractional part, such as `12.345`, to an integer equal to that number multiplied by `10^decimals`.
+            ///
+            /// This is the usual way of representing amounts such as token balances, which have a fixed number of decimal places, as integers. The string is expected to be an optional `+` sign, followed by decimal digits with an optional fractional part after a `.`. The fractional part may have at most `decimals` digits, excluding trailing zeros. The inverse of this method is [`format_scaled`](#method.format_scaled).
+            ///
+            /// # Errors
+            ///
+            /// If a digit would be lost, the error has kind [`InvalidDigit`](core::num::IntErrorKind::InvalidDigit) and its [`index`](crate::errors::ParseIntError::index) method returns the byte index of the first such digit. The other errors are the same as for [`from_str_radix`](#method.from_str_radix).
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// assert_eq!(U256::from_decimal_str_scaled("12.345", 18), Ok(U256::from(12345000000000000000u64)));
+            /// assert_eq!(U256::from_decimal_str_scaled("7", 2), Ok(U256::from(700u16)));
+            /// assert_eq!(U256::from_decimal_str_scaled("0.1230", 3), Ok(U256::from(123u8)));
+            /// assert_eq!(U256::from_decimal_str_scaled("0.1234", 3).unwrap_err().index(), Some(5));
+            /// ```
+            pub const fn from_decimal_str_scaled(src: &str, decimals: u32) -> Result<Self, ParseIntError> {
+                let src = src.as_bytes();
+                let start = match src {
+                    [b'+', ..] => 1,
+                    _ => 0,
+                };
+                Self::parse_scaled(src, start, decimals, false)
+            }
+
+            /// Converts a string slice written in the style of a Rust integer literal to an integer.
+            ///
+            /// The string is expected to be an optional `+` sign, followed by an optional `0x`, `0o` or `0b` prefix which selects the radix (the default is decimal), followed by digits which may be separated by `_` characters, followed by an optional type suffix. The type suffix is `u` followed by the number of bits of the integer, e.g. `u256` for a 256-bit unsigned integer.
+            ///
+            /// If the string contains an invalid character, the error's [`index`](crate::errors::ParseIntError::index) method returns its byte index.
+            ///
+            /// This method is `const`, so can be used to define constants. The [`uint`](crate::uint) macro does this at compile time.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// assert_eq!(U256::from_str_prefixed("0xff_ff"), Ok(U256::from(0xffffu16)));
+            /// assert_eq!(U256::from_str_prefixed("+1_000_000u256"), Ok(U256::from(1000000u32)));
+            /// assert_eq!(U256::from_str_prefixed("0b1012").unwrap_err().index(), Some(5));
+            /// assert_eq!(U256::from_str_prefixed("0o17u128").unwrap_err().index(), Some(4));
+            /// ```
+            pub const fn from_str_prefixed(src: &str) -> Result<Self, ParseIntError> {
+                let src = src.as_bytes();
+                let start = match src {
+                    [b'+'] => return Err(ParseIntError::invalid_digit(src, 0, Self::BITS)),
+                    [b'+', ..] => 1,
+                    _ => 0,
+                };
+                Self::parse_literal_magnitude(src, start, b'u', Self::BITS)
+            }
+
+            /// Returns the number of digits of the integer in the given radix.
+            pub(crate) fn radix_digits_len(&self, radix: u32) -> usize {
+                if self.is_zero() {
+                    1
+                } else if radix.is_power_of_two() {
+                    self.bits().div_ceil(ilog2(radix) as ExpType) as usize
+                } else if radix == 10 {
+                    self.ilog10() as usize + 1
+                } else {
+                    self.ilog(Self::from_digit(radix as $Digit)) as usize + 1
+                }
+            }
+
+            /// Writes the digits of the integer in the given radix into `buf` in little-endian digit order. The length of `buf` must be equal to `self.radix_digits_len(radix)`.
+            pub(crate) fn fill_radix_le(self, radix: u32, buf: &mut [u8]) {
+                if $Digit::BITS == 8 && radix == 256 {
+                    for (byte, digit) in buf.iter_mut().zip(self.digits.iter()) {
+                        *byte = *digit as u8; // we can cast to `u8` here as the underlying digit must be a `u8` anyway
+                    }
+                } else if radix.is_power_of_two() {
+                    let bits = ilog2(radix) as usize;
+                    let digit_bits = digit::$Digit::BITS as usize;
+                    let mask: $Digit = (1 << bits) - 1;
+                    for (i, byte) in buf.iter_mut().enumerate() {
+                        let index = i * bits / digit_bits;
+                        let shift = i * bits % digit_bits;
+                        let mut d = self.digits[index] >> shift;
+                        if shift + bits > digit_bits && index + 1 < N {
+                            d |= self.digits[index + 1] << (digit_bits - shift);
+                        }
+                        *byte = (d & mask) as u8;
+                    }
+                } else {
+                    let (base, power) = Self::radix_base_half(radix);
+                    let radix = radix as $Digit;
+                    let mut copy = self;
+                    let mut i = 0;
+                    while copy.last_digit_index() > 0 {
+                        let (q, mut r) = copy.div_rem_digit(base);
+                        for _ in 0..power {
+                            buf[i] = (r % radix) as u8;
+                            r /= radix;
+                            i += 1;
+                        }
+                        copy = q;
+                    }
+                    let mut r = copy.digits[0];
+                    while i < buf.len() {
+                        buf[i] = (r % radix) as u8;
+                        r /= radix;
+                        i += 1;
+                    }
+                }
+            }
+
+            /// Writes the integer as an ASCII string in the given radix into `buf`. The length of `buf` must be equal to `self.radix_digits_len(radix)`.
+            pub(crate) fn fill_str_radix(self, radix: u32, uppercase: bool, buf: &mut [u8]) -> &str {
+                self.fill_radix_le(radix, buf);
+                buf.reverse();
+                let letter_offset = if uppercase { b'A' - 10 } else { b'a' - 10 };
+                for byte in buf.iter_mut() {
+                    if *byte < 10 {
+                        *byte += b'0';
+                    } else {
+                        *byte += letter_offset;
+                    }
+                }
+                unsafe { core::str::from_utf8_unchecked(buf) }
+            }
+
             /// Returns the integer as a string in the given radix.
             ///
             /// # Panics
@@ -381,18 +744,91 @@ macro_rules! radix {
             /// let n = U512::from_str_radix(src, 36).unwrap();
             /// assert_eq!(n.to_str_radix(36), src);
             /// ```
+            #[cfg(feature = "alloc")]
             #[inline]
             pub fn to_str_radix(&self, radix: u32) -> String {
-                let mut out = Self::to_radix_be(self, radix);
+                assert_range!(radix, 36);
+                let mut out = vec![0; self.radix_digits_len(radix)];
+                self.fill_str_radix(radix, false, &mut out);
+                unsafe { String::from_utf8_unchecked(out) }
+            }
 
-                for byte in out.iter_mut() {
-                    if *byte < 10 {
-                        *byte += b'0';
-                    } else {
-                        *byte += b'a' - 10;
-                    }
+            /// Writes the integer as a string in the given radix into the start of `buf`, and returns the written string slice. Unlike [`to_str_radix`](#method.to_str_radix), this method doesn't allocate.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is too short to hold every digit of the integer.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 36 inclusive.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U512;
+            ///
+            /// let n = U512::from(0xfe9u16);
+            /// let mut buf = [0; 8];
+            /// assert_eq!(n.write_str_radix(&mut buf, 16), Ok("fe9"));
+            /// assert_eq!(n.write_str_radix(&mut buf[..2], 16).unwrap_err().required(), 3);
+            /// ```
+            #[inline]
+            pub fn write_str_radix<'a>(&self, buf: &'a mut [u8], radix: u32) -> Result<&'a str, BufferTooSmallError> {
+                assert_range!(radix, 36);
+                let len = self.radix_digits_len(radix);
+                if buf.len() < len {
+                    return Err(BufferTooSmallError { required: len });
                 }
-                unsafe { String::from_utf8_unchecked(out) }
+                Ok(self.fill_str_radix(radix, false, &mut buf[..len]))
+            }
+
+            /// Writes the integer in hexadecimal with all `Self::BITS / 4` digits, including leading zeros, into the start of `buf`, and returns the written string slice.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is shorter than `Self::BITS / 4`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U128;
+            ///
+            /// let mut buf = [0; 32];
+            /// let n = U128::from(0xbeefu16);
+            /// assert_eq!(n.write_hex_fixed(&mut buf, false), Ok("0000000000000000000000000000beef"));
+            /// ```
+            pub fn write_hex_fixed<'a>(&self, buf: &'a mut [u8], uppercase: bool) -> Result<&'a str, BufferTooSmallError> {
+                let len = Self::BITS as usize / 4;
+                if buf.len() < len {
+                    return Err(BufferTooSmallError { required: len });
+                }
+                let zeros = len - self.radix_digits_len(16);
+                buf[..zeros].fill(b'0');
+                self.fill_str_radix(16, uppercase, &mut buf[zeros..len]);
+                Ok(unsafe { core::str::from_utf8_unchecked(&buf[..len]) })
+            }
+
+            /// Converts a hexadecimal string slice to an integer.
+            ///
+            /// The string is expected to be an optional `0x` or `0X` prefix followed by hexadecimal digits of either case. Any number of leading zeros is accepted, so the output of [`to_hex_fixed`](#method.to_hex_fixed) can be parsed, even after being padded further.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U128;
+            ///
+            /// let n = U128::from(0xbeefu16);
+            /// assert_eq!(U128::from_hex("0x0000beef"), Ok(n));
+            /// assert_eq!(U128::from_hex(&format!("{}", n.to_hex_fixed())), Ok(n));
+            /// ```
+            pub const fn from_hex(src: &str) -> Result<Self, ParseIntError> {
+                let src = src.as_bytes();
+                let start = match src {
+                    [b'0', b'x' | b'X', ..] => 2,
+                    _ => 0,
+                };
+                Self::parse_digits(src, start, src.len(), 16, false)
             }
 
             /// Returns the integer in the given base in big-endian digit order.
@@ -408,6 +844,7 @@ macro_rules! radix {
             /// let n = U512::from_radix_be(digits, 120).unwrap();
             /// assert_eq!(n.to_radix_be(120), digits);
             /// ```
+            #[cfg(feature = "alloc")]
             #[inline]
             pub fn to_radix_be(&self, radix: u32) -> Vec<u8> {
                 let mut v = self.to_radix_le(radix);
@@ -428,102 +865,74 @@ macro_rules! radix {
             /// let n = U512::from_radix_le(digits, 250).unwrap();
             /// assert_eq!(n.to_radix_le(250), digits);
             /// ```
+            #[cfg(feature = "alloc")]
             pub fn to_radix_le(&self, radix: u32) -> Vec<u8> {
-                if self.is_zero() {
-                    vec![0]
-                } else if radix.is_power_of_two() {
-                    if $Digit::BITS == 8 && radix == 256 {
-                        return (&self.digits[0..=self.last_digit_index()])
-                            .into_iter()
-                            .map(|d| *d as u8)
-                            .collect(); // we can cast to `u8` here as the underlying digit must be a `u8` anyway
-                    }
-
-                    let bits = ilog2(radix);
-                    if digit::$Digit::BITS_U8 % bits == 0 {
-                        self.to_bitwise_digits_le(bits)
-                    } else {
-                        self.to_inexact_bitwise_digits_le(bits)
-                    }
-                } else if radix == 10 {
-                    self.to_radix_digits_le(10)
-                } else {
-                    self.to_radix_digits_le(radix)
-                }
-            }
-
-            fn to_bitwise_digits_le(self, bits: u8) -> Vec<u8> {
-                let last_digit_index = self.last_digit_index();
-                let mask: $Digit = (1 << bits) - 1;
-                let digits_per_big_digit = digit::$Digit::BITS_U8 / bits;
-                let digits = div_ceil(self.bits(), bits as ExpType);
-                let mut out = Vec::with_capacity(digits as usize);
-
-                let mut r = self.digits[last_digit_index];
-
-                for mut d in IntoIterator::into_iter(self.digits).take(last_digit_index) {
-                    for _ in 0..digits_per_big_digit {
-                        out.push((d & mask) as u8);
-                        d >>= bits;
-                    }
-                }
-                while r != 0 {
-                    out.push((r & mask) as u8);
-                    r >>= bits;
-                }
+                assert_range!(radix, 256);
+                let mut out = vec![0; self.radix_digits_len(radix)];
+                self.fill_radix_le(radix, &mut out);
                 out
             }
 
-            fn to_inexact_bitwise_digits_le(self, bits: u8) -> Vec<u8> {
-                let mask: $Digit = (1 << bits) - 1;
-                let digits = div_ceil(self.bits(), bits as ExpType);
-                let mut out = Vec::with_capacity(digits as usize);
-                let mut r = 0;
-                let mut rbits = 0;
-                for c in self.digits {
-                    r |= c << rbits;
-                    rbits += digit::$Digit::BITS_U8;
-
-                    while rbits >= bits {
-                        out.push((r & mask) as u8);
-                        r >>= bits;
-
-                        if rbits > digit::$Digit::BITS_U8 {
-                            r = c >> (digit::$Digit::BITS_U8 - (rbits - bits));
-                        }
-                        rbits -= bits;
-                    }
-                }
-                if rbits != 0 {
-                    out.push(r as u8);
-                }
-                while let Some(&0) = out.last() {
-                    out.pop();
-                }
-                out
+            /// Writes the integer in the given base into the start of `buf` in big-endian digit order, and returns the written digits. Unlike [`to_radix_be`](#method.to_radix_be), this method doesn't allocate.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is too short to hold every digit of the integer.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 256 inclusive.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U512;
+            ///
+            /// let digits = &[3, 55, 60, 100, 5, 0, 5, 88];
+            /// let n = U512::from_radix_be(digits, 120).unwrap();
+            /// let mut buf = [0; 16];
+            /// assert_eq!(n.write_radix_be(&mut buf, 120), Ok(&digits[..]));
+            /// ```
+            #[inline]
+            pub fn write_radix_be<'a>(&self, buf: &'a mut [u8], radix: u32) -> Result<&'a [u8], BufferTooSmallError> {
+                let digits = self.write_radix_le(buf, radix)?.len();
+                let out = &mut buf[..digits];
+                out.reverse();
+                Ok(out)
             }
 
-            fn to_radix_digits_le(self, radix: u32) -> Vec<u8> {
-                let radix_digits = div_ceil(self.bits(), ilog2(radix) as ExpType);
-                let mut out = Vec::with_capacity(radix_digits as usize);
-                let (base, power) = Self::radix_base_half(radix);
-                let radix = radix as $Digit;
-                let mut copy = self;
-                while copy.last_digit_index() > 0 {
-                    let (q, mut r) = copy.div_rem_digit(base);
-                    for _ in 0..power {
-                        out.push((r % radix) as u8);
-                        r /= radix;
-                    }
-                    copy = q;
-                }
-                let mut r = copy.digits[0];
-                while r != 0 {
-                    out.push((r % radix) as u8);
-                    r /= radix;
+            /// Writes the integer in the given base into the start of `buf` in little-endian digit order, and returns the written digits. Unlike [`to_radix_le`](#method.to_radix_le), this method doesn't allocate.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is too short to hold every digit of the integer.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 256 inclusive.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U512;
+            ///
+            /// let digits = &[1, 67, 88, 200, 55, 68, 87, 120, 178];
+            /// let n = U512::from_radix_le(digits, 250).unwrap();
+            /// let mut buf = [0; 16];
+            /// assert_eq!(n.write_radix_le(&mut buf, 250), Ok(&digits[..]));
+            /// assert!(n.write_radix_le(&mut buf[..8], 250).is_err());
+            /// ```
+            pub fn write_radix_le<'a>(&self, buf: &'a mut [u8], radix: u32) -> Result<&'a [u8], BufferTooSmallError> {
+                assert_range!(radix, 256);
+                let len = self.radix_digits_len(radix);
+                if buf.len() < len {
+                    return Err(BufferTooSmallError { required: len });
                 }
-                out
+                let out = &mut buf[..len];
+                self.fill_radix_le(radix, out);
+                Ok(out)
             }
+
             const BP: ($Digit, usize) = Self::radix_base(10);
         }
 
@@ -536,9 +945,7 @@ macro_rules! radix {
                 let mut i = 0;
                 while i < buf.len() {
                     if Self::byte_to_digit(buf[i]) >= 10 {
-                        return Err(ParseIntError {
-                            kind: IntErrorKind::InvalidDigit,
-                        });
+                        return Err(ParseIntError::invalid_digit(buf, i, Self::BITS));
                     }
                     i += 1;
                 }
@@ -564,9 +971,7 @@ macro_rules! radix {
                         j += 1;
                     }
                     if carry != 0 {
-                        return Err(ParseIntError {
-                            kind: IntErrorKind::PosOverflow,
-                        });
+                        return Err(ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS));
                     }
 
                     let mut n = 0;
@@ -579,9 +984,7 @@ macro_rules! radix {
                     out = match out.checked_add(Self::from_digit(n)) {
                         Some(out) => out,
                         None => {
-                            return Err(ParseIntError {
-                                kind: IntErrorKind::PosOverflow,
-                            })
+                            return Err(ParseIntError::new(IntErrorKind::PosOverflow, Self::BITS))
                         }
                     };
                     start = end;
@@ -621,6 +1024,197 @@ macro_rules! radix {
 				quickcheck_from_to_radix!(utest, radix_le, 255);
 				quickcheck_from_to_radix!(utest, str_radix, 36);
 
+				#[test]
+				fn from_str_prefixed() {
+					use core::num::IntErrorKind;
+
+					let n = UTEST::from(0x1f2e3du32);
+					assert_eq!(UTEST::from_str_prefixed("2043453"), Ok(n));
+					assert_eq!(UTEST::from_str_prefixed("+0x1f_2e_3d"), Ok(n));
+					assert_eq!(UTEST::from_str_prefixed("0x_1F2E3D_"), Ok(n));
+					assert_eq!(UTEST::from_str_prefixed("0o7627075"), Ok(n));
+					assert_eq!(UTEST::from_str_prefixed("0b111110010111000111101"), Ok(n));
+					assert_eq!(UTEST::from_str_prefixed(&format!("2_043_453u{}", UTEST::BITS)), Ok(n));
+					assert_eq!(UTEST::from_str_prefixed(&format!("0x1f2e3d_u{}", UTEST::BITS)), Ok(n));
+					assert_eq!(UTEST::from_str_prefixed(&format!("0x{:x}", UTEST::MAX)), Ok(UTEST::MAX));
+
+					let err = |src: &str| UTEST::from_str_prefixed(src).unwrap_err();
+					assert_eq!(err("").kind(), &IntErrorKind::Empty);
+					assert_eq!(err("0x").kind(), &IntErrorKind::Empty);
+					assert_eq!(err("0b__").kind(), &IntErrorKind::Empty);
+					assert_eq!(err("+").kind(), &IntErrorKind::InvalidDigit);
+					assert_eq!(err("+").index(), Some(0));
+					assert_eq!(err("-1").index(), Some(0));
+					assert_eq!(err("_1").index(), Some(0));
+					assert_eq!(err("0o178").index(), Some(4));
+					assert_eq!(err("0xfg").index(), Some(3));
+					assert_eq!(err("12u").index(), Some(2));
+					assert_eq!(err("12u0").index(), Some(2));
+					assert_eq!(err(&format!("12u0{}", UTEST::BITS)).index(), Some(2));
+					assert_eq!(err(&format!("12u{}", UTEST::BITS * 2)).index(), Some(2));
+					assert_eq!(err(&format!("12i{}", UTEST::BITS)).index(), Some(2));
+					assert_eq!(err(&format!("0x1{:x}", UTEST::MAX)).kind(), &IntErrorKind::PosOverflow);
+				}
+
+				quickcheck::quickcheck! {
+					fn quickcheck_parse_const(u: crate::test::types::utest, radix: u8) -> quickcheck::TestResult {
+						if !(2..=36).contains(&radix) {
+							return quickcheck::TestResult::discard();
+						}
+						let s = UTEST::from(u).to_str_radix(radix as u32);
+						quickcheck::TestResult::from_bool(UTEST::parse_const(&s, radix as u32) == UTEST::from_str_radix(&s, radix as u32))
+					}
+				}
+
+				#[test]
+				fn parse_const() {
+					use core::num::IntErrorKind;
+
+					const N: Result<UTEST, crate::errors::ParseIntError> = UTEST::parse_const("+1f2e3d", 16);
+					assert_eq!(N, Ok(UTEST::from(0x1f2e3du32)));
+					assert_eq!(UTEST::parse_const("", 10).unwrap_err().kind(), &IntErrorKind::Empty);
+					assert_eq!(UTEST::parse_const("+", 10).unwrap_err().kind(), &IntErrorKind::InvalidDigit);
+					assert_eq!(UTEST::parse_const("+", 10).unwrap_err().index(), Some(0));
+					assert_eq!(UTEST::parse_const("+", 10), UTEST::from_str_radix("+", 10));
+					assert_eq!(UTEST::parse_const("1_000", 10).unwrap_err().index(), Some(1));
+					assert_eq!(UTEST::parse_const("-1", 10).unwrap_err().index(), Some(0));
+					let max = format!("{}", UTEST::MAX);
+					assert_eq!(UTEST::parse_const(&max, 10), Ok(UTEST::MAX));
+					assert_eq!(UTEST::parse_const(&format!("{}0", max), 10).unwrap_err().kind(), &IntErrorKind::PosOverflow);
+					let invalid = format!("{}0x", max);
+					assert_eq!(UTEST::parse_const(&invalid, 10).unwrap_err().kind(), &IntErrorKind::InvalidDigit);
+					assert_eq!(UTEST::parse_const(&invalid, 10), UTEST::from_str_radix(&invalid, 10));
+				}
+
+				quickcheck::quickcheck! {
+					fn quickcheck_scaled(u: crate::test::types::utest, decimals: u8) -> bool {
+						let decimals = decimals as u32 % 48;
+						let u = UTEST::from(u);
+						let s = format!("{}", u.format_scaled(decimals, decimals));
+						UTEST::from_decimal_str_scaled(&s, decimals) == Ok(u) && UTEST::from_sci_str(&format!("{:e}", u)) == Ok(u)
+					}
+				}
+
+				#[test]
+				fn parse_error_context() {
+					use core::num::IntErrorKind;
+
+					let err = UTEST::from_str_radix("+12ö4", 10).unwrap_err();
+					assert_eq!((err.index(), err.invalid_char(), err.bits()), (Some(3), Some('ö'), Some(UTEST::BITS)));
+					assert_eq!(format!("{}", err), format!("(bnum) attempt to parse integer from string containing invalid digit 'ö' at byte index 3 (target type has {} bits)", UTEST::BITS));
+					assert_eq!(UTEST::from_str_radix("12g", 16).unwrap_err().invalid_char(), Some('g'));
+					assert_eq!(UTEST::from_str_prefixed("0b10😀").unwrap_err().invalid_char(), Some('😀'));
+					assert_eq!("1x".parse::<UTEST>().unwrap_err().invalid_char(), Some('x'));
+
+					let err = UTEST::from_str_radix("", 10).unwrap_err();
+					assert_eq!((err.index(), err.invalid_char(), err.bits()), (None, None, Some(UTEST::BITS)));
+
+					for kind in [IntErrorKind::Empty, IntErrorKind::InvalidDigit, IntErrorKind::PosOverflow, IntErrorKind::NegOverflow, IntErrorKind::Zero] {
+						let err = crate::errors::ParseIntError::new(kind.clone(), UTEST::BITS);
+						let core_err = core::num::ParseIntError::from(err);
+						assert_eq!(core_err.kind(), &kind);
+						let err = crate::errors::ParseIntError::from(core_err);
+						assert_eq!((err.kind(), err.bits()), (&kind, None));
+					}
+				}
+
+				#[test]
+				fn sci_str() {
+					use core::num::IntErrorKind;
+
+					assert_eq!(UTEST::from_sci_str("1.5e30"), UTEST::from_str_radix(&format!("15{}", "0".repeat(29)), 10));
+					assert_eq!(UTEST::from_sci_str("+12E+2"), Ok(UTEST::from(1200u16)));
+					assert_eq!(UTEST::from_sci_str(".5e1"), Ok(UTEST::from(5u8)));
+					assert_eq!(UTEST::from_sci_str("5.e0"), Ok(UTEST::from(5u8)));
+					assert_eq!(UTEST::from_sci_str("1000e-3"), Ok(UTEST::ONE));
+					assert_eq!(UTEST::from_sci_str("0e99999999999999999999"), Ok(UTEST::ZERO));
+					assert_eq!(UTEST::from_sci_str("1e99999999999999999999").unwrap_err().kind(), &IntErrorKind::PosOverflow);
+					assert_eq!(UTEST::from_sci_str("1e-99999999999999999999").unwrap_err().index(), Some(0));
+					assert_eq!(UTEST::from_sci_str("1.05e1").unwrap_err().index(), Some(3));
+					assert_eq!(UTEST::from_sci_str("").unwrap_err().kind(), &IntErrorKind::Empty);
+					assert_eq!(UTEST::from_sci_str(".e1").unwrap_err().index(), Some(0));
+					assert_eq!(UTEST::from_sci_str("1e").unwrap_err().index(), Some(1));
+					assert_eq!(UTEST::from_sci_str("1e+x").unwrap_err().index(), Some(3));
+					assert_eq!(UTEST::from_sci_str("1e1 ").unwrap_err().index(), Some(3));
+				}
+
+				#[test]
+				fn decimal_str_scaled() {
+					assert_eq!(UTEST::from_decimal_str_scaled("12.345", 3), Ok(UTEST::from(12345u16)));
+					assert_eq!(UTEST::from_decimal_str_scaled("12.3450", 3), Ok(UTEST::from(12345u16)));
+					assert_eq!(UTEST::from_decimal_str_scaled("12.3456", 3).unwrap_err().index(), Some(6));
+					assert_eq!(UTEST::from_decimal_str_scaled("1e3", 3).unwrap_err().index(), Some(1));
+					assert_eq!(UTEST::from_decimal_str_scaled("0.000", 0), Ok(UTEST::ZERO));
+
+					assert_eq!(format!("{}", UTEST::from(12345u16).format_scaled(3, 1)), "12.3");
+					assert_eq!(format!("{}", UTEST::from(12350u16).format_scaled(3, 2)), "12.35");
+					assert_eq!(format!("{}", UTEST::from(12250u16).format_scaled(3, 1)), "12.2");
+					assert_eq!(format!("{}", UTEST::from(12350u16).format_scaled(3, 1)), "12.4");
+					assert_eq!(format!("{}", UTEST::from(999u16).format_scaled(3, 0)), "1");
+					assert_eq!(format!("{}", UTEST::from(5u8).format_scaled(0, 2)), "5.00");
+					assert_eq!(format!("{:08}", UTEST::from(5u8).format_scaled(2, 3)), "0000.050");
+					assert_eq!(format!("{}", UTEST::MAX.format_scaled(1000, 2)), "0.00");
+					assert_eq!(format!("{}", UTEST::MAX.format_scaled(1000, 1000)), format!("0.{}{}", "0".repeat(1000 - format!("{}", UTEST::MAX).len()), UTEST::MAX));
+				}
+
+				#[test]
+				fn hex_fixed() {
+					use core::num::IntErrorKind;
+
+					let width = UTEST::BITS as usize / 4;
+					let n = UTEST::from(0x1f2e3du32);
+					let mut buf = [0; 64];
+					assert_eq!(n.write_hex_fixed(&mut buf, true), Ok(format!("{:0width$X}", 0x1f2e3du32, width = width).as_str()));
+					assert_eq!(UTEST::MAX.write_hex_fixed(&mut buf, false), Ok("f".repeat(width).as_str()));
+					assert_eq!(n.write_hex_fixed(&mut buf[..width - 1], false).unwrap_err().required(), width);
+
+					assert_eq!(UTEST::from_hex("1F2e3d"), Ok(n));
+					assert_eq!(UTEST::from_hex("0x001f2e3d"), Ok(n));
+					assert_eq!(UTEST::from_hex(&format!("0X{}", "0".repeat(width * 2))), Ok(UTEST::ZERO));
+					assert_eq!(UTEST::from_hex(&format!("{}", UTEST::MAX.to_hex_fixed())), Ok(UTEST::MAX));
+					assert_eq!(UTEST::from_hex("0x").unwrap_err().kind(), &IntErrorKind::Empty);
+					assert_eq!(UTEST::from_hex("0xg").unwrap_err().index(), Some(2));
+					assert_eq!(UTEST::from_hex(&format!("1{}", UTEST::MAX.to_hex_fixed())).unwrap_err().kind(), &IntErrorKind::PosOverflow);
+				}
+
+				#[test]
+				fn uint_macro() {
+					const N: UTEST = crate::uint!(UTEST, "0x1f_2e_3d");
+					const MAX: UTEST = crate::uint!(UTEST, "0xffff_ffff_ffff_ffff");
+					assert_eq!(N, UTEST::from(0x1f2e3du32));
+					assert_eq!(MAX, UTEST::from(u64::MAX));
+				}
+
+				quickcheck::quickcheck! {
+					fn quickcheck_write_str_radix(u: crate::test::types::utest, radix: u8) -> quickcheck::TestResult {
+						if !(2..=36).contains(&radix) {
+							return quickcheck::TestResult::discard();
+						}
+						let u = UTEST::from(u);
+						let s = u.to_str_radix(radix as u32);
+						let mut buf = [0; 130];
+						let short = u.write_str_radix(&mut buf[..s.len() - 1], radix as u32);
+						if short != Err(crate::errors::BufferTooSmallError { required: s.len() }) {
+							return quickcheck::TestResult::failed();
+						}
+						quickcheck::TestResult::from_bool(u.write_str_radix(&mut buf, radix as u32) == Ok(s.as_str()))
+					}
+
+					fn quickcheck_write_radix_be(u: crate::test::types::utest, radix: u16) -> quickcheck::TestResult {
+						if !(2..=256).contains(&radix) {
+							return quickcheck::TestResult::discard();
+						}
+						let u = UTEST::from(u);
+						let mut buf = [0; 130];
+						let be = u.to_radix_be(radix as u32);
+						let le = u.to_radix_le(radix as u32);
+						if u.write_radix_be(&mut buf, radix as u32) != Ok(&be[..]) {
+							return quickcheck::TestResult::failed();
+						}
+						quickcheck::TestResult::from_bool(u.write_radix_le(&mut buf, radix as u32) == Ok(&le[..]))
+					}
+				}
+
 				#[test]
 				fn from_to_radix_le() {
 					let buf = &[
@@ -0,0 +1,44 @@
+use ::bytemuck::{Pod, Zeroable};
+
+macro_rules! bytemuck {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        // SAFETY: the types are `#[repr(transparent)]` over `[$Digit; N]`, for which every bit pattern, including all zeros, is valid, and which has no padding.
+        unsafe impl<const N: usize> Zeroable for $BUint<N> {}
+
+        unsafe impl<const N: usize> Pod for $BUint<N> {}
+
+        unsafe impl<const N: usize> Zeroable for $BInt<N> {}
+
+        unsafe impl<const N: usize> Pod for $BInt<N> {}
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_bytemuck_cast(a: utest, b: itest) -> bool {
+                        let values = [UTEST::from(a), UTEST::from(b as utest)];
+                        let bytes: &[u8] = bytemuck::cast_slice(&values);
+                        let signed: &[ITEST] = bytemuck::cast_slice(&values);
+                        bytes.len() == 32
+                            && bytes[..16] == *values[0].as_ne_bytes()
+                            && bytes[16..] == *values[1].as_ne_bytes()
+                            && signed[1] == ITEST::from(b)
+                            && bytemuck::pod_read_unaligned::<UTEST>(&bytes[16..]) == values[1]
+                    }
+                }
+
+                #[test]
+                fn bytemuck_zeroed() {
+                    assert_eq!(<UTEST as bytemuck::Zeroable>::zeroed(), UTEST::ZERO);
+                    assert_eq!(<ITEST as bytemuck::Zeroable>::zeroed(), ITEST::ZERO);
+                    assert_eq!(core::mem::align_of::<UTEST>(), core::mem::align_of::<$Digit>());
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(bytemuck);
@@ -0,0 +1,325 @@
+//! Extension traits for reading and writing integers with the [`bytes`](https://docs.rs/bytes/latest/bytes/) crate.
+//!
+//! The `bytes` feature must be enabled to use items from this module.
+//!
+//! [`BufExt`] adds methods to every [`Buf`] for reading an integer of any type of this crate from its big-endian or little-endian bytes, analogous to [`Buf::get_u128`] and [`Buf::get_u128_le`]. [`BufMutExt`] adds methods to every [`BufMut`] for writing an integer in the same way, analogous to [`BufMut::put_u128`] and [`BufMut::put_u128_le`]. An integer always takes up `Self::BITS / 8` bytes, which are its two's complement bytes in the given byte order.
+//!
+//! ```
+//! use bnum::bytes::{BufExt, BufMutExt};
+//! use bnum::types::{I256, U256};
+//!
+//! let mut buf = Vec::new();
+//! buf.put_bnum_be(&U256::from(0x1234u16));
+//! buf.put_bnum_le(&I256::NEG_ONE);
+//! assert_eq!(buf.len(), 64);
+//! assert_eq!(&buf[30..32], &[0x12, 0x34]);
+//!
+//! let mut frame = &buf[..];
+//! assert_eq!(frame.get_bnum_be::<U256>(), U256::from(0x1234u16));
+//! assert_eq!(frame.get_bnum_le::<I256>(), I256::NEG_ONE);
+//! assert!(frame.try_get_bnum_be::<U256>().is_err());
+//! ```
+
+use ::bytes::{Buf, BufMut, TryGetError};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Implemented by every integer type of this crate, and used as a bound by the methods of [`BufExt`] and [`BufMutExt`].
+///
+/// This trait is sealed, so can't be implemented outside of this crate.
+pub trait Integer: sealed::Sealed + Sized {
+    #[doc(hidden)]
+    const BYTES: usize;
+
+    /// Reads the integer from the first `Self::BYTES` bytes of `buf`, which must have at least that many bytes remaining.
+    #[doc(hidden)]
+    fn get<B: Buf + ?Sized>(buf: &mut B, big_endian: bool) -> Self;
+
+    #[doc(hidden)]
+    fn put<B: BufMut + ?Sized>(&self, buf: &mut B, big_endian: bool);
+}
+
+/// Extension methods for reading integers of this crate from a [`Buf`].
+///
+/// This trait is implemented for every type which implements [`Buf`].
+pub trait BufExt: Buf {
+    /// Reads an integer from `Self::BITS / 8` bytes of `self` in big-endian byte order, and advances the current position by that number of bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there aren't enough bytes remaining in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bnum::bytes::BufExt;
+    /// use bnum::types::U256;
+    ///
+    /// let mut bytes = [0; 33];
+    /// bytes[31] = 1;
+    /// let mut buf = &bytes[..];
+    /// assert_eq!(buf.get_bnum_be::<U256>(), U256::ONE);
+    /// assert_eq!(buf.len(), 1);
+    /// ```
+    #[inline]
+    fn get_bnum_be<T: Integer>(&mut self) -> T {
+        match self.try_get_bnum_be() {
+            Ok(out) => out,
+            Err(e) => panic_advance(e),
+        }
+    }
+
+    /// Reads an integer from `Self::BITS / 8` bytes of `self` in little-endian byte order, and advances the current position by that number of bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there aren't enough bytes remaining in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bnum::bytes::BufExt;
+    /// use bnum::types::I256;
+    ///
+    /// let mut buf = &[0xff; 32][..];
+    /// assert_eq!(buf.get_bnum_le::<I256>(), I256::NEG_ONE);
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    fn get_bnum_le<T: Integer>(&mut self) -> T {
+        match self.try_get_bnum_le() {
+            Ok(out) => out,
+            Err(e) => panic_advance(e),
+        }
+    }
+
+    /// Reads an integer from `Self::BITS / 8` bytes of `self` in big-endian byte order, and advances the current position by that number of bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryGetError`] if there aren't enough bytes remaining in `self`, in which case the current position isn't advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bnum::bytes::BufExt;
+    /// use bnum::types::U256;
+    ///
+    /// let mut buf = &[0; 31][..];
+    /// let err = buf.try_get_bnum_be::<U256>().unwrap_err();
+    /// assert_eq!((err.requested, err.available), (32, 31));
+    /// assert_eq!(buf.len(), 31);
+    /// ```
+    #[inline]
+    fn try_get_bnum_be<T: Integer>(&mut self) -> Result<T, TryGetError> {
+        try_get(self, true)
+    }
+
+    /// Reads an integer from `Self::BITS / 8` bytes of `self` in little-endian byte order, and advances the current position by that number of bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryGetError`] if there aren't enough bytes remaining in `self`, in which case the current position isn't advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bnum::bytes::BufExt;
+    /// use bnum::types::U256;
+    ///
+    /// let mut bytes = [0; 32];
+    /// bytes[0] = 1;
+    /// let mut buf = &bytes[..];
+    /// assert_eq!(buf.try_get_bnum_le::<U256>(), Ok(U256::ONE));
+    /// assert!(buf.try_get_bnum_le::<U256>().is_err());
+    /// ```
+    #[inline]
+    fn try_get_bnum_le<T: Integer>(&mut self) -> Result<T, TryGetError> {
+        try_get(self, false)
+    }
+}
+
+impl<B: Buf + ?Sized> BufExt for B {}
+
+/// Extension methods for writing integers of this crate to a [`BufMut`].
+///
+/// This trait is implemented for every type which implements [`BufMut`].
+pub trait BufMutExt: BufMut {
+    /// Writes `Self::BITS / 8` bytes of `n` to `self` in big-endian byte order, and advances the current position by that number of bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there isn't enough remaining capacity in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bnum::bytes::BufMutExt;
+    /// use bnum::types::U256;
+    ///
+    /// let mut buf = Vec::new();
+    /// buf.put_bnum_be(&U256::from(0x1234u16));
+    /// assert_eq!(buf.len(), 32);
+    /// assert_eq!(&buf[30..], &[0x12, 0x34]);
+    /// ```
+    #[inline]
+    fn put_bnum_be<T: Integer>(&mut self, n: &T) {
+        n.put(self, true);
+    }
+
+    /// Writes `Self::BITS / 8` bytes of `n` to `self` in little-endian byte order, and advances the current position by that number of bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there isn't enough remaining capacity in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bnum::bytes::BufMutExt;
+    /// use bnum::types::I256;
+    ///
+    /// let mut bytes = [0; 32];
+    /// let mut buf = &mut bytes[..];
+    /// buf.put_bnum_le(&I256::from(-2i8));
+    /// assert_eq!(bytes[0], 0xfe);
+    /// assert!(bytes[1..].iter().all(|&byte| byte == 0xff));
+    /// ```
+    #[inline]
+    fn put_bnum_le<T: Integer>(&mut self, n: &T) {
+        n.put(self, false);
+    }
+}
+
+impl<B: BufMut + ?Sized> BufMutExt for B {}
+
+#[inline]
+fn try_get<T: Integer, B: Buf + ?Sized>(buf: &mut B, big_endian: bool) -> Result<T, TryGetError> {
+    let available = buf.remaining();
+    if available < T::BYTES {
+        return Err(TryGetError {
+            requested: T::BYTES,
+            available,
+        });
+    }
+    Ok(T::get(buf, big_endian))
+}
+
+#[cold]
+fn panic_advance(e: TryGetError) -> ! {
+    panic!(
+        crate::errors::err_msg!("not enough bytes remaining in buffer: requested {} but only {} available"),
+        e.requested, e.available
+    )
+}
+
+macro_rules! bytes {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> sealed::Sealed for $BUint<N> {}
+
+        impl<const N: usize> Integer for $BUint<N> {
+            const BYTES: usize = Self::BYTES as usize;
+
+            #[inline]
+            fn get<B: Buf + ?Sized>(buf: &mut B, big_endian: bool) -> Self {
+                let mut out = Self::ZERO;
+                let mut bytes = [0; crate::digit::$Digit::BYTES as usize];
+                for i in 0..N {
+                    buf.copy_to_slice(&mut bytes);
+                    if big_endian {
+                        out.digits[N - 1 - i] = $Digit::from_be_bytes(bytes);
+                    } else {
+                        out.digits[i] = $Digit::from_le_bytes(bytes);
+                    }
+                }
+                out
+            }
+
+            #[inline]
+            fn put<B: BufMut + ?Sized>(&self, buf: &mut B, big_endian: bool) {
+                for i in 0..N {
+                    if big_endian {
+                        buf.put_slice(&self.digits[N - 1 - i].to_be_bytes());
+                    } else {
+                        buf.put_slice(&self.digits[i].to_le_bytes());
+                    }
+                }
+            }
+        }
+
+        impl<const N: usize> sealed::Sealed for $BInt<N> {}
+
+        impl<const N: usize> Integer for $BInt<N> {
+            const BYTES: usize = Self::BYTES as usize;
+
+            #[inline]
+            fn get<B: Buf + ?Sized>(buf: &mut B, big_endian: bool) -> Self {
+                Self::from_bits($BUint::get(buf, big_endian))
+            }
+
+            #[inline]
+            fn put<B: BufMut + ?Sized>(&self, buf: &mut B, big_endian: bool) {
+                self.bits.put(buf, big_endian);
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::bytes::{BufExt, BufMutExt};
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+                use ::bytes::{Buf, BufMut, Bytes};
+                use alloc::vec::Vec;
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_bytes_buf(u: utest, i: itest) -> bool {
+                        let mut buf = Vec::new();
+                        buf.put_bnum_be(&UTEST::from(u));
+                        buf.put_bnum_le(&ITEST::from(i));
+                        let mut expected = Vec::new();
+                        expected.put_u128(u);
+                        expected.put_i128_le(i);
+                        if buf != expected {
+                            return false;
+                        }
+
+                        // a buffer whose bytes aren't contiguous
+                        let mut chain = Bytes::copy_from_slice(&buf[..7]).chain(Bytes::copy_from_slice(&buf[7..]));
+                        chain.get_bnum_be::<UTEST>() == UTEST::from(u)
+                            && chain.try_get_bnum_le::<ITEST>() == Ok(ITEST::from(i))
+                            && !chain.has_remaining()
+                    }
+                }
+
+                #[test]
+                fn bytes_buf() {
+                    let mut buf = &[0xff; 31][..];
+                    assert_eq!(buf.try_get_bnum_le::<ITEST>(), Ok(ITEST::NEG_ONE));
+                    let err = buf.try_get_bnum_le::<ITEST>().unwrap_err();
+                    assert_eq!((err.requested, err.available), (16, 15));
+                    assert_eq!(buf.remaining(), 15);
+                }
+
+                #[test]
+                #[should_panic]
+                fn bytes_get_too_short() {
+                    let mut buf = &[0; 15][..];
+                    let _ = buf.get_bnum_be::<UTEST>();
+                }
+
+                #[test]
+                #[should_panic]
+                fn bytes_put_too_short() {
+                    let mut bytes = [0; 15];
+                    let mut buf = &mut bytes[..];
+                    buf.put_bnum_le(&UTEST::MAX);
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(bytes);
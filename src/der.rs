@@ -0,0 +1,504 @@
+//! ASN.1 DER encoding of integers as `INTEGER` values, as used in cryptographic keys and signatures.
+//!
+//! An integer is encoded as the `INTEGER` tag `0x02`, followed by the length of the content in its shortest form, followed by the content, which is the shortest two's complement big-endian representation of the integer. This means that the content of an unsigned integer whose most significant bit is set starts with a `0x00` byte. Decoding only accepts this canonical encoding.
+//!
+//! The encoding and decoding methods are [`der_integer_len`](crate::BUint::der_integer_len), [`write_der_integer`](crate::BUint::write_der_integer), [`to_der_integer`](crate::BUint::to_der_integer), [`decode_der_integer`](crate::BUint::decode_der_integer) and [`from_der_integer`](crate::BUint::from_der_integer), which are defined on each unsigned and signed integer type.
+//!
+//! The `der` feature also implements the `EncodeValue`, `DecodeValue` and `FixedTag` traits from the [`der`](https://docs.rs/der/latest/der/) crate, so that bnum integers can be used as `INTEGER` fields of types which are encoded with that crate.
+
+use crate::errors::{BufferTooSmallError, DerDecodeError, DerErrorKind};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The tag of an `INTEGER` value.
+const INTEGER_TAG: u8 = 0x02;
+
+/// Returns the length of the DER encoding of a length.
+const fn length_len(len: usize) -> usize {
+    if len < 0x80 {
+        1
+    } else {
+        1 + (usize::BITS - len.leading_zeros()).div_ceil(8) as usize
+    }
+}
+
+/// Reads the tag and length of an `INTEGER` value from the start of `src`, and returns the length of the content and the length of the tag and length.
+fn decode_header(src: &[u8]) -> Result<(usize, usize), DerDecodeError> {
+    match src.first() {
+        Some(&INTEGER_TAG) => {}
+        Some(_) => return Err(DerDecodeError::new(DerErrorKind::UnexpectedTag)),
+        None => return Err(DerDecodeError::new(DerErrorKind::InputTooShort)),
+    }
+    let first = match src.get(1) {
+        Some(&first) => first,
+        None => return Err(DerDecodeError::new(DerErrorKind::InputTooShort)),
+    };
+    if first < 0x80 {
+        return Ok((first as usize, 2));
+    }
+    // 0x80 is the indefinite length, which isn't allowed in DER
+    let len_of_len = (first & 0x7f) as usize;
+    let len_bytes = match src.get(2..2 + len_of_len) {
+        Some(len_bytes) if len_of_len != 0 => len_bytes,
+        Some(_) => return Err(DerDecodeError::new(DerErrorKind::NonCanonical)),
+        None => return Err(DerDecodeError::new(DerErrorKind::InputTooShort)),
+    };
+    if len_bytes[0] == 0 {
+        return Err(DerDecodeError::new(DerErrorKind::NonCanonical));
+    }
+    if len_of_len > core::mem::size_of::<usize>() {
+        return Err(DerDecodeError::new(DerErrorKind::InputTooShort));
+    }
+    let len = len_bytes.iter().fold(0, |len, &byte| (len << 8) | byte as usize);
+    if len < 0x80 {
+        return Err(DerDecodeError::new(DerErrorKind::NonCanonical));
+    }
+    Ok((len, 2 + len_of_len))
+}
+
+#[cfg(feature = "der")]
+impl From<DerDecodeError> for ::der::Error {
+    #[inline]
+    fn from(err: DerDecodeError) -> Self {
+        match err.kind {
+            DerErrorKind::NonCanonical => ::der::Tag::Integer.non_canonical_error(),
+            _ => ::der::Tag::Integer.value_error(),
+        }
+    }
+}
+
+macro_rules! der {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> $BUint<N> {
+            #[inline]
+            const fn der_len(&self, negative: bool) -> usize {
+                let len = self.twos_complement_len(negative);
+                1 + length_len(len) + len
+            }
+
+            fn write_der<'a>(&self, buf: &'a mut [u8], negative: bool) -> Result<&'a [u8], BufferTooSmallError> {
+                let required = self.der_len(negative);
+                if buf.len() < required {
+                    return Err(BufferTooSmallError { required });
+                }
+                let buf = &mut buf[..required];
+                let len = self.twos_complement_len(negative);
+                buf[0] = INTEGER_TAG;
+                let start = if len < 0x80 {
+                    buf[1] = len as u8;
+                    2
+                } else {
+                    let len_of_len = length_len(len) - 1;
+                    buf[1] = 0x80 | len_of_len as u8;
+                    buf[2..2 + len_of_len].copy_from_slice(&len.to_be_bytes()[core::mem::size_of::<usize>() - len_of_len..]);
+                    2 + len_of_len
+                };
+                let _ = self.write_padded_bytes(&mut buf[start..], len, negative, true);
+                Ok(buf)
+            }
+
+            /// Decodes the `len` content bytes of an `INTEGER`, which `next` returns in order, into the bits of an unsigned or signed integer.
+            fn decode_der_content<E: From<DerDecodeError>>(len: usize, signed: bool, mut next: impl FnMut() -> Result<u8, E>) -> Result<Self, E> {
+                if len == 0 {
+                    return Err(DerDecodeError::new(DerErrorKind::NonCanonical).into());
+                }
+                // shifting an 8 bit integer left by 8 bits would overflow, but then there is at most one significant byte
+                let push = |out: Self, byte: u8| if Self::BITS > 8 { (out << 8u32) | Self::from(byte) } else { Self::from(byte) };
+                let first = next()?;
+                let negative = first >> 7 == 1;
+                let mut out = push(if negative { Self::MAX } else { Self::ZERO }, first);
+                if len > 1 {
+                    let second = next()?;
+                    // the first byte is redundant if it is only the sign extension of the second byte
+                    if (first == 0 && second >> 7 == 0) || (first == u8::MAX && second >> 7 == 1) {
+                        return Err(DerDecodeError::new(DerErrorKind::NonCanonical).into());
+                    }
+                    out = push(out, second);
+                }
+                let magnitude_len = if !signed && first == 0 { len - 1 } else { len };
+                if (negative && !signed) || magnitude_len > Self::BYTES as usize {
+                    return Err(DerDecodeError::new(DerErrorKind::OutOfRange).into());
+                }
+                for _ in 2..len {
+                    out = push(out, next()?);
+                }
+                Ok(out)
+            }
+
+            fn decode_der(buf: &mut &[u8], signed: bool) -> Result<Self, DerDecodeError> {
+                let src = *buf;
+                let (len, start) = decode_header(src)?;
+                let content = match src.get(start..).and_then(|rest| rest.get(..len)) {
+                    Some(content) => content,
+                    None => return Err(DerDecodeError::new(DerErrorKind::InputTooShort)),
+                };
+                let mut bytes = content.iter();
+                let out = Self::decode_der_content(len, signed, || Ok::<_, DerDecodeError>(*bytes.next().unwrap()))?;
+                *buf = &src[start + len..];
+                Ok(out)
+            }
+
+            /// Returns the length in bytes of the DER encoding of the integer as an `INTEGER`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// assert_eq!(U256::ZERO.der_integer_len(), 3);
+            /// assert_eq!(U256::from(0x7fu8).der_integer_len(), 3);
+            /// assert_eq!(U256::from(0x80u8).der_integer_len(), 4);
+            /// assert_eq!(U256::MAX.der_integer_len(), 35);
+            /// ```
+            #[must_use]
+            #[inline]
+            pub const fn der_integer_len(&self) -> usize {
+                self.der_len(false)
+            }
+
+            /// Writes the DER encoding of the integer as an `INTEGER` into the start of `buf`, and returns the written bytes.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is shorter than [`der_integer_len`](#method.der_integer_len).
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// let mut buf = [0; 35];
+            /// assert_eq!(U256::from(0x80u8).write_der_integer(&mut buf), Ok(&[0x02, 0x02, 0x00, 0x80][..]));
+            /// assert_eq!(U256::ZERO.write_der_integer(&mut buf), Ok(&[0x02, 0x01, 0x00][..]));
+            /// ```
+            #[inline]
+            pub fn write_der_integer<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], BufferTooSmallError> {
+                self.write_der(buf, false)
+            }
+
+            /// Returns the DER encoding of the integer as an `INTEGER`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U2048;
+            ///
+            /// let modulus = U2048::MAX;
+            /// let encoded = modulus.to_der_integer();
+            /// assert_eq!(&encoded[..5], &[0x02, 0x82, 0x01, 0x01, 0x00]);
+            /// assert_eq!(U2048::from_der_integer(&encoded), Ok(modulus));
+            /// ```
+            #[cfg(feature = "alloc")]
+            #[inline]
+            pub fn to_der_integer(&self) -> Vec<u8> {
+                let mut out = vec![0; self.der_integer_len()];
+                self.write_der_integer(&mut out).unwrap();
+                out
+            }
+
+            /// Decodes a DER-encoded `INTEGER` from the start of `buf`, and advances `buf` past the encoding.
+            ///
+            /// Only the canonical encoding of an integer is accepted. The slice is only advanced if decoding succeeds.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`DerDecodeError`](crate::errors::DerDecodeError) if `buf` doesn't start with the canonical encoding of an `INTEGER`, or if the integer is negative or too large to be represented by `Self`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            /// use bnum::errors::DerErrorKind;
+            ///
+            /// let mut buf = &[0x02, 0x01, 0x05, 0x02, 0x02, 0x00, 0xff][..];
+            /// assert_eq!(U256::decode_der_integer(&mut buf), Ok(U256::from(5u8)));
+            /// assert_eq!(U256::decode_der_integer(&mut buf), Ok(U256::from(255u8)));
+            /// assert!(buf.is_empty());
+            ///
+            /// assert_eq!(U256::decode_der_integer(&mut &[0x02, 0x02, 0x00, 0x7f][..]).unwrap_err().kind(), &DerErrorKind::NonCanonical);
+            /// assert_eq!(U256::decode_der_integer(&mut &[0x02, 0x01, 0xff][..]).unwrap_err().kind(), &DerErrorKind::OutOfRange);
+            /// ```
+            #[inline]
+            pub fn decode_der_integer(buf: &mut &[u8]) -> Result<Self, DerDecodeError> {
+                Self::decode_der(buf, false)
+            }
+
+            /// Decodes a DER-encoded `INTEGER` which takes up the whole of `src`.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`DerDecodeError`](crate::errors::DerDecodeError) in the same cases as [`decode_der_integer`](#method.decode_der_integer), and also if `src` has bytes after the end of the encoding.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            /// use bnum::errors::DerErrorKind;
+            ///
+            /// assert_eq!(U256::from_der_integer(&[0x02, 0x01, 0x2a]), Ok(U256::from(42u8)));
+            /// assert_eq!(U256::from_der_integer(&[0x04, 0x01, 0x2a]).unwrap_err().kind(), &DerErrorKind::UnexpectedTag);
+            /// ```
+            pub fn from_der_integer(mut src: &[u8]) -> Result<Self, DerDecodeError> {
+                let out = Self::decode_der_integer(&mut src)?;
+                if !src.is_empty() {
+                    return Err(DerDecodeError::new(DerErrorKind::TrailingBytes));
+                }
+                Ok(out)
+            }
+        }
+
+        impl<const N: usize> $BInt<N> {
+            /// Returns the length in bytes of the DER encoding of the integer as an `INTEGER`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// assert_eq!(I256::from(-128i16).der_integer_len(), 3);
+            /// assert_eq!(I256::from(-129i16).der_integer_len(), 4);
+            /// assert_eq!(I256::MIN.der_integer_len(), 34);
+            /// ```
+            #[must_use]
+            #[inline]
+            pub const fn der_integer_len(&self) -> usize {
+                self.bits.der_len(self.is_negative())
+            }
+
+            /// Writes the DER encoding of the integer as an `INTEGER` into the start of `buf`, and returns the written bytes.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is shorter than [`der_integer_len`](#method.der_integer_len).
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// let mut buf = [0; 34];
+            /// assert_eq!(I256::from(-129i16).write_der_integer(&mut buf), Ok(&[0x02, 0x02, 0xff, 0x7f][..]));
+            /// assert_eq!(I256::from(-1i8).write_der_integer(&mut buf), Ok(&[0x02, 0x01, 0xff][..]));
+            /// ```
+            #[inline]
+            pub fn write_der_integer<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], BufferTooSmallError> {
+                self.bits.write_der(buf, self.is_negative())
+            }
+
+            /// Returns the DER encoding of the integer as an `INTEGER`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// let n = I256::from(-1000i16);
+            /// assert_eq!(n.to_der_integer(), [0x02, 0x02, 0xfc, 0x18]);
+            /// assert_eq!(I256::from_der_integer(&n.to_der_integer()), Ok(n));
+            /// ```
+            #[cfg(feature = "alloc")]
+            #[inline]
+            pub fn to_der_integer(&self) -> Vec<u8> {
+                let mut out = vec![0; self.der_integer_len()];
+                self.write_der_integer(&mut out).unwrap();
+                out
+            }
+
+            /// Decodes a DER-encoded `INTEGER` from the start of `buf`, and advances `buf` past the encoding.
+            ///
+            /// Only the canonical encoding of an integer is accepted. The slice is only advanced if decoding succeeds.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`DerDecodeError`](crate::errors::DerDecodeError) if `buf` doesn't start with the canonical encoding of an `INTEGER`, or if the integer is out of range of `Self`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            /// use bnum::errors::DerErrorKind;
+            ///
+            /// let mut buf = &[0x02, 0x01, 0x80, 0x02, 0x01, 0x7f][..];
+            /// assert_eq!(I256::decode_der_integer(&mut buf), Ok(I256::from(-128i16)));
+            /// assert_eq!(I256::decode_der_integer(&mut buf), Ok(I256::from(127u8)));
+            ///
+            /// assert_eq!(I256::decode_der_integer(&mut &[0x02, 0x02, 0xff, 0x80][..]).unwrap_err().kind(), &DerErrorKind::NonCanonical);
+            /// ```
+            #[inline]
+            pub fn decode_der_integer(buf: &mut &[u8]) -> Result<Self, DerDecodeError> {
+                $BUint::decode_der(buf, true).map(Self::from_bits)
+            }
+
+            /// Decodes a DER-encoded `INTEGER` which takes up the whole of `src`.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`DerDecodeError`](crate::errors::DerDecodeError) in the same cases as [`decode_der_integer`](#method.decode_der_integer), and also if `src` has bytes after the end of the encoding.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// assert_eq!(I256::from_der_integer(&[0x02, 0x01, 0xfe]), Ok(I256::from(-2i8)));
+            /// assert!(I256::from_der_integer(&[0x02, 0x01, 0xfe, 0x00]).is_err());
+            /// ```
+            pub fn from_der_integer(mut src: &[u8]) -> Result<Self, DerDecodeError> {
+                let out = Self::decode_der_integer(&mut src)?;
+                if !src.is_empty() {
+                    return Err(DerDecodeError::new(DerErrorKind::TrailingBytes));
+                }
+                Ok(out)
+            }
+        }
+
+        #[cfg(feature = "der")]
+        impl<const N: usize> ::der::FixedTag for $BUint<N> {
+            const TAG: ::der::Tag = ::der::Tag::Integer;
+        }
+
+        #[cfg(feature = "der")]
+        impl<const N: usize> ::der::EncodeValue for $BUint<N> {
+            #[inline]
+            fn value_len(&self) -> ::der::Result<::der::Length> {
+                ::der::Length::try_from(self.twos_complement_len(false))
+            }
+
+            fn encode_value(&self, writer: &mut impl ::der::Writer) -> ::der::Result<()> {
+                for i in (0..self.twos_complement_len(false)).rev() {
+                    writer.write_byte(self.padded_byte(i, false))?;
+                }
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "der")]
+        impl<'a, const N: usize> ::der::DecodeValue<'a> for $BUint<N> {
+            #[inline]
+            fn decode_value<R: ::der::Reader<'a>>(reader: &mut R, header: ::der::Header) -> ::der::Result<Self> {
+                Self::decode_der_content(u32::from(header.length) as usize, false, || reader.read_byte())
+            }
+        }
+
+        #[cfg(feature = "der")]
+        impl<const N: usize> ::der::FixedTag for $BInt<N> {
+            const TAG: ::der::Tag = ::der::Tag::Integer;
+        }
+
+        #[cfg(feature = "der")]
+        impl<const N: usize> ::der::EncodeValue for $BInt<N> {
+            #[inline]
+            fn value_len(&self) -> ::der::Result<::der::Length> {
+                ::der::Length::try_from(self.bits.twos_complement_len(self.is_negative()))
+            }
+
+            fn encode_value(&self, writer: &mut impl ::der::Writer) -> ::der::Result<()> {
+                let negative = self.is_negative();
+                for i in (0..self.bits.twos_complement_len(negative)).rev() {
+                    writer.write_byte(self.bits.padded_byte(i, negative))?;
+                }
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "der")]
+        impl<'a, const N: usize> ::der::DecodeValue<'a> for $BInt<N> {
+            #[inline]
+            fn decode_value<R: ::der::Reader<'a>>(reader: &mut R, header: ::der::Header) -> ::der::Result<Self> {
+                $BUint::decode_der_content(u32::from(header.length) as usize, true, || reader.read_byte()).map(Self::from_bits)
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::errors::DerErrorKind;
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+                use crate::types::{I1024, U1024};
+                use alloc::vec::Vec;
+
+                /// The DER encoding of the two's complement big-endian bytes of a primitive integer, for comparison.
+                fn der_bytes(bytes: &[u8]) -> Vec<u8> {
+                    let mut start = 0;
+                    while start + 1 < bytes.len() && ((bytes[start] == 0 && bytes[start + 1] >> 7 == 0) || (bytes[start] == 0xff && bytes[start + 1] >> 7 == 1)) {
+                        start += 1;
+                    }
+                    let content = &bytes[start..];
+                    [&[0x02, content.len() as u8], content].concat()
+                }
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_der(u: utest, i: itest) -> bool {
+                        let (big_u, big_i) = (UTEST::from(u), ITEST::from(i));
+                        let u_encoded = der_bytes(&[&[0][..], &u.to_be_bytes()].concat());
+                        let i_encoded = der_bytes(&i.to_be_bytes());
+                        big_u.to_der_integer() == u_encoded
+                            && big_u.der_integer_len() == u_encoded.len()
+                            && UTEST::from_der_integer(&u_encoded) == Ok(big_u)
+                            && big_i.to_der_integer() == i_encoded
+                            && big_i.der_integer_len() == i_encoded.len()
+                            && ITEST::from_der_integer(&i_encoded) == Ok(big_i)
+                            && UTEST::from_der_integer(&i_encoded).is_ok() == (i >= 0)
+                            && ITEST::from_der_integer(&u_encoded).is_ok() == (u <= i128::MAX as u128)
+                    }
+                }
+
+                #[test]
+                fn der() {
+                    let cases: [(&[u8], DerErrorKind); 11] = [
+                        (&[], DerErrorKind::InputTooShort),
+                        (&[0x02], DerErrorKind::InputTooShort),
+                        (&[0x03, 0x01, 0x00], DerErrorKind::UnexpectedTag),
+                        (&[0x02, 0x00], DerErrorKind::NonCanonical),
+                        (&[0x02, 0x02, 0x00, 0x01], DerErrorKind::NonCanonical),
+                        (&[0x02, 0x02, 0x00], DerErrorKind::InputTooShort),
+                        (&[0x02, 0x80, 0x01, 0x00, 0x00], DerErrorKind::NonCanonical),
+                        (&[0x02, 0x81, 0x01, 0x01], DerErrorKind::NonCanonical),
+                        (&[0x02, 0x82, 0x00, 0x01, 0x01], DerErrorKind::NonCanonical),
+                        (&[0x02, 0x01, 0x80], DerErrorKind::OutOfRange),
+                        (&[0x02, 0x01, 0x01, 0x00], DerErrorKind::TrailingBytes),
+                    ];
+                    for (src, kind) in cases {
+                        assert_eq!(UTEST::from_der_integer(src).unwrap_err().kind(), &kind);
+                    }
+                    assert_eq!(ITEST::from_der_integer(&[0x02, 0x02, 0xff, 0x80]).unwrap_err().kind(), &DerErrorKind::NonCanonical);
+
+                    let too_large = [&[0x02, 0x12, 0x01][..], &[0; 17]].concat();
+                    assert_eq!(UTEST::from_der_integer(&too_large).unwrap_err().kind(), &DerErrorKind::OutOfRange);
+                    assert_eq!(ITEST::from_der_integer(&too_large).unwrap_err().kind(), &DerErrorKind::OutOfRange);
+                    let u_max = UTEST::MAX.to_der_integer();
+                    assert_eq!(u_max.len(), 19);
+                    assert_eq!(ITEST::from_der_integer(&u_max).unwrap_err().kind(), &DerErrorKind::OutOfRange);
+
+                    let long = U1024::MAX >> 8u32;
+                    let encoded = long.to_der_integer();
+                    assert_eq!(&encoded[..4], &[0x02, 0x81, 0x80, 0x00]);
+                    assert_eq!(U1024::from_der_integer(&encoded), Ok(long));
+                    assert_eq!(I1024::from_der_integer(&encoded), Ok(I1024::MAX >> 7u32));
+                    assert_eq!(I1024::from_der_integer(&I1024::MIN.to_der_integer()), Ok(I1024::MIN));
+
+                    let mut buf = [0; 18];
+                    assert_eq!(UTEST::MAX.write_der_integer(&mut buf).unwrap_err().required(), 19);
+                }
+
+                #[cfg(feature = "der")]
+                quickcheck::quickcheck! {
+                    fn quickcheck_der_crate(u: utest) -> bool {
+                        use ::der::{Decode, Encode};
+
+                        let big_u = UTEST::from(u);
+                        let mut buf = [0; 32];
+                        let encoded = u.encode_to_slice(&mut buf).unwrap();
+                        let mut big_buf = [0; 32];
+                        big_u.encode_to_slice(&mut big_buf).unwrap() == encoded
+                            && UTEST::from_der(encoded) == Ok(big_u)
+                            && ITEST::from_der(&big_u.to_der_integer()).is_ok() == (u <= i128::MAX as u128)
+                    }
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(der);
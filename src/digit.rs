@@ -114,8 +114,6 @@ macro_rules! digit_module {
                     (a % rhs as DoubleDigit) as Digit,
                 )
             }
-
-            pub const HEX_PADDING: usize = BITS as usize / 4;
         }
     };
 }
@@ -0,0 +1,31 @@
+use core::fmt::{self, Display, Formatter};
+
+/// The error type that is returned when a buffer is too short to hold the output of a method which writes into a caller-provided buffer.
+///
+/// This error can occur for example when the [`write_str_radix`](crate::BUint::write_str_radix) method of [`BUint`](crate::BUint) is called with a buffer that has fewer bytes than the number of digits of the integer.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct BufferTooSmallError {
+    pub(crate) required: usize,
+}
+
+impl BufferTooSmallError {
+    /// Returns the minimum length that the buffer needed to have for the write to succeed.
+    pub const fn required(&self) -> usize {
+        self.required
+    }
+}
+
+impl Display for BufferTooSmallError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} buffer too small: at least {} bytes are required",
+            super::err_prefix!(),
+            self.required
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferTooSmallError {}
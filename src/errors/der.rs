@@ -0,0 +1,53 @@
+use core::fmt::{self, Display, Formatter};
+
+/// The reason that decoding a DER-encoded `INTEGER` failed.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum DerErrorKind {
+    /// The input ended before the end of the encoding.
+    InputTooShort,
+    /// The tag of the encoding is not the `INTEGER` tag.
+    UnexpectedTag,
+    /// The encoding is not the canonical one, for example because the content has a redundant leading byte or the length isn't in its shortest form.
+    NonCanonical,
+    /// The integer is out of range of the target type, for example because it is negative and the target type is unsigned.
+    OutOfRange,
+    /// The input contains bytes after the end of the encoding.
+    TrailingBytes,
+}
+
+/// The error type that is returned when decoding a DER-encoded `INTEGER` fails.
+///
+/// This error can occur for example when the [`from_der_integer`](crate::BUint::from_der_integer) method of [`BUint`](crate::BUint) is called with the encoding of a negative integer.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct DerDecodeError {
+    pub(crate) kind: DerErrorKind,
+}
+
+impl DerDecodeError {
+    pub(crate) const fn new(kind: DerErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// Returns the reason that decoding failed.
+    pub const fn kind(&self) -> &DerErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for DerDecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let description = match self.kind {
+            DerErrorKind::InputTooShort => "input too short",
+            DerErrorKind::UnexpectedTag => "unexpected tag",
+            DerErrorKind::NonCanonical => "non-canonical encoding",
+            DerErrorKind::OutOfRange => "integer out of range of target type",
+            DerErrorKind::TrailingBytes => "unexpected bytes after encoding",
+        };
+        write!(f, "{} invalid DER integer: {}", super::err_prefix!(), description)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DerDecodeError {}
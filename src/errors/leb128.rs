@@ -0,0 +1,44 @@
+use core::fmt::{self, Display, Formatter};
+
+/// The reason that decoding a LEB128-encoded integer failed.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum Leb128ErrorKind {
+    /// The input ended before a byte without the continuation bit.
+    InputTooShort,
+    /// The integer is out of range of the target type.
+    Overflow,
+}
+
+/// The error type that is returned when decoding a LEB128-encoded integer fails.
+///
+/// This error can occur for example when the [`read_uleb128`](crate::BUint::read_uleb128) method of [`BUint`](crate::BUint) is called with the encoding of an integer which has more bits than the type.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Leb128DecodeError {
+    pub(crate) kind: Leb128ErrorKind,
+}
+
+impl Leb128DecodeError {
+    pub(crate) const fn new(kind: Leb128ErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// Returns the reason that decoding failed.
+    pub const fn kind(&self) -> &Leb128ErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for Leb128DecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let description = match self.kind {
+            Leb128ErrorKind::InputTooShort => "input ended before the last byte",
+            Leb128ErrorKind::Overflow => "integer out of range of target type",
+        };
+        write!(f, "{} invalid LEB128 integer: {}", super::err_prefix!(), description)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Leb128DecodeError {}
@@ -5,8 +5,20 @@ mod macros;
 #[allow(unused_imports)]
 pub use macros::*;
 
+mod buffer;
+pub use buffer::*;
+
+mod der;
+pub use der::*;
+
+mod leb128;
+pub use leb128::*;
+
 mod parseint;
 pub use parseint::*;
 
+mod rlp;
+pub use rlp::*;
+
 mod tryfrom;
 pub use tryfrom::*;
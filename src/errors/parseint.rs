@@ -1,21 +1,105 @@
+use crate::ExpType;
 use core::fmt::{self, Debug, Display, Formatter};
 use core::num::IntErrorKind;
 
 /// The error type that is returned when parsing an integer from an invalid source.
 ///
 /// This error can occur when the `from_str_radix` or [`FromStr::from_str`](https://doc.rust-lang.org/core/str/trait.FromStr.html#tymethod.from_str) methods of e.g. [`BUint`](crate::BUint::from_str_radix) are called with an invalid input string.
+///
+/// Besides the [`IntErrorKind`](https://doc.rust-lang.org/core/num/enum.IntErrorKind.html), the error records the position and value of the invalid character, if there is one, and the bit width of the type that was being parsed, all of which are included in its [`Display`] output. It can be converted to and from [`core::num::ParseIntError`], but only the kind is kept by these conversions.
 #[derive(PartialEq, Eq, Clone)]
 pub struct ParseIntError {
     pub(crate) kind: IntErrorKind,
+    pub(crate) index: Option<usize>,
+    pub(crate) invalid_char: Option<char>,
+    pub(crate) bits: Option<ExpType>,
+}
+
+/// Decodes the UTF-8 character which starts at byte index `index` of `src`.
+const fn char_at(src: &[u8], index: usize) -> char {
+    let first = src[index] as u32;
+    let (len, init) = match first {
+        0x00..=0x7f => return first as u8 as char,
+        // a continuation byte, so `index` is not at a character boundary
+        0x80..=0xbf => return char::REPLACEMENT_CHARACTER,
+        0xc0..=0xdf => (2, first & 0x1f),
+        0xe0..=0xef => (3, first & 0x0f),
+        _ => (4, first & 0x07),
+    };
+    let mut code = init;
+    let mut i = 1;
+    while i < len && index + i < src.len() {
+        code = (code << 6) | (src[index + i] as u32 & 0x3f);
+        i += 1;
+    }
+    match char::from_u32(code) {
+        Some(c) => c,
+        None => char::REPLACEMENT_CHARACTER,
+    }
 }
 
 impl ParseIntError {
+    /// Creates an error of the given kind which is not caused by a particular character, for a target type with `bits` bits.
+    pub(crate) const fn new(kind: IntErrorKind, bits: ExpType) -> Self {
+        Self {
+            kind,
+            index: None,
+            invalid_char: None,
+            bits: Some(bits),
+        }
+    }
+
+    /// Creates an error caused by the character starting at byte index `index` of `src`, for a target type with `bits` bits.
+    pub(crate) const fn invalid_digit(src: &[u8], index: usize, bits: ExpType) -> Self {
+        Self {
+            kind: IntErrorKind::InvalidDigit,
+            index: Some(index),
+            invalid_char: Some(char_at(src, index)),
+            bits: Some(bits),
+        }
+    }
+
     /// Returns the enum [`IntErrorKind`](https://doc.rust-lang.org/core/num/enum.IntErrorKind.html), which shows the reason that the parsing input was invalid.
     pub const fn kind(&self) -> &IntErrorKind {
         &self.kind
     }
 
-    const fn description(&self) -> &str {
+    /// Returns the byte index in the source string of the invalid character which caused the error, if the error was caused by an invalid character.
+    pub const fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Returns the invalid character which caused the error, if the error was caused by an invalid character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bnum::types::U256;
+    ///
+    /// let err = U256::from_str_radix("12€4", 10).unwrap_err();
+    /// assert_eq!(err.invalid_char(), Some('€'));
+    /// assert_eq!(err.index(), Some(2));
+    /// ```
+    pub const fn invalid_char(&self) -> Option<char> {
+        self.invalid_char
+    }
+
+    /// Returns the number of bits of the type that was being parsed, if known.
+    ///
+    /// This is `None` only for errors converted from a [`core::num::ParseIntError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bnum::types::I512;
+    ///
+    /// assert_eq!(I512::from_str_radix("", 10).unwrap_err().bits(), Some(512));
+    /// ```
+    pub const fn bits(&self) -> Option<ExpType> {
+        self.bits
+    }
+
+    pub(crate) const fn description(&self) -> &str {
         match &self.kind {
             IntErrorKind::Empty => "attempt to parse integer from empty string",
             IntErrorKind::InvalidDigit => {
@@ -30,14 +114,25 @@ impl ParseIntError {
             IntErrorKind::Zero => {
                 "attempt to parse the integer `0` which cannot be represented by the target type"
             }
-            _ => panic!("unsupported `IntErrorKind` variant"), // necessary as `IntErrorKind` is non-exhaustive
+            // `IntErrorKind` is non-exhaustive, and errors converted from `core::num::ParseIntError` may have newer variants
+            _ => "attempt to parse integer from invalid string",
         }
     }
 }
 
 impl Display for ParseIntError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{} {}", super::err_prefix!(), self.description())
+        write!(f, "{} {}", super::err_prefix!(), self.description())?;
+        if let Some(c) = self.invalid_char {
+            write!(f, " {:?}", c)?;
+        }
+        if let Some(index) = self.index {
+            write!(f, " at byte index {}", index)?;
+        }
+        match self.bits {
+            Some(bits) => write!(f, " (target type has {} bits)", bits),
+            None => Ok(()),
+        }
     }
 }
 
@@ -46,3 +141,31 @@ impl Debug for ParseIntError {
         Display::fmt(&self, f)
     }
 }
+
+impl From<core::num::ParseIntError> for ParseIntError {
+    #[inline]
+    fn from(err: core::num::ParseIntError) -> Self {
+        Self {
+            kind: *err.kind(),
+            index: None,
+            invalid_char: None,
+            bits: None,
+        }
+    }
+}
+
+impl From<ParseIntError> for core::num::ParseIntError {
+    /// Converts the error to a [`core::num::ParseIntError`] with the same kind. As `core::num::ParseIntError` can't be constructed directly, it is obtained by parsing a primitive integer from a string which causes an error of the same kind.
+    fn from(err: ParseIntError) -> Self {
+        match err.kind {
+            IntErrorKind::Empty => "".parse::<u8>().unwrap_err(),
+            IntErrorKind::PosOverflow => "256".parse::<u8>().unwrap_err(),
+            IntErrorKind::NegOverflow => "-129".parse::<i8>().unwrap_err(),
+            IntErrorKind::Zero => "0".parse::<core::num::NonZeroU8>().unwrap_err(),
+            _ => "a".parse::<u8>().unwrap_err(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseIntError {}
@@ -0,0 +1,53 @@
+use core::fmt::{self, Display, Formatter};
+
+/// The reason that decoding an RLP-encoded integer failed.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum RlpErrorKind {
+    /// The input ended before the end of the encoding.
+    InputTooShort,
+    /// The input is an RLP list instead of a string.
+    UnexpectedList,
+    /// The encoding is not the canonical one, for example because the integer has leading zero bytes or the length prefix is longer than necessary.
+    NonCanonical,
+    /// The integer is too large to be represented by the target type.
+    Overflow,
+    /// The input contains bytes after the end of the encoding.
+    TrailingBytes,
+}
+
+/// The error type that is returned when decoding an RLP-encoded integer fails.
+///
+/// This error can occur for example when the [`from_rlp`](crate::BUint::from_rlp) method of [`BUint`](crate::BUint) is called with an encoding which has leading zero bytes.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct RlpDecodeError {
+    pub(crate) kind: RlpErrorKind,
+}
+
+impl RlpDecodeError {
+    pub(crate) const fn new(kind: RlpErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// Returns the reason that decoding failed.
+    pub const fn kind(&self) -> &RlpErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for RlpDecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let description = match self.kind {
+            RlpErrorKind::InputTooShort => "input too short",
+            RlpErrorKind::UnexpectedList => "expected string, found list",
+            RlpErrorKind::NonCanonical => "non-canonical encoding",
+            RlpErrorKind::Overflow => "integer too large to fit in target type",
+            RlpErrorKind::TrailingBytes => "unexpected bytes after encoding",
+        };
+        write!(f, "{} invalid RLP integer: {}", super::err_prefix!(), description)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RlpDecodeError {}
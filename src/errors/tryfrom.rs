@@ -1,16 +1,64 @@
+use crate::ExpType;
 use core::fmt::{self, Display, Formatter};
 
 /// The error type that is returned when a failed conversion from an integer occurs.
 ///
-/// This error will occur for example when using the [`TryFrom`](https://doc.rust-lang.org/core/convert/trait.TryFrom.html) trait to convert a negative [`i32`] to a [`BUint`](crate::BUint).
+/// This error will occur for example when using the [`TryFrom`](https://doc.rust-lang.org/core/convert/trait.TryFrom.html) trait to convert a negative [`i32`] to a [`BUint`](crate::BUint). The bit width of the target type is recorded and included in the [`Display`] output. The error can be converted to and from [`core::num::TryFromIntError`].
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
-pub struct TryFromIntError(pub(crate) ());
+pub struct TryFromIntError {
+    pub(crate) bits: Option<ExpType>,
+}
 
-const ERROR_MESSAGE: &str = concat!(super::err_prefix!(), "out of range integral type conversion attempted");
+impl TryFromIntError {
+    /// Creates an error for a conversion to a type with `bits` bits.
+    pub(crate) const fn new(bits: ExpType) -> Self {
+        Self { bits: Some(bits) }
+    }
+
+    /// Returns the number of bits of the type that the conversion was to, if known.
+    ///
+    /// This is `None` only for errors converted from a [`core::num::TryFromIntError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bnum::types::U256;
+    ///
+    /// assert_eq!(U256::try_from(-1i8).unwrap_err().bits(), Some(256));
+    /// assert_eq!(u16::try_from(U256::MAX).unwrap_err().bits(), Some(16));
+    /// ```
+    pub const fn bits(&self) -> Option<ExpType> {
+        self.bits
+    }
+}
+
+const ERROR_MESSAGE: &str = concat!(super::err_prefix!(), " out of range integral type conversion attempted");
 
 impl Display for TryFromIntError {
     #[inline]
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}", ERROR_MESSAGE)
+        write!(f, "{}", ERROR_MESSAGE)?;
+        match self.bits {
+            Some(bits) => write!(f, " (target type has {} bits)", bits),
+            None => Ok(()),
+        }
     }
-}
\ No newline at end of file
+}
+
+impl From<core::num::TryFromIntError> for TryFromIntError {
+    #[inline]
+    fn from(_err: core::num::TryFromIntError) -> Self {
+        Self { bits: None }
+    }
+}
+
+impl From<TryFromIntError> for core::num::TryFromIntError {
+    /// As `core::num::TryFromIntError` can't be constructed directly, it is obtained from a failing conversion between primitive integers.
+    #[inline]
+    fn from(_err: TryFromIntError) -> Self {
+        u8::try_from(256u16).unwrap_err()
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromIntError {}
@@ -1 +1,374 @@
-// TODO: implement casts from and to float for primitive types and buint, bint
\ No newline at end of file
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use crate::bigints::*;
+use super::RoundingMode;
+use crate::cast::{As, CastFrom};
+use crate::ExpType;
+use core::num::FpCategory;
+
+macro_rules! cast_from_int {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident; $($uint: ty), *; $($int: ty), *) => {
+        $(
+            impl<const W: usize, const MB: usize> CastFrom<$uint> for $Float<W, MB> {
+                #[inline]
+                fn cast_from(from: $uint) -> Self {
+                    let sig = $BUint::<{128 / $Digit::BITS as usize}>::from(from as u128);
+                    Self::round_parts(false, $BInt::ZERO, sig, false, RoundingMode::NearestTiesEven)
+                }
+            }
+        )*
+        $(
+            impl<const W: usize, const MB: usize> CastFrom<$int> for $Float<W, MB> {
+                #[inline]
+                fn cast_from(from: $int) -> Self {
+                    let sig = $BUint::<{128 / $Digit::BITS as usize}>::from(from.unsigned_abs() as u128);
+                    Self::round_parts(from.is_negative(), $BInt::ZERO, sig, false, RoundingMode::NearestTiesEven)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! cast_to_int {
+    ($Float: ident; $($int: ty), *) => {
+        $(
+            impl<const W: usize, const MB: usize> CastFrom<$Float<W, MB>> for $int {
+                /// Follows the semantics of the `as` operator: the value is truncated towards zero, out of range values saturate and NaN is converted to zero.
+                #[inline]
+                #[allow(unused_comparisons)]
+                fn cast_from(from: $Float<W, MB>) -> Self {
+                    if from.is_nan() {
+                        return 0;
+                    }
+                    match from.int_parts() {
+                        (false, Some(m)) if m <= <$int>::MAX as u128 => m as $int,
+                        (false, _) => <$int>::MAX,
+                        (true, Some(m)) if m <= (<$int>::MIN as i128).unsigned_abs() => (m as i128).wrapping_neg() as $int,
+                        (true, _) => <$int>::MIN,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Converts the float `$from` to the float type `$Target`, which may have a different digit type.
+macro_rules! convert {
+    ($from: expr, $Target: ty, $rounding: expr) => {{
+        let from = $from;
+        let negative = from.is_sign_negative();
+        let with_sign = |f: $Target| if negative { -f } else { f };
+        match from.classify() {
+            FpCategory::Nan => with_sign(<$Target>::NAN),
+            FpCategory::Infinite => with_sign(<$Target>::INFINITY),
+            FpCategory::Zero => with_sign(<$Target>::ZERO),
+            _ => {
+                // Two more bits than the target's significand are enough to round correctly, as long as any bits shifted out are kept track of.
+                let (significand, msb, sticky) = from.decode_narrowed(<$Target>::MB as ExpType + 2);
+                let msb = (msb.is_negative(), msb.unsigned_abs());
+                <$Target>::round_digits(negative, (msb.0, msb.1.digits()), significand.digits(), sticky, $rounding)
+            },
+        }
+    }};
+}
+
+macro_rules! cast_between {
+    ($Float: ident; $($From: ident), *) => {
+        $(
+            impl<const W: usize, const MB: usize, const W2: usize, const MB2: usize> CastFrom<$From<W2, MB2>> for $Float<W, MB> {
+                /// Rounds to nearest with ties to even, in the same way as [`convert`](crate::Float::convert).
+                #[inline]
+                fn cast_from(from: $From<W2, MB2>) -> Self {
+                    convert!(from, Self, RoundingMode::NearestTiesEven)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! cast {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            /// Converts `self` to a float with `W2` digits and `MB2` mantissa bits, rounding to nearest with ties to even if the value cannot be represented exactly.
+            ///
+            /// Widening conversions are always exact. Narrowing conversions are correctly rounded, produce subnormals where appropriate and overflow to infinity.
+            ///
+            /// This only converts between floats with the same digit type. To convert to a float with a different digit type, e.g. from `Float<1, 52>` to `FloatD16<1, 10>`, use the [`As`](crate::cast::As) trait, which rounds in the same way.
+            #[inline]
+            pub fn convert<const W2: usize, const MB2: usize>(self) -> $Float<W2, MB2> {
+                self.convert_with_rounding(RoundingMode::NearestTiesEven)
+            }
+
+            /// Converts `self` to a float with `W2` digits and `MB2` mantissa bits, rounding according to `rounding` if the value cannot be represented exactly.
+            ///
+            /// NaNs are converted to the quiet NaN of the target format with the same sign. When the value is too large to be represented, the result is either infinity or the largest finite value of the same sign, depending on `rounding`.
+            pub fn convert_with_rounding<const W2: usize, const MB2: usize>(self, rounding: RoundingMode) -> $Float<W2, MB2> {
+                convert!(self, $Float<W2, MB2>, rounding)
+            }
+
+            /// Returns the significand of `self` shifted right so that it has at most `bits` bits, the exponent of the most significant bit of `self`, and whether any of the bits shifted out were set. `self` must be finite and nonzero.
+            fn decode_narrowed(self, bits: ExpType) -> ($BUint<W>, $BInt<W>, bool) {
+                let (mant, e) = self.decode();
+                let msb = e + Self::exp_from(mant.bits() - 1);
+                let excess = mant.bits().saturating_sub(bits);
+                (mant >> excess, msb, mant.trailing_zeros() < excess)
+            }
+
+            /// Assembles an integer from its little-endian digits, which may be of a different width to `$Digit`, returning `None` if it doesn't fit.
+            fn uint_from_digits<D: Copy + Into<u64>>(digits: &[D]) -> Option<$BUint<W>> {
+                let digit_bits = core::mem::size_of::<D>() as ExpType * 8;
+                let mut out = $BUint::<W>::ZERO;
+                for &digit in digits.iter().rev() {
+                    if !out.is_zero() {
+                        if out.bits() + digit_bits > Self::BITS {
+                            return None;
+                        }
+                        out <<= digit_bits;
+                    }
+                    let digit: u64 = digit.into();
+                    let d: $BUint<W> = digit.as_();
+                    if d.as_::<u64>() != digit {
+                        return None;
+                    }
+                    out |= d;
+                }
+                Some(out)
+            }
+
+            /// Rounds `(-1)^negative * significand * 2^(msb - significand.bits() + 1)` to this format, where `msb` is the exponent of the most significant bit, given as its sign and the little-endian digits of its magnitude. `significand` must be nonzero and have at most `MB + 2` bits, and `sticky` is as for `round_parts`.
+            ///
+            /// The digits can be of any width, so this can round a value decoded from a float with a different digit type.
+            fn round_digits<D: Copy + Into<u64>>(negative: bool, msb: (bool, &[D]), significand: &[D], sticky: bool, rounding: RoundingMode) -> Self {
+                let significand = Self::uint_from_digits(significand).expect("significand must fit");
+                let msb = match Self::uint_from_digits(msb.1) {
+                    Some(magnitude) if magnitude.bits() < Self::BITS => {
+                        let magnitude = $BInt::from_bits(magnitude);
+                        if msb.0 { -magnitude } else { magnitude }
+                    },
+                    // The exponent is out of range of this format's exponent type, so the value is certainly out of range of this format.
+                    _ => {
+                        let f = if msb.0 {
+                            if rounding.round_up(negative, false, false, true) {
+                                Self::MIN_POSITIVE_SUBNORMAL
+                            } else {
+                                Self::ZERO
+                            }
+                        } else if rounding.overflows_to_infinity(negative) {
+                            Self::INFINITY
+                        } else {
+                            Self::MAX
+                        };
+                        return if negative { -f } else { f };
+                    },
+                };
+                let e = msb.saturating_sub(Self::exp_from(significand.bits() - 1));
+                Self::round_parts(negative, e, significand, sticky, rounding)
+            }
+
+            /// Returns the sign of `self` and the magnitude of its integer part, or `None` for the magnitude if it is too large to fit in a `u128`. `self` must not be NaN.
+            pub(crate) fn int_parts(self) -> (bool, Option<u128>) {
+                let negative = self.is_sign_negative();
+                if self.is_infinite() {
+                    return (negative, None);
+                }
+                let (_, exp, sig) = self.trunc().to_parts();
+                if sig.is_zero() {
+                    return (negative, Some(0));
+                }
+                let magnitude = if exp.is_negative() {
+                    // `trunc` has cleared the fractional bits, so no set bits are shifted out.
+                    u128::try_from(sig >> exp.unsigned_abs().as_::<ExpType>()).ok()
+                } else if exp >= Self::exp_from(u128::BITS as ExpType) {
+                    None
+                } else {
+                    let exp: u32 = exp.as_();
+                    u128::try_from(sig).ok().filter(|s| s.leading_zeros() >= exp).map(|s| s << exp)
+                };
+                (negative, magnitude)
+            }
+        }
+
+        cast_from_int!($Float, $BUint, $BInt, $Digit; u8, u16, u32, u64, u128, usize; i8, i16, i32, i64, i128, isize);
+
+        cast_to_int!($Float; u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+        impl<const W: usize, const MB: usize> CastFrom<f64> for $Float<W, MB> {
+            #[inline]
+            fn cast_from(from: f64) -> Self {
+                $Float::<{64 / $Digit::BITS as usize}, 52>::from_bits(from.to_bits().as_()).convert()
+            }
+        }
+
+        impl<const W: usize, const MB: usize> CastFrom<f32> for $Float<W, MB> {
+            #[inline]
+            fn cast_from(from: f32) -> Self {
+                // Every `f32` is exactly representable as an `f64`.
+                Self::cast_from(from as f64)
+            }
+        }
+
+        impl<const W: usize, const MB: usize> CastFrom<$Float<W, MB>> for f64 {
+            #[inline]
+            fn cast_from(from: $Float<W, MB>) -> Self {
+                let f: $Float<{64 / $Digit::BITS as usize}, 52> = from.convert();
+                f64::from_bits(f.to_bits().as_())
+            }
+        }
+
+        impl<const W: usize, const MB: usize> CastFrom<$Float<W, MB>> for f32 {
+            #[inline]
+            fn cast_from(from: $Float<W, MB>) -> Self {
+                // Rounding to `f64` with round-to-odd and then to `f32` gives a correctly rounded result, as `f64` has more than twice as many significand bits as `f32`.
+                let f: $Float<{64 / $Digit::BITS as usize}, 52> = from.convert_with_rounding(RoundingMode::TowardZero);
+                let mut bits: u64 = f.to_bits().as_();
+                if f.is_finite() && f.convert::<W, MB>() != from {
+                    bits |= 1;
+                }
+                f64::from_bits(bits) as f32
+            }
+        }
+    };
+}
+
+super::float_impl!(cast);
+
+cast_between!(Float; Float, FloatD32, FloatD16, FloatD8);
+cast_between!(FloatD32; Float, FloatD32, FloatD16, FloatD8);
+cast_between!(FloatD16; Float, FloatD32, FloatD16, FloatD8);
+cast_between!(FloatD8; Float, FloatD32, FloatD16, FloatD8);
+
+#[cfg(test)]
+mod tests {
+    use super::RoundingMode;
+    use crate::cast::As;
+    use crate::{BUint, F64};
+
+    type F128 = crate::Float::<2, 112>;
+
+    fn f128(bits: u128) -> F128 {
+        F128::from_bits(BUint::from(bits))
+    }
+
+    #[test]
+    fn convert_round_trip() {
+        for f in [1.0f64, 0.1, -core::f64::consts::PI, 1e300, -1e-310, f64::MAX, f64::MIN_POSITIVE, f64::INFINITY, -0.0] {
+            let wide: F128 = F64::from(f).convert();
+            let narrow: F64 = wide.convert();
+            assert_eq!(narrow.to_bits(), f.to_bits().into(), "converting {} failed", f);
+        }
+        assert!(F64::NAN.convert::<2, 112>().is_nan());
+    }
+
+    #[test]
+    fn convert_widening() {
+        let one: F128 = F64::ONE.convert();
+        assert_eq!(one.to_bits(), F128::ONE.to_bits());
+
+        // The smallest positive f64 subnormal, 2^-1074, is a normal binary128 value.
+        let tiny: F128 = F64::MIN_POSITIVE_SUBNORMAL.convert();
+        assert_eq!(tiny.to_bits(), BUint::from((16383u128 - 1074) << 112));
+    }
+
+    #[test]
+    fn convert_narrowing() {
+        // 1 + 2^-60 is not representable in binary64.
+        let f = f128((16383u128 << 112) | (1u128 << 52));
+        assert_eq!(f.convert::<1, 52>().to_bits(), 1.0f64.to_bits().into());
+        let up: F64 = f.convert_with_rounding(RoundingMode::TowardPositive);
+        assert_eq!(up.to_bits(), (1.0f64 + f64::EPSILON).to_bits().into());
+        let down: F64 = (-f).convert_with_rounding(RoundingMode::TowardPositive);
+        assert_eq!(down.to_bits(), (-1.0f64).to_bits().into());
+
+        // Exactly halfway between 1 and 1 + 2^-52: ties to even and ties away differ.
+        let half = f128((16383u128 << 112) | (1u128 << 59));
+        assert_eq!(half.convert::<1, 52>().to_bits(), 1.0f64.to_bits().into());
+        let away: F64 = half.convert_with_rounding(RoundingMode::NearestTiesAway);
+        assert_eq!(away.to_bits(), (1.0f64 + f64::EPSILON).to_bits().into());
+
+        // 2^-1075 is halfway between zero and the smallest subnormal; 3 * 2^-1076 rounds up to it.
+        let f = f128((16383u128 - 1075) << 112);
+        assert_eq!(f.convert::<1, 52>().to_bits(), 0u64.into());
+        let f = f128(((16383u128 - 1075) << 112) | (1u128 << 111));
+        assert_eq!(f.convert::<1, 52>().to_bits(), 1u64.into());
+
+        assert_eq!(F128::MAX.convert::<1, 52>().to_bits(), f64::INFINITY.to_bits().into());
+        let max: F64 = F128::MAX.convert_with_rounding(RoundingMode::TowardZero);
+        assert_eq!(max.to_bits(), f64::MAX.to_bits().into());
+        let min: F64 = F128::MIN.convert_with_rounding(RoundingMode::TowardPositive);
+        assert_eq!(min.to_bits(), f64::MIN.to_bits().into());
+    }
+
+    #[test]
+    fn convert_from_binary256() {
+        type F256 = crate::FloatD8<32, 236>;
+        type F16 = crate::FloatD8<2, 10>;
+        type F8 = crate::FloatD8<1, 3>;
+
+        // `2^exp * (1 + 2^-n)`, optionally with an extra `2^-200` to break ties.
+        let f256 = |exp: i32, n: u32, sticky: bool| {
+            let one = crate::BUintD8::<32>::ONE;
+            let sig = (one << 200u32) | (one << (200 - n)) | if sticky { one } else { crate::BUintD8::ZERO };
+            F256::from_parts_rounded(false, crate::BIntD8::from(exp - 200), sig)
+        };
+        let to_f16 = |f: F256| f.convert::<2, 10>().to_bits().as_::<u16>();
+        let to_f8 = |f: F256| f.convert::<1, 3>().to_bits().as_::<u8>();
+
+        // The exponent of the least significant bit of the binary256 significand is out of range of the 8 and 16 bit exponent types.
+        assert_eq!(to_f8(F256::ONE), 0x38);
+        assert_eq!(to_f16(F256::ONE), 0x3c00);
+
+        assert_eq!(to_f16(f256(0, 11, false)), 0x3c00);
+        assert_eq!(to_f16(f256(0, 11, true)), 0x3c01);
+        assert_eq!(to_f16(f256(-24, 200, false)), 0x0001);
+        assert_eq!(to_f16(f256(-25, 200, true)), 0x0001);
+        assert_eq!(to_f16(f256(16, 1, false)), 0x7c00);
+        assert_eq!(to_f16(-F256::MAX), 0xfc00);
+        let max: F16 = F256::MAX.convert_with_rounding(RoundingMode::TowardZero);
+        assert_eq!(max.to_bits().as_::<u16>(), 0x7bff);
+
+        assert_eq!(to_f8(f256(0, 4, false)), 0x38);
+        assert_eq!(to_f8(f256(0, 4, true)), 0x39);
+        assert_eq!(to_f8(f256(-9, 200, false)), 0x01);
+        assert_eq!(to_f8(f256(-200, 200, false)), 0x00);
+        assert_eq!(to_f8(f256(100, 1, false)), 0x78);
+        let tiny: F8 = f256(-200, 200, false).convert_with_rounding(RoundingMode::TowardPositive);
+        assert_eq!(tiny.to_bits().as_::<u8>(), 0x01);
+        let max: F8 = f256(100, 1, false).convert_with_rounding(RoundingMode::TowardZero);
+        assert_eq!(max.to_bits().as_::<u8>(), 0x77);
+    }
+
+    #[test]
+    fn convert_between_digit_types() {
+        type F16 = crate::FloatD16<1, 10>;
+        type BF16 = crate::FloatD8<2, 7>;
+
+        assert_eq!(F64::from(0.1f64).as_::<F16>().to_bits().digits()[0], 0x2e66);
+        assert_eq!(F64::from(-0.1f64).as_::<BF16>().to_bits().digits(), &[0xcd, 0xbd]);
+        assert_eq!(F64::from(65520.0f64).as_::<F16>().to_bits().digits()[0], 0x7c00);
+        assert!(F64::NAN.as_::<BF16>().is_nan());
+        for f in [1.5f64, -0.0, 6.103515625e-5, 5.960464477539063e-8, 65504.0] {
+            let half = F64::from(f).as_::<F16>();
+            assert_eq!(half.as_::<F64>().to_bits(), f.to_bits().into());
+            assert_eq!(half.as_::<crate::FloatD8<8, 52>>().as_::<F64>().to_bits(), f.to_bits().into());
+        }
+    }
+
+    #[test]
+    fn cast_primitives() {
+        assert_eq!(F64::from(3.7f64).as_::<i32>(), 3);
+        assert_eq!(F64::from(-3.7f64).as_::<i32>(), -3);
+        assert_eq!(F64::from(-1.5f64).as_::<u8>(), 0);
+        assert_eq!(F64::from(300.0f64).as_::<u8>(), 255);
+        assert_eq!(F64::from(-1e300f64).as_::<i128>(), i128::MIN);
+        assert_eq!(F64::NAN.as_::<u64>(), 0);
+        assert_eq!(F64::INFINITY.as_::<u16>(), u16::MAX);
+
+        assert_eq!(u128::MAX.as_::<F64>().to_bits(), (u128::MAX as f64).to_bits().into());
+        assert_eq!((-12345i64).as_::<F64>().to_bits(), (-12345.0f64).to_bits().into());
+        assert_eq!(1.1f32.as_::<F128>().as_::<f32>(), 1.1f32);
+
+        // 1 + 2^-24 + 2^-60 rounds up to 1 + 2^-23 as an `f32`, but rounding to `f64` first would produce a tie that rounds down to 1.
+        let f = f128((16383u128 << 112) | (1u128 << 88) | (1u128 << 52));
+        assert_eq!(f.as_::<f32>(), 1.0f32 + f32::EPSILON);
+        assert_eq!(f.as_::<f64>(), 1.0f64 + 2.0f64.powi(-24));
+    }
+}
@@ -1,104 +1,101 @@
-use super::Float;
-use crate::digit::Digit;
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use crate::bigints::*;
 use core::num::FpCategory;
-use crate::BUint;
 
-struct Masks<const W: usize, const MB: usize>;
+macro_rules! classify {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            const Q_NAN_MASK: $BUint<W> = Self::NAN.to_bits();
+            const FINITE_MASK: $BUint<W> = Self::INFINITY.to_bits();
 
-impl<const W: usize, const MB: usize> Masks<W, MB> {
-    const Q_NAN_MASK: BUint<W> = Float::<W, MB>::NAN.to_bits();
-    const FINITE_MASK: BUint<W> = Float::<W, MB>::INFINITY.to_bits();
-}
-
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    #[inline]
-    pub const fn is_sign_positive(self) -> bool {
-        !self.is_sign_negative()
-    }
+            #[inline]
+            pub const fn is_sign_positive(self) -> bool {
+                !self.is_sign_negative()
+            }
 
-    #[inline]
-    pub const fn is_sign_negative(self) -> bool {
-        self.to_int().is_negative()
-    }
+            #[inline]
+            pub const fn is_sign_negative(self) -> bool {
+                self.to_int().is_negative()
+            }
 
-    #[inline]
-    pub const fn is_finite(self) -> bool {
-        self.to_bits() & Masks::<W, MB>::FINITE_MASK != Masks::<W, MB>::FINITE_MASK
-    }
+            #[inline]
+            pub fn is_finite(self) -> bool {
+                self.to_bits() & Self::FINITE_MASK != Self::FINITE_MASK
+            }
 
-    #[inline]
-    pub const fn is_infinite(self) -> bool {
-        self.abs().to_bits() == Masks::<W, MB>::FINITE_MASK
-        /*let bits = self.abs().to_bits();
-        bits.trailing_zeros() == Self::MB && bits.count_ones() == Self::EXPONENT_BITS as ExpType*/
-    }
+            #[inline]
+            pub fn is_infinite(self) -> bool {
+                self.abs().to_bits() == Self::FINITE_MASK
+            }
 
-    #[inline]
-    pub const fn is_nan(self) -> bool {
-        //!(self.mantissa().is_zero() || self.is_finite())
-        !self.is_finite() && self.to_bits().trailing_zeros() < Self::MB
-    }
+            #[inline]
+            pub fn is_nan(self) -> bool {
+                !self.is_finite() && self.to_bits().trailing_zeros() < Self::MB
+            }
 
-    #[inline]
-    pub const fn is_quiet_nan(self) -> bool {
-        self.to_bits() & Masks::<W, MB>::Q_NAN_MASK == Masks::<W, MB>::Q_NAN_MASK
-    }
+            #[inline]
+            pub fn is_quiet_nan(self) -> bool {
+                self.to_bits() & Self::Q_NAN_MASK == Self::Q_NAN_MASK
+            }
 
-    #[inline]
-    pub const fn is_quiet_indefinite_nan(self) -> bool {
-        self == Self::NAN
-    }
+            #[inline]
+            pub fn is_quiet_indefinite_nan(self) -> bool {
+                self == Self::NAN
+            }
 
-    #[inline] 
-    pub const fn is_signalling_nan(self) -> bool {
-        self.to_bits() & Masks::<W, MB>::Q_NAN_MASK == Self::INFINITY.to_bits()
-    }
+            #[inline]
+            pub fn is_signalling_nan(self) -> bool {
+                self.to_bits() & Self::Q_NAN_MASK == Self::INFINITY.to_bits()
+            }
 
-    #[inline]
-    pub const fn is_subnormal(self) -> bool {
-        /*!self.is_zero() && self.exponent().is_zero()*/
-        let lz = self.abs().to_bits().leading_zeros();
-        lz < Self::BITS && lz > Self::EXPONENT_BITS
-    }
+            #[inline]
+            pub const fn is_subnormal(self) -> bool {
+                let lz = self.abs().to_bits().leading_zeros();
+                lz < Self::BITS && lz > Self::EXPONENT_BITS
+            }
 
-    #[inline]
-    pub const fn is_normal(self) -> bool {
-        matches!(self.classify(), FpCategory::Normal)
-    }
+            #[inline]
+            pub fn is_normal(self) -> bool {
+                matches!(self.classify(), FpCategory::Normal)
+            }
 
-    #[inline]
-    pub const fn is_zero(&self) -> bool {
-        let mut i = 0;
-        while i < W - 1 {
-            if self.words()[i] != 0 {
-                return false;
+            #[inline]
+            pub const fn is_zero(&self) -> bool {
+                let mut i = 0;
+                while i < W - 1 {
+                    if self.words()[i] != 0 {
+                        return false;
+                    }
+                    i += 1;
+                }
+                let last = self.words()[W - 1];
+                last.trailing_zeros() >= $Digit::BITS - 1
             }
-            i += 1;
-        }
-        let last = self.words()[W - 1];
-        last.trailing_zeros() >= Digit::BITS - 1
-    }
 
-    #[inline]
-    pub const fn classify(self) -> FpCategory {
-        let u = self.abs().to_bits();
-        if u.is_zero() {
-            FpCategory::Zero
-        } else if u == Self::INFINITY.to_bits() {
-            FpCategory::Infinite
-        } else {
-            let u = u & Masks::<W, MB>::FINITE_MASK;
-            if u.is_zero() {
-                FpCategory::Subnormal
-            } else if u == Masks::<W, MB>::FINITE_MASK {
-                FpCategory::Nan
-            } else {
-                FpCategory::Normal
+            #[inline]
+            pub fn classify(self) -> FpCategory {
+                let u = self.abs().to_bits();
+                if u.is_zero() {
+                    FpCategory::Zero
+                } else if u == Self::INFINITY.to_bits() {
+                    FpCategory::Infinite
+                } else {
+                    let u = u & Self::FINITE_MASK;
+                    if u.is_zero() {
+                        FpCategory::Subnormal
+                    } else if u == Self::FINITE_MASK {
+                        FpCategory::Nan
+                    } else {
+                        FpCategory::Normal
+                    }
+                }
             }
         }
-    }
+    };
 }
 
+super::float_impl!(classify);
+
 #[cfg(test)]
 mod tests {
     use crate::F64;
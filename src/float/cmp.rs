@@ -1,105 +1,115 @@
-use super::Float;
-use crate::{Bint, BUint};
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use crate::bigints::*;
 use core::cmp::{PartialOrd, PartialEq, Ordering};
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    #[inline]
-    pub const fn max(self, other: Self) -> Self {
-        handle_nan!(other; self);
-        handle_nan!(self; other);
-        if self < other {
-            other
-        } else {
-            self
-        }
-    }
+macro_rules! cmp {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            #[inline]
+            pub fn max(self, other: Self) -> Self {
+                handle_nan!(other; self);
+                handle_nan!(self; other);
+                if self < other || (self == other && other.is_sign_positive()) {
+                    other
+                } else {
+                    self
+                }
+            }
 
-    #[inline]
-    pub const fn min(self, other: Self) -> Self {
-        handle_nan!(other; self);
-        handle_nan!(self; other);
-        if self > other {
-            other
-        } else {
-            self
-        }
-    }
+            #[inline]
+            pub fn min(self, other: Self) -> Self {
+                handle_nan!(other; self);
+                handle_nan!(self; other);
+                if self > other || (self == other && other.is_sign_negative()) {
+                    other
+                } else {
+                    self
+                }
+            }
 
-    #[inline]
-    pub const fn maximum(self, other: Self) -> Self {
-        handle_nan!(self; self);
-        handle_nan!(other; other);
-        if let Ordering::Less = self.total_cmp(&other) {
-            other
-        } else {
-            self
-        }
-    }
+            #[inline]
+            pub fn maximum(self, other: Self) -> Self {
+                handle_nan!(self; self);
+                handle_nan!(other; other);
+                if let Ordering::Less = self.total_cmp(&other) {
+                    other
+                } else {
+                    self
+                }
+            }
 
-    #[inline]
-    pub const fn minimum(self, other: Self) -> Self {
-        handle_nan!(self; self);
-        handle_nan!(other; other);
-        if let Ordering::Greater = self.total_cmp(&other) {
-            other
-        } else {
-            self
-        }
-    }
+            #[inline]
+            pub fn minimum(self, other: Self) -> Self {
+                handle_nan!(self; self);
+                handle_nan!(other; other);
+                if let Ordering::Greater = self.total_cmp(&other) {
+                    other
+                } else {
+                    self
+                }
+            }
 
-    #[inline]
-    pub const fn clamp(self, min: Self, max: Self) -> Self {
-        assert!(min <= max);
-        let mut x = self;
-        if x < min {
-            x = min;
-        }
-        if x > max {
-            x = max;
-        }
-        x
-    }
+            #[inline]
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                assert!(min <= max);
+                let mut x = self;
+                if x < min {
+                    x = min;
+                }
+                if x > max {
+                    x = max;
+                }
+                x
+            }
 
-    #[inline]
-    pub const fn total_cmp(&self, other: &Self) -> Ordering {
-        let left = self.to_int();
-        let right = other.to_int();
-        if left.is_negative() && right.is_negative() {
-            Bint::cmp(&left, &right).reverse()
-        } else {
-            Bint::cmp(&left, &right)
+            #[inline]
+            pub fn total_cmp(&self, other: &Self) -> Ordering {
+                let left = self.to_int();
+                let right = other.to_int();
+                if left.is_negative() && right.is_negative() {
+                    $BInt::cmp(&left, &right).reverse()
+                } else {
+                    $BInt::cmp(&left, &right)
+                }
+            }
         }
-    }
-}
 
-impl<const W: usize, const MB: usize> const PartialEq for Float<W, MB> {
-    #[inline]
-    fn eq(&self, other: &Self) -> bool {
-        handle_nan!(false; self, other);
-        (self.is_zero() && other.is_zero()) || BUint::eq(&self.to_bits(), &other.to_bits())
-    }
-}
+        impl<const W: usize, const MB: usize> PartialEq for $Float<W, MB> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                handle_nan!(false; self, other);
+                (self.is_zero() && other.is_zero()) || $BUint::eq(&self.to_bits(), &other.to_bits())
+            }
+        }
 
-impl<const W: usize, const MB: usize> const PartialOrd for Float<W, MB> {
-    #[inline]
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        handle_nan!(None; self, other);
-        if self.is_zero() && other.is_zero() {
-            return Some(Ordering::Equal);
+        impl<const W: usize, const MB: usize> PartialOrd for $Float<W, MB> {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                handle_nan!(None; self, other);
+                if self.is_zero() && other.is_zero() {
+                    return Some(Ordering::Equal);
+                }
+                Some(self.total_cmp(other))
+            }
         }
-        Some(self.total_cmp(other))
-    }
+    };
 }
 
+super::float_impl!(cmp);
+
 #[cfg(test)]
 mod tests {
+    use crate::F64;
 	use crate::test::test_bignum;
 
+    // Which zero `f64::max` and `f64::min` return when comparing `0.0` and `-0.0` is unspecified, so these cases are tested separately in `zero_signs`.
     test_bignum! {
-        function: <f64>::max(a: f64, b: f64)
+        function: <f64>::max(a: f64, b: f64),
+        skip: a == 0.0 && b == 0.0
     }
     test_bignum! {
-        function: <f64>::min(a: f64, b: f64)
+        function: <f64>::min(a: f64, b: f64),
+        skip: a == 0.0 && b == 0.0
     }
     test_bignum! {
         function: <f64>::maximum(a: f64, b: f64)
@@ -112,15 +122,20 @@ mod tests {
         skip: !(b <= c)
     }
 
+    #[test]
+    fn zero_signs() {
+        for (a, b) in [(F64::ZERO, F64::NEG_ZERO), (F64::NEG_ZERO, F64::ZERO)] {
+            assert_eq!(a.min(b).to_bits(), F64::NEG_ZERO.to_bits());
+            assert_eq!(a.max(b).to_bits(), F64::ZERO.to_bits());
+        }
+    }
+
     #[test]
     fn maximum() {
         let f1 = 0f64;
         let f2 = -0f64;
-        //println!("{:064b}", ((-0.0f64).div_euclid(f2)).to_bits());
-        let a = (crate::F64::from(f1).min(crate::F64::from(f2))).to_bits();
+        let a = (F64::from(f1).min(F64::from(f2))).to_bits();
         let b = (f1.min(f2)).to_bits();
-        /*println!("{:064b}", a);
-        println!("{:064b}", b);*/
         assert!(a == b.into());
     }
 
@@ -131,6 +146,6 @@ mod tests {
         function: <f64>::partial_cmp(a: ref &f64, b: ref &f64)
     }
     test_bignum! {
-        function: <f64>::eq(a: ref &f64, b: ref &f64)
+        function: <f64 as PartialEq>::eq(a: ref &f64, b: ref &f64)
     }
 }
\ No newline at end of file
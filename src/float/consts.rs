@@ -1,114 +1,293 @@
-use super::Float;
-use crate::uint::BUint;
-use crate::int::Bint;
-use crate::As;
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use crate::bigints::*;
+use crate::digit;
+use crate::ExpType;
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    pub const RADIX: u32 = 2;
+macro_rules! consts {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            pub const RADIX: u32 = 2;
 
-    pub const MANTISSA_DIGITS: u32 = MB as u32 + 1;
+            pub const MANTISSA_DIGITS: u32 = MB as u32 + 1;
 
-    pub const DIGITS: u32 = BUint::<W>::ONE.wrapping_shl(Self::MB).ilog10() as u32;
+            /// The number of decimal digits that can be represented without change, `floor(MB * log10(2))`.
+            pub const DIGITS: u32 = (MB as u128 * 301029995663981195 / 1000000000000000000) as u32;
 
-    pub const EPSILON: Self = {
-        let u = Self::EXP_BIAS.to_bits() - MB.as_::<BUint<W>>();
-        Self::from_bits(u << Self::MB)
-    };
+            pub const EPSILON: Self = {
+                if Self::EXPONENT_BITS >= u64::BITS as ExpType || (1u64 << (Self::EXPONENT_BITS - 1)) - 1 > MB as u64 {
+                    // Subtract `MB` from the exponent field of one.
+                    Self::from_bits(Self::ONE.to_bits().wrapping_sub(Self::shl_const(Self::uint_from_exp_type(Self::MB), Self::MB)))
+                } else {
+                    // `2^-MB` is subnormal, and equal to `2^(bias - 1) * 2^(1 - bias - MB)`.
+                    let bias = (1 << (Self::EXPONENT_BITS - 1)) - 1;
+                    Self::from_bits($BUint::power_of_two(bias - 1))
+                }
+            };
 
-    pub const EXP_BIAS: Bint<W> = Bint::MAX.wrapping_shr(Self::MB + 1);
+            pub const EXP_BIAS: $BInt<W> = $BInt::from_bits(Self::bit_range(0, Self::EXPONENT_BITS - 1));
 
-    pub const MIN: Self = {
-        let mut e = BUint::MAX;
-        e = e.wrapping_shr(Self::MB + 1);
-        e = e.wrapping_shl(Self::MB + 1);
-        let mut m = BUint::MAX;
-        m = m.wrapping_shr(Self::EXPONENT_BITS + 1);
-        Self::from_bits(e | m)
-    };
+            pub const MIN: Self = Self::MAX.negate();
 
-    pub const MIN_POSITIVE: Self = {
-        Self::from_bits(BUint::ONE.wrapping_shl(Self::MB))
-    };
-    pub const MAX_NEGATIVE: Self = -Self::MIN_POSITIVE;
-    pub const MAX: Self = Self::MIN.abs();
-
-    pub const MIN_EXP: Bint<W> = (-Self::EXP_BIAS).wrapping_add(Bint::ONE.wrapping_shl(1));
-    pub const MAX_EXP: Bint<W> = Self::EXP_BIAS.wrapping_add(Bint::ONE);
-    pub const MAX_UNBIASED_EXP: BUint<W> = Self::EXP_BIAS.to_bits() * BUint::TWO;
-    pub const MIN_10_EXP: Self = todo!();
-    pub const MAX_10_EXP: Self = todo!();
-
-    pub const MAX_SUBNORMAL: Self = Self::from_bits(BUint::MAX.wrapping_shr(Self::EXPONENT_BITS + 1));
-    pub const MIN_SUBNORMAL: Self = -Self::MAX_SUBNORMAL;
-    pub const MIN_POSITIVE_SUBNORMAL: Self = Self::from_bits(BUint::ONE);
-    pub const MAX_NEGATIVE_SUBNORMAL: Self = -Self::MIN_POSITIVE_SUBNORMAL;
-
-    pub const NAN: Self = {
-        let mut u = BUint::MAX;
-        u = u.wrapping_shl(1);
-        u = u.wrapping_shr(Self::MB);
-        u = u.wrapping_shl(Self::MB - 1);
-        Self::from_bits(u)
-    };
+            pub const MIN_POSITIVE: Self = Self::from_bits($BUint::power_of_two(Self::MB));
+            pub const MAX_NEGATIVE: Self = Self::MIN_POSITIVE.negate();
+            pub const MAX: Self = Self::from_parts(false, Self::bit_range(Self::MB + 1, Self::BITS - 1), Self::MANTISSA_MASK);
 
-    pub const QNAN: Self = {
-        let bits = Self::NAN.to_bits();
-        Self::from_bits(bits | (BUint::ONE << (Self::MB - 1)))
-    };
+            pub const MIN_EXP: $BInt<W> = $BInt::from_bits(Self::uint_from_exp_type(2)).wrapping_sub(Self::EXP_BIAS);
+            pub const MAX_EXP: $BInt<W> = Self::EXP_BIAS.wrapping_add($BInt::ONE);
+            pub const MAX_UNBIASED_EXP: $BUint<W> = Self::EXP_BIAS.to_bits().wrapping_add(Self::EXP_BIAS.to_bits());
 
-    pub const NEG_NAN: Self = -Self::NAN;
+            pub const MAX_SUBNORMAL: Self = Self::from_bits(Self::MANTISSA_MASK);
+            pub const MIN_SUBNORMAL: Self = Self::MAX_SUBNORMAL.negate();
+            pub const MIN_POSITIVE_SUBNORMAL: Self = Self::from_bits($BUint::ONE);
+            pub const MAX_NEGATIVE_SUBNORMAL: Self = Self::MIN_POSITIVE_SUBNORMAL.negate();
 
-    pub const NEG_QNAN: Self = -Self::QNAN;
+            pub const NAN: Self = Self::from_bits(Self::bit_range(Self::MB - 1, Self::BITS - 1));
 
-    pub const INFINITY: Self = {
-        let mut u = BUint::MAX;
-        u = u.wrapping_shl(1);
-        u = u.wrapping_shr(1 + Self::MB);
-        u = u.wrapping_shl(Self::MB);
-        Self::from_bits(u)
-    };
+            pub const QNAN: Self = Self::NAN;
 
-    pub const NEG_INFINITY: Self = {
-        let mut u = BUint::MAX;
-        u = u.wrapping_shr(Self::MB);
-        u = u.wrapping_shl(Self::MB);
-        Self::from_bits(u)
-    };
+            pub const NEG_NAN: Self = Self::NAN.negate();
 
-    pub const ZERO: Self = Self::from_bits(BUint::ZERO);
+            pub const NEG_QNAN: Self = Self::QNAN.negate();
 
-    pub const NEG_ZERO: Self = Self::from_words(*Bint::<W>::MIN.digits());
+            pub const INFINITY: Self = Self::from_bits(Self::bit_range(Self::MB, Self::BITS - 1));
 
-    pub const ONE: Self = {
-        let mut u = BUint::MAX;
-        u = u.wrapping_shl(2);
-        u = u.wrapping_shr(2 + Self::MB);
-        u = u.wrapping_shl(Self::MB);
-        Self::from_bits(u)
-    };
+            pub const NEG_INFINITY: Self = Self::INFINITY.negate();
 
-    pub const TWO: Self = {
-        let (exp, _) = Self::ONE.exp_mant();
-        Self::from_exp_mant(false, exp + BUint::ONE, BUint::ZERO)
-    };
+            pub const ZERO: Self = Self::from_bits($BUint::ZERO);
 
-    pub const HALF: Self = {
-        let (exp, _) = Self::ONE.exp_mant();
-        Self::from_exp_mant(false, exp - BUint::ONE, BUint::ZERO)
-    };
+            pub const NEG_ZERO: Self = Self::from_words(*$BInt::<W>::MIN.to_bits().digits());
+
+            pub const ONE: Self = Self::from_bits(Self::bit_range(Self::MB, Self::BITS - 2));
+
+            pub const TWO: Self = Self::from_bits($BUint::power_of_two(Self::BITS - 2));
+
+            pub const HALF: Self = Self::from_bits(Self::ONE.to_bits().wrapping_sub($BUint::power_of_two(Self::MB)));
+
+            pub const QUARTER: Self = Self::from_bits(Self::ONE.to_bits().wrapping_sub($BUint::power_of_two(Self::MB + 1)));
+
+            pub const NEG_ONE: Self = Self::ONE.negate();
+        }
+
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            #[inline]
+            const fn table_bit(table: &[u64; 16], index: usize) -> bool {
+                table[index / 64] & (1 << (index % 64)) != 0
+            }
+
+            /// Creates a float from the `len` bits of a constant's significand table starting at bit `msb`, multiplied by `2^(exp + msb - 1023)`.
+            ///
+            /// If `len` is `MB + 1`, the value is rounded to nearest using the next bit of the table. Since the constants are irrational, the bits beyond those stored are never all zero, so a set rounding bit always means the value lies above the halfway point. Otherwise, the value is truncated to `len` bits.
+            const fn from_table(table: &[u64; 16], msb: usize, len: usize, exp: i32) -> Self {
+                let mut digits = [0; W];
+                let mut i = 1;
+                while i < len {
+                    if Self::table_bit(table, msb - i) {
+                        let j = Self::MB - i as ExpType;
+                        digits[(j >> digit::$Digit::BIT_SHIFT) as usize] |= 1 << (j & digit::$Digit::BITS_MINUS_1);
+                    }
+                    i += 1;
+                }
+                let mut mant = $BUint::from_digits(digits);
+                let mut exp = exp + msb as i32 - 1023;
+                if len == MB + 1 && msb >= len && Self::table_bit(table, msb - len) {
+                    mant = mant.wrapping_add($BUint::ONE);
+                    if mant.bit(Self::MB) {
+                        // The mantissa carried into the exponent.
+                        mant = $BUint::ZERO;
+                        exp += 1;
+                    }
+                }
+                let bias = Self::EXP_BIAS.to_bits();
+                let biased_exp = if exp < 0 {
+                    bias.wrapping_sub(Self::uint_from_exp_type(exp.unsigned_abs() as ExpType))
+                } else {
+                    bias.wrapping_add(Self::uint_from_exp_type(exp as ExpType))
+                };
+                assert!(
+                    !biased_exp.is_zero() && biased_exp.bits() <= Self::EXPONENT_BITS && biased_exp.count_ones() < Self::EXPONENT_BITS,
+                    "mathematical constant is outside the normal range of this format"
+                );
+                Self::from_parts(false, Self::shl_const(biased_exp, Self::MB), mant)
+            }
+
+            /// Rounds the constant with the given significand table and unbiased exponent to the nearest value of this format.
+            const fn round_constant(table: &[u64; 16], exp: i32) -> Self {
+                assert!(MB + 2 <= 1024, "mathematical constants are only stored to 1024 bits of precision");
+                Self::from_table(table, 1023, MB + 1, exp)
+            }
+
+            /// Splits the constant into a high part, which has only the upper half of its significand bits set, and a low part, which is the rest of the constant rounded to this format.
+            ///
+            /// The product of the high part and an integer of at most `(MB + 1) / 2` bits is exact, which is what argument reduction in the elementary functions relies on.
+            const fn split_constant(table: &[u64; 16], exp: i32) -> (Self, Self) {
+                let high_len = MB.div_ceil(2);
+                let mut msb = 1023 - high_len;
+                while !Self::table_bit(table, msb) {
+                    msb -= 1;
+                }
+                let low_len = if msb < MB { msb + 1 } else { MB + 1 };
+                (
+                    Self::from_table(table, 1023, high_len, exp),
+                    Self::from_table(table, msb, low_len, exp),
+                )
+            }
+
+            pub(super) const LN_2_SPLIT: (Self, Self) = Self::split_constant(&significands::LN_2, -1);
+
+            pub(super) const FRAC_PI_2_SPLIT: (Self, Self) = Self::split_constant(&significands::PI, 0);
+
+            /// Archimedes' constant (π), correctly rounded to this format.
+            pub const PI: Self = Self::round_constant(&significands::PI, 1);
+
+            /// The full circle constant (τ = 2π), correctly rounded to this format.
+            pub const TAU: Self = Self::round_constant(&significands::PI, 2);
+
+            /// π/2, correctly rounded to this format.
+            pub const FRAC_PI_2: Self = Self::round_constant(&significands::PI, 0);
+
+            /// π/3, correctly rounded to this format.
+            pub const FRAC_PI_3: Self = Self::round_constant(&significands::FRAC_PI_3, 0);
+
+            /// π/4, correctly rounded to this format.
+            pub const FRAC_PI_4: Self = Self::round_constant(&significands::PI, -1);
+
+            /// π/6, correctly rounded to this format.
+            pub const FRAC_PI_6: Self = Self::round_constant(&significands::FRAC_PI_3, -1);
+
+            /// π/8, correctly rounded to this format.
+            pub const FRAC_PI_8: Self = Self::round_constant(&significands::PI, -2);
+
+            /// 1/π, correctly rounded to this format.
+            pub const FRAC_1_PI: Self = Self::round_constant(&significands::FRAC_1_PI, -2);
+
+            /// 2/π, correctly rounded to this format.
+            pub const FRAC_2_PI: Self = Self::round_constant(&significands::FRAC_1_PI, -1);
+
+            /// 2/sqrt(π), correctly rounded to this format.
+            pub const FRAC_2_SQRT_PI: Self = Self::round_constant(&significands::FRAC_2_SQRT_PI, 0);
+
+            /// sqrt(2), correctly rounded to this format.
+            pub const SQRT_2: Self = Self::round_constant(&significands::SQRT_2, 0);
+
+            /// 1/sqrt(2), correctly rounded to this format.
+            pub const FRAC_1_SQRT_2: Self = Self::round_constant(&significands::SQRT_2, -1);
 
-    pub const QUARTER: Self = {
-        let (exp, _) = Self::ONE.exp_mant();
-        Self::from_exp_mant(false, exp - BUint::TWO, BUint::ZERO)
+            /// Euler's number (e), correctly rounded to this format.
+            pub const E: Self = Self::round_constant(&significands::E, 1);
+
+            /// log<sub>2</sub>(10), correctly rounded to this format.
+            pub const LOG2_10: Self = Self::round_constant(&significands::LOG2_10, 1);
+
+            /// log<sub>2</sub>(e), correctly rounded to this format.
+            pub const LOG2_E: Self = Self::round_constant(&significands::LOG2_E, 0);
+
+            /// log<sub>10</sub>(2), correctly rounded to this format.
+            pub const LOG10_2: Self = Self::round_constant(&significands::LOG10_2, -2);
+
+            /// log<sub>10</sub>(e), correctly rounded to this format.
+            pub const LOG10_E: Self = Self::round_constant(&significands::LOG10_E, -2);
+
+            /// ln(2), correctly rounded to this format.
+            pub const LN_2: Self = Self::round_constant(&significands::LN_2, -1);
+
+            /// ln(10), correctly rounded to this format.
+            pub const LN_10: Self = Self::round_constant(&significands::LN_10, 1);
+        }
     };
-    
-    pub const NEG_ONE: Self = Self::from_bits(Self::ONE.bits | Self::NEG_ZERO.bits);
+}
+
+super::float_impl!(consts);
+
+/// Significands of mathematical constants, truncated to 1024 bits. The most significant bit of each is the units bit, so each represents a value in the range `[1, 2)`.
+mod significands {
+    pub const PI: [u64; 16] = [
+        0x98da48361c55d39a, 0xc2007cb8a163bf05, 0x49286651ece45b3d, 0xae9f24117c4b1fe6,
+        0xee386bfb5a899fa5, 0x0bff5cb6f406b7ed, 0xf44c42e9a637ed6b, 0xe485b576625e7ec6,
+        0x4fe1356d6d51c245, 0x302b0a6df25f1437, 0xef9519b3cd3a431b, 0x514a08798e3404dd,
+        0x020bbea63b139b22, 0x29024e088a67cc74, 0xc4c6628b80dc1cd1, 0xc90fdaa22168c234,
+    ];
+
+    pub const FRAC_PI_3: [u64; 16] = [
+        0x10918579683937bc, 0x8155a87b16427f59, 0x30c5998bf342e77e, 0x746a180ba8321544,
+        0x9ed047fce7066a6e, 0x5d54e879f8047a9e, 0xa2dd81f1197a9e47, 0x985923a441945484,
+        0x8a96239e48e12c2e, 0xcac75c494c3f62cf, 0x9fb8bbcd337c2cbc, 0x8b86b0510978033e,
+        0x56b27f197cb7bcc1, 0x70ac3405b19a884d, 0x2dd99707ab3d688b, 0x860a91c16b9b2c23,
+    ];
+
+    pub const FRAC_1_PI: [u64; 16] = [
+        0x7527bac7ebe5f17b, 0x4f463f669e5fea2d, 0x6d367ecf27cb09b7, 0xef2f118b5a0a6d1f,
+        0x1ff897ffde05980f, 0x9c845f8bbdf9283b, 0x3991d639835339f4, 0xe99c7026b45f7e41,
+        0xe88235f52ebb4484, 0xfe1deb1cb129a73e, 0x06492eea09d1921c, 0xb7246e3a424dd2e0,
+        0xfe5163abdebbc561, 0xdb6295993c439041, 0xfc2757d1f534ddc0, 0xa2f9836e4e441529,
+    ];
+
+    pub const FRAC_2_SQRT_PI: [u64; 16] = [
+        0x18d3e91adcff6c03, 0x50754b409e94d32d, 0xac2c88bbba81b1c7, 0xeb9feb2436f2f272,
+        0xd27a3282dada7316, 0x9522f2f93e16b2a3, 0x9c22f47f7b7fb57c, 0x52561dcc244dc65e,
+        0x74f76f877ffec251, 0xbd1f4eee48e1ca78, 0x40c036096cc79aeb, 0xc0759cf859270f11,
+        0x39a15830cce620b0, 0x1409a0ebac3e7517, 0x71d48a7f6bfec344, 0x906eba8214db688d,
+    ];
+
+    pub const SQRT_2: [u64; 16] = [
+        0xeaa4a0899040ca4a, 0xf52f120f836e582e, 0xcb2a634331f3c84d, 0xc6d5a8a38bb7e9dc,
+        0x460abc722f7c4e33, 0xcab1bc911688458a, 0x53059c6011bc337b, 0xd2202e8742af1f4e,
+        0x780487363dfa2768, 0x0f74a85e439c7b4a, 0xa8b1fe6fdc83db39, 0x4afc83043ab8a2c3,
+        0xed17ac8583339915, 0x1d6f60ba893ba84c, 0x597d89b3754abe9f, 0xb504f333f9de6484,
+    ];
+
+    pub const E: [u64; 16] = [
+        0xbc0ab182b324fb61, 0x30acca4f483a797a, 0x1df158a136ade735, 0xe2a689daf3efe872,
+        0x984f0c70e0e68b77, 0xb557135e7f57c935, 0x856365553ded1af3, 0x2433f51f5f066ed0,
+        0xd3df1ed5d5fd6561, 0xf681b202aec4617a, 0x7d2fe363630c75d8, 0xcc939dce249b3ef9,
+        0xa9e13641146433fb, 0xd8b9c583ce2d3695, 0xafdc5620273d3cf1, 0xadf85458a2bb4a9a,
+    ];
+
+    pub const LOG2_10: [u64; 16] = [
+        0xd69c4bc2089e1f61, 0xaa9363cf9c1988c4, 0x304f88b43eb2b86f, 0x8658c6dfea3eed71,
+        0x8e8f75775109530b, 0x385e5f901cc2d1ac, 0xfee6c26b8f8c9bd0, 0xf64f96c17d9b5d4d,
+        0xcff162cd4f603381, 0x1435ee480565ca54, 0x3e24beab63ad0bd6, 0x06650fde9dd51f3a,
+        0x91b6ac8082e7859d, 0xd96c55fe37b3ad4e, 0x492bf6ff4dafdb4c, 0xd49a784bcd1b8afe,
+    ];
+
+    pub const LOG2_E: [u64; 16] = [
+        0x612f08fbae30a173, 0xce7e20358cd5db8f, 0x278ccf084679c940, 0x199a94836f5b4967,
+        0x8d1cf457ab63253c, 0x0b5ebbbf3a828546, 0xb21b43d579d5a206, 0x2fe294932617d9d5,
+        0xc4bfaf0353df39b3, 0xea90b9e60c4a909f, 0x24d92f75c16be0b3, 0xde1c43f755176cd6,
+        0x8b25166cd1a13247, 0xeb577aa8dd695a58, 0xbe87fed0691d3e88, 0xb8aa3b295c17f0bb,
+    ];
+
+    pub const LOG10_2: [u64; 16] = [
+        0xdeea3177c8f88caa, 0x6a367b813cf4c8fb, 0x0b0833c5941ab821, 0x048d7ea59eb153d8,
+        0xeb91129b1592d9dc, 0xd26c272fde7102f4, 0x9cf1d190c40602e5, 0x5f837dee02555cbc,
+        0x58b527f52552d2cc, 0x91473495b18b921e, 0x9e5cbc733471bd12, 0x286a2d81919fabd0,
+        0x8a5e6f26b7cc63cb, 0x26ad30c543d1f349, 0x8f8959ac0b7c9178, 0x9a209a84fbcff798,
+    ];
+
+    pub const LOG10_E: [u64; 16] = [
+        0xb3fe40c2ecdb1f6c, 0xd794d64f27c968eb, 0x648ecc89cc51f934, 0x5430212ae68c04d4,
+        0x50b36ded2f3739d5, 0x658b61ea42c84d6a, 0x400720058c1dc4da, 0x4356bd1948d06ff9,
+        0x6fa2b8d2c8cda7b3, 0x18ce3bd9fd38dcbc, 0x92235592c6464a15, 0x75424efa1402f3f2,
+        0x3a3f2d44f78ea53c, 0x3ee3460245c9a202, 0x355baaafad33dc32, 0xde5bd8a937287195,
+    ];
+
+    pub const LN_2: [u64; 16] = [
+        0xda2d97c50f3fd5c6, 0x655fa1872f20e3a2, 0xf5dfa6bd38303248, 0x72ce87b19d6548ca,
+        0x256fa0ec7657f74b, 0xb9ea9bc3b136603b, 0x1acbda11317c387e, 0x3e96ca16224ae8c5,
+        0x27573b291169b825, 0xed2eae35c1382144, 0x559552fb4afa1b10, 0xe7b876206debac98,
+        0x8a0d175b8baafa2b, 0x40f343267298b62d, 0xc9e3b39803f2f6af, 0xb17217f7d1cf79ab,
+    ];
+
+    pub const LN_10: [u64; 16] = [
+        0x469ea58e9305e981, 0x5b08b057d5ede20f, 0x8e93368d44789c4f, 0xca67b35b23605085,
+        0x5161bb49d219c7bb, 0xef66ceb04ab3c6fa, 0x765aa6c3b0d831fb, 0x782cf8a28a8c911e,
+        0xfb8f788402e516d6, 0x2c622418410be2da, 0xcc70cbc02c5f0d68, 0x962f02d7b1a8105c,
+        0x83c61e8201f02d72, 0xe28fecf9da5df90e, 0xea56d62b82d30a28, 0x935d8dddaaa8ac16,
+    ];
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::F64;
-    type F32 = crate::Float::<4, 23>;
+    use crate::{F32, F64};
 
     macro_rules! test_constant {
         ($($constant: ident), *) => {
@@ -144,4 +323,38 @@ mod tests {
         assert_eq!(F64::EXP_BIAS, 1023i32.into());
         assert_eq!(F32::MAX_UNBIASED_EXP, 254u32.into());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_math_constants() {
+        macro_rules! test_math_constant {
+            ($($constant: ident), *) => {
+                $(
+                    assert_eq!(F64::$constant.to_bits(), core::f64::consts::$constant.to_bits().into(), "constant `{}` not equal to the primitive equivalent", stringify!($constant));
+                )*
+            }
+        }
+
+        test_math_constant!(PI, TAU, FRAC_PI_2, FRAC_PI_3, FRAC_PI_4, FRAC_PI_6, FRAC_PI_8, FRAC_1_PI, FRAC_2_PI, FRAC_2_SQRT_PI, SQRT_2, FRAC_1_SQRT_2, E, LOG2_10, LOG2_E, LOG10_2, LOG10_E, LN_2, LN_10);
+
+        // binary128 value of pi, 0x4000921fb54442d18469898cc51701b8
+        type F128 = crate::Float<2, 112>;
+        assert_eq!(F128::PI.to_bits(), 0x4000921fb54442d18469898cc51701b8u128.into());
+    }
+
+    #[test]
+    fn test_digit_families() {
+        type Binary32 = crate::FloatD32<1, 23>;
+        type Binary16 = crate::FloatD16<1, 10>;
+        type Binary16D8 = crate::FloatD8<2, 10>;
+        type BFloat16 = crate::FloatD16<1, 7>;
+
+        assert_eq!(Binary32::MAX.to_bits(), f32::MAX.to_bits().into());
+        assert_eq!(Binary32::EPSILON.to_bits(), f32::EPSILON.to_bits().into());
+        assert_eq!(Binary32::PI.to_bits(), core::f32::consts::PI.to_bits().into());
+        assert_eq!(Binary32::LN_2.to_bits(), core::f32::consts::LN_2.to_bits().into());
+
+        assert_eq!(Binary16::PI.to_bits(), 0x4248u16.into());
+        assert_eq!(Binary16D8::PI.to_bits(), 0x4248u16.into());
+        assert_eq!(BFloat16::PI.to_bits(), 0x4049u16.into());
+    }
+}
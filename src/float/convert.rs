@@ -1,56 +1,75 @@
-use super::Float;
-use crate::BUint;
-use crate::digit;
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use crate::bigints::*;
+#[cfg(feature = "nightly")]
+use crate::{digit, doc};
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    #[inline(always)]
-    pub const fn to_bits(self) -> BUint<W> {
-        self.bits
-    }
+macro_rules! convert {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            #[inline(always)]
+            pub const fn to_bits(self) -> $BUint<W> {
+                self.bits
+            }
 
-    #[inline(always)]
-    pub const fn from_bits(v: BUint<W>) -> Self {
-        Self {
-            bits: v,
+            #[inline(always)]
+            pub const fn from_bits(v: $BUint<W>) -> Self {
+                Self {
+                    bits: v,
+                }
+            }
         }
-    }
-}
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    #[inline]
-    pub const fn to_be_bytes(self) -> [u8; W * digit::BYTES as usize] {
-        self.to_bits().to_be_bytes()
-    }
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            #[cfg(feature = "nightly")]
+            #[doc = doc::requires_feature!("nightly")]
+            #[inline]
+            pub const fn to_be_bytes(self) -> [u8; W * digit::$Digit::BYTES as usize] {
+                self.to_bits().to_be_bytes()
+            }
 
-    #[inline]
-    pub const fn to_le_bytes(self) -> [u8; W * digit::BYTES as usize] {
-        self.to_bits().to_le_bytes()
-    }
+            #[cfg(feature = "nightly")]
+            #[doc = doc::requires_feature!("nightly")]
+            #[inline]
+            pub const fn to_le_bytes(self) -> [u8; W * digit::$Digit::BYTES as usize] {
+                self.to_bits().to_le_bytes()
+            }
 
-    #[inline]
-    pub const fn to_ne_bytes(self) -> [u8; W * digit::BYTES as usize] {
-        self.to_bits().to_ne_bytes()
-    }
+            #[cfg(feature = "nightly")]
+            #[doc = doc::requires_feature!("nightly")]
+            #[inline]
+            pub const fn to_ne_bytes(self) -> [u8; W * digit::$Digit::BYTES as usize] {
+                self.to_bits().to_ne_bytes()
+            }
 
-    #[inline]
-    pub const fn from_be_bytes(bytes: [u8; W * digit::BYTES as usize]) -> Self {
-        Self::from_bits(BUint::from_be_bytes(bytes))
-    }
+            #[cfg(feature = "nightly")]
+            #[doc = doc::requires_feature!("nightly")]
+            #[inline]
+            pub const fn from_be_bytes(bytes: [u8; W * digit::$Digit::BYTES as usize]) -> Self {
+                Self::from_bits($BUint::from_be_bytes(bytes))
+            }
 
-    #[inline]
-    pub const fn from_le_bytes(bytes: [u8; W * digit::BYTES as usize]) -> Self {
-        Self::from_bits(BUint::from_le_bytes(bytes))
-    }
+            #[cfg(feature = "nightly")]
+            #[doc = doc::requires_feature!("nightly")]
+            #[inline]
+            pub const fn from_le_bytes(bytes: [u8; W * digit::$Digit::BYTES as usize]) -> Self {
+                Self::from_bits($BUint::from_le_bytes(bytes))
+            }
 
-    #[inline]
-    pub const fn from_ne_bytes(bytes: [u8; W * digit::BYTES as usize]) -> Self {
-        Self::from_bits(BUint::from_ne_bytes(bytes))
-    }
+            #[cfg(feature = "nightly")]
+            #[doc = doc::requires_feature!("nightly")]
+            #[inline]
+            pub const fn from_ne_bytes(bytes: [u8; W * digit::$Digit::BYTES as usize]) -> Self {
+                Self::from_bits($BUint::from_ne_bytes(bytes))
+            }
+        }
+    };
 }
 
+super::float_impl!(convert);
+
 #[cfg(test)]
 mod tests {
-    use crate::test::U8ArrayWrapper;
+    use crate::F64;
 	use crate::test::test_bignum;
 
     test_bignum! {
@@ -59,22 +78,28 @@ mod tests {
     test_bignum! {
         function: <f64>::from_bits(a: u64)
     }
+    #[cfg(feature = "nightly")]
     test_bignum! {
         function: <f64>::to_be_bytes(a: f64)
     }
+    #[cfg(feature = "nightly")]
     test_bignum! {
         function: <f64>::to_le_bytes(a: f64)
     }
+    #[cfg(feature = "nightly")]
     test_bignum! {
         function: <f64>::to_ne_bytes(a: f64)
     }
+    #[cfg(feature = "nightly")]
     test_bignum! {
-        function: <f64>::from_be_bytes(a: U8ArrayWrapper<8>)
+        function: <f64>::from_be_bytes(a: crate::test::U8ArrayWrapper<8>)
     }
+    #[cfg(feature = "nightly")]
     test_bignum! {
-        function: <f64>::from_le_bytes(a: U8ArrayWrapper<8>)
+        function: <f64>::from_le_bytes(a: crate::test::U8ArrayWrapper<8>)
     }
+    #[cfg(feature = "nightly")]
     test_bignum! {
-        function: <f64>::from_ne_bytes(a: U8ArrayWrapper<8>)
+        function: <f64>::from_ne_bytes(a: crate::test::U8ArrayWrapper<8>)
     }
 }
\ No newline at end of file
@@ -1,5 +1,8 @@
-use super::Float;
-use crate::{BUint, Bint};
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use crate::bigints::*;
+use crate::ExpType;
+use crate::cast::As;
+use super::RoundingMode;
 
 /*/// Returns tuple of division and whether u is less than v
 pub const fn div_float<const N: usize>(u: BUint<N>, v: BUint<N>) -> (BUint<N>, bool) {
@@ -195,380 +198,416 @@ All functions:
 mul_add, div_euclid, rem_euclid, powi, powf, exp, exp2, ln, log, log2, log10, cbrt, hypot, sin, cos, tan, asin, acos, atan, atan2, sin_cos, exp_m1, ln_1p, sinh, cosh, tanh, asinh, acosh, atanh, to_degrees, to_radians
 */
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    pub fn scalbn(mut self, mut n: Bint<W>) -> Self where [(); W * 2]:, {
-        let x1p127 = Self::from_bits(BUint::MAX >> 1u8 << (Self::MB + 2)); // 0x1p127f === 2 ^ 127
-        let x1p24 = Self::from_exp_mant(false, BUint::from(MB), BUint::ZERO); // 0x1p24f === 2 ^ 24
+macro_rules! math {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            #[inline]
+            fn saturate_exponent(e: $BInt<W>) -> i32 {
+                let i: i32 = e.as_();
+                if i.as_::<$BInt<W>>() == e {
+                    i
+                } else if e.is_negative() {
+                    i32::MIN
+                } else {
+                    i32::MAX
+                }
+            }
 
-        if n > Self::EXP_BIAS {
-            self = self * x1p127;
-            n -= Self::EXP_BIAS;
-            if n > Self::EXP_BIAS {
-                self = self * x1p127;
-                n -= Self::EXP_BIAS;
-                if n > Self::EXP_BIAS {
-                    n = Self::EXP_BIAS;
+            #[inline]
+            fn exponent_from_i32(n: i32) -> $BInt<W> {
+                let e: $BInt<W> = n.as_();
+                if e.as_::<i32>() == n {
+                    e
+                } else if n.is_negative() {
+                    $BInt::MIN
+                } else {
+                    $BInt::MAX
                 }
             }
-        } else if n < Self::MIN_EXP {
-            self = self * (Self::MIN_POSITIVE * x1p24);
-            n += Self::EXP_BIAS - Bint::from(MB) - Bint::TWO;
-            if n < Self::MIN_EXP {
-                self = self * (Self::MIN_POSITIVE * x1p24);
-                n += Self::EXP_BIAS - Bint::from(MB) - Bint::TWO;
-                if n < Self::MIN_EXP {
-                    n = Self::MIN_EXP;
+
+            /// Returns the least number greater than `self`.
+            ///
+            /// NaN and positive infinity are returned unchanged, and both zeros return the smallest positive subnormal value.
+            #[inline]
+            pub fn next_up(self) -> Self {
+                if self.is_nan() || self == Self::INFINITY {
+                    return self;
+                }
+                if self.is_zero() {
+                    return Self::MIN_POSITIVE_SUBNORMAL;
+                }
+                let bits = self.to_bits();
+                if self.is_sign_negative() {
+                    Self::from_bits(bits - $BUint::ONE)
+                } else {
+                    Self::from_bits(bits + $BUint::ONE)
                 }
             }
-        }
-        self * Self::from_bits(((Self::EXP_BIAS + n).to_bits()) << Self::MB)
-    }
 
-    #[inline]
-    pub const fn abs(self) -> Self {
-        if self.is_sign_negative() {
-            -self
-        } else {
-            self
-        }
-    }
+            /// Returns the greatest number less than `self`.
+            ///
+            /// NaN and negative infinity are returned unchanged, and both zeros return the largest negative subnormal value.
+            #[inline]
+            pub fn next_down(self) -> Self {
+                -(-self).next_up()
+            }
 
-    pub fn sqrt(self) -> Self {
-        handle_nan!(self; self);
-        if self.is_zero() {
-            return self;
-        }
-        let bits = self.to_bits();
-        if bits == Self::INFINITY.to_bits() {
-            return Self::INFINITY;
-        }
-        if self.is_sign_negative() {
-            let u = BUint::MAX << (Self::MB - 1);
-            return Self::from_bits(u);
-        }
+            /// Returns the next representable value after `self` in the direction of `toward`.
+            ///
+            /// If either argument is NaN, NaN is returned. If `self == toward`, `toward` is returned.
+            #[inline]
+            pub fn nextafter(self, toward: Self) -> Self {
+                handle_nan!(Self::NAN; self, toward);
+                if self == toward {
+                    toward
+                } else if self < toward {
+                    self.next_up()
+                } else {
+                    self.next_down()
+                }
+            }
 
-        let tiny = Self::from_bits(BUint::from(0b11011u8) << Self::MB); // TODO: may not work for exponents stored with very few bits
-        
-        let mut ix = Bint::from_bits(bits);
-        let mut i: Bint<W>;
-        let mut m = ix >> Self::MB;
-        if m.is_zero() {
-            /* subnormal x */
-            i = Bint::ZERO;
-            while (ix & (Bint::ONE << Self::MB)).is_zero() {
-                ix <<= 1;
-                i = i + Bint::ONE;
-            }
-            m -= i - Bint::ONE;
-        }
-        m -= Self::EXP_BIAS; /* unbias exponent */
-        ix = (ix & Bint::from_bits(BUint::MAX >> (Self::BITS - Self::MB))) | (Bint::ONE << Self::MB);
-        if m & Bint::ONE == Bint::ONE {
-            /* odd m, double x to make it even */
-            ix += ix;
-        }
-        m >>= 1; /* m = [m/2] */
-
-        /* generate sqrt(x) bit by bit */
-        ix += ix;
-        let mut q = Bint::ZERO;
-        let mut s = Bint::ZERO;
-        let mut r = BUint::ONE << (Self::MB + 1); /* r = moving bit from right to left */
-
-        let mut t: Bint<W>;
-        while !r.is_zero() {
-            t = s + Bint::from_bits(r);
-            if t <= ix {
-                s = t + Bint::from_bits(r);
-                ix -= t;
-                q += Bint::from_bits(r);
-            }
-            ix += ix;
-            r = r >> 1u8;
-        }
+            /// Returns the unit in the last place of `self`: the distance between `|self|` and the next representable value of greater magnitude, as if the exponent range were unbounded.
+            ///
+            /// Returns NaN for NaN and positive infinity for infinite values.
+            #[inline]
+            pub fn ulp(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_infinite() {
+                    return Self::INFINITY;
+                }
+                if self.is_zero() || self.is_subnormal() {
+                    return Self::MIN_POSITIVE_SUBNORMAL;
+                }
+                let (_, exp, _) = self.to_parts();
+                Self::from_parts_rounded(false, exp, $BUint::ONE)
+            }
 
-        /* use floating add to find out rounding direction */
-        let mut z: Self;
-        if !ix.is_zero() {
-            z = Self::ONE - tiny; /* raise inexact flag */
-            if z >= Self::ONE {
-                z = Self::ONE + tiny;
-                if z > Self::ONE {
-                    q += Bint::TWO;
-                } else {
-                    q += q & Bint::ONE;
+            /// Decomposes `self` into a normalised fraction and an integral power of two, such that `self = fraction * 2^exponent` and the magnitude of `fraction` is in the range `[0.5, 1)`.
+            ///
+            /// Zero, infinite and NaN values are returned unchanged with an exponent of `0`. The exponent saturates at the bounds of `i32`.
+            #[inline]
+            pub fn frexp(self) -> (Self, i32) {
+                if self.is_zero() || !self.is_finite() {
+                    return (self, 0);
                 }
+                let (negative, exp, sig) = self.to_parts();
+                let bits = Self::exp_from(sig.bits());
+                (Self::from_parts_rounded(negative, -bits, sig), Self::saturate_exponent(exp + bits))
             }
-        }
 
-        ix = (q >> 1u8) + Bint::from_bits((BUint::MAX << (Self::MB + 1 + 2)) >> 2u8);
-        ix += m << Self::MB;
-        Self::from_bits(ix.to_bits())
-    }
+            /// Returns `self * 2^n`, correctly rounded. This is equivalent to [`scalbn`](Self::scalbn).
+            #[inline]
+            pub fn ldexp(self, n: i32) -> Self {
+                self.scalbn(n)
+            }
 
-    #[inline]
-    pub fn round(self) -> Self where [(); W * 2]:, {
-        let a = Self::HALF - Self::QUARTER * Self::EPSILON; // TODO: can precalculate quarter * eps so no need for where bound
-        (self + a.copysign(self)).trunc()
-    }
+            /// Returns `self * 2^n`, correctly rounded.
+            ///
+            /// Unlike repeated multiplication, this rounds only once, so results in the subnormal range are exact whenever they can be represented.
+            #[inline]
+            pub fn scalbn(self, n: i32) -> Self {
+                if self.is_zero() || !self.is_finite() {
+                    return self;
+                }
+                let (negative, exp, sig) = self.to_parts();
+                Self::from_parts_rounded(negative, exp.saturating_add(Self::exponent_from_i32(n)), sig)
+            }
 
-    #[inline]
-    pub fn ceil(self) -> Self {
-        let mut u = self.to_bits();
-        let e = self.exponent() - Self::EXP_BIAS;
+            /// Returns the unbiased exponent of `self`, as if subnormal values were normalised, so that `2^self.ilogb()` is at most `|self|`.
+            ///
+            /// Returns `i32::MIN` for zero and NaN, and `i32::MAX` for infinite values. The exponent saturates at the bounds of `i32`.
+            #[inline]
+            pub fn ilogb(self) -> i32 {
+                if self.is_zero() || self.is_nan() {
+                    return i32::MIN;
+                }
+                if self.is_infinite() {
+                    return i32::MAX;
+                }
+                let (_, exp, sig) = self.to_parts();
+                Self::saturate_exponent(exp + Self::exp_from(sig.bits() - 1))
+            }
 
-        if e >= Bint::from(MB) {
-            return self;
-        }
-        if !e.is_negative() {
-            let m = (BUint::MAX >> (Self::BITS - Self::MB)) >> e;
-            if (u & m).is_zero() {
-                return self;
-            }
-            if self.is_sign_positive() {
-                u += m;
-            }
-            u &= !m;
-        } else if self.is_sign_negative() {
-			return Self::NEG_ZERO;
-		} else if !(u << 1u8).is_zero() {
-			return Self::ONE;
-        }
-        Self::from_bits(u)
-    }
+            /// Returns the unbiased exponent of `self` as a float, as if subnormal values were normalised.
+            ///
+            /// Returns negative infinity for zero, positive infinity for infinite values and NaN for NaN.
+            #[inline]
+            pub fn logb(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_zero() {
+                    return Self::NEG_INFINITY;
+                }
+                if self.is_infinite() {
+                    return Self::INFINITY;
+                }
+                let (_, exp, sig) = self.to_parts();
+                let e = exp + Self::exp_from(sig.bits() - 1);
+                Self::from_parts_rounded(e.is_negative(), $BInt::ZERO, e.unsigned_abs())
+            }
 
-    #[inline]
-    pub fn floor(self) -> Self {
-        let mut bits = self.to_bits();
-        let e = self.exponent() - Self::EXP_BIAS;
+            #[inline]
+            pub const fn abs(self) -> Self {
+                let mut words = *self.words();
+                words[W - 1] &= (!0) >> 1;
+                Self::from_words(words)
+            }
 
-        if e >= Bint::from(MB) {
-            return self;
-        }
-        if !e.is_negative() {
-            let m = (BUint::MAX >> (Self::BITS - Self::MB)) >> e;
-            if (bits & m).is_zero() {
-                return self;
-            }
-            if self.is_sign_negative() {
-                bits += m;
-            }
-            bits &= !m;
-        } else if self.is_sign_positive() {
-			return Self::ZERO;
-		} else if !(bits << 1u8).is_zero() {
-			return Self::NEG_ONE;
-		}
-        Self::from_bits(bits)
-    }
+            pub fn sqrt(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_zero() || self == Self::INFINITY {
+                    return self;
+                }
+                if self.is_sign_negative() {
+                    return Self::NAN;
+                }
+                // Normalise the significand to `MB + 1` bits, and make the exponent of `sig * 2^(MB + 2)` even, so that its square root can be taken by halving it.
+                let (_, exp, sig) = self.to_parts();
+                let shift = Self::MB + 1 - sig.bits();
+                let mut sig = sig << shift;
+                let mut exp = exp - Self::exp_from(shift) - Self::exp_from(Self::MB + 2);
+                if exp.to_bits().bit(0) {
+                    sig <<= 1u8;
+                    exp -= $BInt::ONE;
+                }
 
-    #[inline]
-    pub fn trunc(self) -> Self {
-        //return self.fract_trunc().1;
-        let mut i = self.to_bits();
-        let exp_bits = Bint::from(Self::BITS - Self::MB);
-        let mut e = self.exponent() - Self::EXP_BIAS + exp_bits;
+                // Generate the `MB + 2` bits of the square root of `sig * 2^(MB + 2)` bit by bit. `rem` stays below `2^(MB + 3)`, so it cannot overflow.
+                let mut rem = sig << 1u8;
+                let mut q = $BUint::<W>::ZERO;
+                let mut s = $BUint::ZERO;
+                let mut r = $BUint::power_of_two(Self::MB + 1);
+                while !r.is_zero() {
+                    let t = s + r;
+                    if t <= rem {
+                        s = t + r;
+                        rem -= t;
+                        q += r;
+                    }
+                    rem <<= 1u8;
+                    r >>= 1u8;
+                }
+                Self::round_parts(false, exp >> 1u8, q, !rem.is_zero(), RoundingMode::NearestTiesEven)
+            }
 
-        if e >= Bint::from(Self::BITS) {
-            return self;
-        }
-        if e < exp_bits {
-            e = Bint::ONE;
-        }
-        let m = Bint::NEG_ONE.to_bits() >> e;
-        if (i & m).is_zero() {
-            return self;
-        }
-        i &= !m;
-        Self::from_bits(i)
-    }
+            #[inline]
+            pub fn round(self) -> Self {
+                let trunc = self.trunc();
+                if (self - trunc).abs() >= Self::HALF {
+                    trunc + Self::ONE.copysign(self)
+                } else {
+                    trunc
+                }
+            }
 
-    #[inline]
-    pub fn fract(self) -> Self {
-        self.fract_trunc().0
-    }
+            #[inline]
+            pub fn ceil(self) -> Self {
+                let trunc = self.trunc();
+                if self.is_sign_positive() && trunc.to_bits() != self.to_bits() {
+                    trunc + Self::ONE
+                } else {
+                    trunc
+                }
+            }
 
-    #[inline]
-    pub fn fract_trunc(self) -> (Self, Self) {
-        handle_nan!((self, self); self);
+            #[inline]
+            pub fn floor(self) -> Self {
+                let trunc = self.trunc();
+                if self.is_sign_negative() && trunc.to_bits() != self.to_bits() {
+                    trunc - Self::ONE
+                } else {
+                    trunc
+                }
+            }
 
-        let mut u = self.to_bits();
-        let e = self.exponent() - Self::EXP_BIAS;
+            #[inline]
+            pub fn trunc(self) -> Self {
+                if self.is_zero() || !self.is_finite() {
+                    return self;
+                }
+                let (negative, exp, sig) = self.to_parts();
+                if !exp.is_negative() {
+                    return self;
+                }
+                // Clear the bits of the significand below the units bit.
+                let frac_bits = exp.unsigned_abs();
+                if frac_bits >= $BUint::<W>::BITS.as_::<$BUint<W>>() {
+                    return if negative {
+                        Self::NEG_ZERO
+                    } else {
+                        Self::ZERO
+                    };
+                }
+                let frac_bits: ExpType = frac_bits.as_();
+                let sig = (sig >> frac_bits) << frac_bits;
+                Self::from_parts_rounded(negative, exp, sig)
+            }
 
-        if self.is_infinite() {
-            return (Self::NEG_NAN, self);
-        }
+            #[inline]
+            pub fn fract(self) -> Self {
+                self.fract_trunc().0
+            }
 
-        if e >= Bint::from(MB) {
-            return (Self::ZERO, self);
-        }
-        if e.is_negative() {
-            let trunc = if self.is_sign_negative() {
-                Self::NEG_ZERO
-            } else {
-                Self::ZERO
-            };
-            if self.is_zero() {
-                return (Self::ZERO, self);
+            #[inline]
+            pub fn fract_trunc(self) -> (Self, Self) {
+                let trunc = self.trunc();
+                (self - trunc, trunc)
             }
-            return (self, trunc);
-        }
 
-        let mask = BUint::<W>::MAX >> (e + Bint::from(Self::BITS - Self::MB));
-        if (u & mask).is_zero() {
-            return (Self::ZERO, self);
-        }
-        u &= !mask;
-        let trunc = Self::from_bits(u);
-        (self - trunc, trunc)
-    }
+            #[inline]
+            pub fn recip(self) -> Self {
+                Self::ONE / self
+            }
 
-    #[inline]
-    pub fn recip(self) -> Self where [(); W * 2]:, {
-        Self::ONE / self
-    }
+            #[inline]
+            pub fn div_euclid(self, rhs: Self) -> Self {
+                let div = (self / rhs).trunc();
+                if self % rhs < Self::ZERO {
+                    return if rhs > Self::ZERO {
+                        div - Self::ONE
+                    } else {
+                        div + Self::ONE
+                    }
+                }
+                div
+            }
 
-    #[inline]
-    pub fn div_euclid(self, rhs: Self) -> Self where [(); W * 2]:, {
-        let div = (self / rhs).trunc();
-        if self % rhs < Self::ZERO {
-            return if rhs > Self::ZERO {
-                div - Self::ONE
-            } else {
-                div + Self::ONE
+            #[inline]
+            pub fn rem_euclid(self, rhs: Self) -> Self {
+                let rem = self % rhs;
+                if rem < Self::NEG_ZERO {
+                    rem + rhs.abs()
+                } else {
+                    rem
+                }
             }
-        }
-        div
-    }
 
-    #[inline]
-    pub fn rem_euclid(self, rhs: Self) -> Self {
-        let rem = self % rhs;
-        if rem < Self::NEG_ZERO {
-            rem + rhs.abs()
-        } else {
-            rem
-        }
-    }
+            /*pub fn remquof(mut self, mut y: Self) -> /*(Self, $BInt<W>)*/Self where [(); {(W * 2).saturating_sub(W)
+            }]: Sized, [(); W.saturating_sub(W * 2)]: Sized {
+                handle_nan!(self; self);
+                handle_nan!(y; y);
+                if self.is_infinite() || y.is_infinite() {
+                    return Self::NAN;
+                }
 
-    /*pub fn remquof(mut self, mut y: Self) -> /*(Self, Bint<W>)*/Self where [(); {(W * 2).saturating_sub(W)
-    }]: Sized, [(); W.saturating_sub(W * 2)]: Sized {
-        handle_nan!(self; self);
-        handle_nan!(y; y);
-        if self.is_infinite() || y.is_infinite() {
-            return Self::NAN;
-        }
+                if y.is_zero() {
+                    return Self::QNAN;
+                }
+                if self.is_zero() {
+                    return self;
+                }
+                let ux = self.to_bits();
+                let mut uy = y.to_bits();
+                let mut ex = self.exponent();
+                let mut ey = y.exponent();
+                let sx = self.is_sign_negative();
+                let sy = y.is_sign_negative();
+                let mut uxi = ux;
+
+                /* normalize x and y */
+                let mut i;
+                if ex.is_zero() {
+                    i = uxi << (Self::BITS - Self::MB);
+                    while !$BInt::from_bits(i).is_negative() {
+                        ex -= $BInt::ONE;
+                        i <<= 1u8;
+                    }
+                    uxi <<= -ex + $BInt::ONE;
+                } else {
+                    uxi &= $BUint::MAX >> (Self::BITS - Self::MB);
+                    uxi |= $BUint::ONE << Self::MB;
+                }
+                if ey.is_zero() {
+                    i = uy << (Self::BITS - Self::MB);
+                    while !$BInt::from_bits(i).is_negative() {
+                        ey -= $BInt::ONE;
+                        i <<= 1u8;
+                    }
+                    uy <<= -ey + $BInt::ONE;
+                } else {
+                    uy &= $BUint::MAX >> (Self::BITS - Self::MB);
+                    uy |= $BUint::ONE << Self::MB;
+                }
 
-        if y.is_zero() {
-            return Self::QNAN;
-        }
-        if self.is_zero() {
-            return self;
-        }
-        let ux = self.to_bits();
-        let mut uy = y.to_bits();
-        let mut ex = self.exponent();
-        let mut ey = y.exponent();
-        let sx = self.is_sign_negative();
-        let sy = y.is_sign_negative();
-        let mut uxi = ux;
-    
-        /* normalize x and y */
-        let mut i;
-        if ex.is_zero() {
-            i = uxi << (Self::BITS - Self::MB);
-            while !Bint::from_bits(i).is_negative() {
-                ex -= Bint::ONE;
-                i <<= 1u8;
-            }
-            uxi <<= -ex + Bint::ONE;
-        } else {
-            uxi &= BUint::MAX >> (Self::BITS - Self::MB);
-            uxi |= BUint::ONE << Self::MB;
-        }
-        if ey.is_zero() {
-            i = uy << (Self::BITS - Self::MB);
-            while !Bint::from_bits(i).is_negative() {
-                ey -= Bint::ONE;
-                i <<= 1u8;
-            }
-            uy <<= -ey + Bint::ONE;
-        } else {
-            uy &= BUint::MAX >> (Self::BITS - Self::MB);
-            uy |= BUint::ONE << Self::MB;
-        }
-    
-        let mut q = BUint::<W>::ZERO;
-        if ex + Bint::ONE != ey {
-            if ex < ey {
-                return /*(self, 0);*/self;
-            }
-            /* x mod y */
-            while ex > ey {
-                i = uxi.wrapping_sub(uy);
-                if !Bint::from_bits(i).is_negative() {
-                    uxi = i;
-                    q += BUint::ONE;
-                }
-                uxi <<= 1u8;
-                q <<= 1u8;
-                ex -= Bint::ONE;
-            }
-            i = uxi.wrapping_sub(uy);
-            if !Bint::from_bits(i).is_negative() {
-                uxi = i;
-                q += BUint::ONE;
-            }
-            if uxi.is_zero() {
-                //ex = Bint::TWO - Bint::from(Self::BITS);
-                ex = Bint::from(-60i8);
-            } else {
-                while (uxi >> Self::MB).is_zero() {
-                    uxi <<= 1u8;
-                    ex -= Bint::ONE;
+                let mut q = $BUint::<W>::ZERO;
+                if ex + $BInt::ONE != ey {
+                    if ex < ey {
+                        return /*(self, 0);*/self;
+                    }
+                    /* x mod y */
+                    while ex > ey {
+                        i = uxi.wrapping_sub(uy);
+                        if !$BInt::from_bits(i).is_negative() {
+                            uxi = i;
+                            q += $BUint::ONE;
+                        }
+                        uxi <<= 1u8;
+                        q <<= 1u8;
+                        ex -= $BInt::ONE;
+                    }
+                    i = uxi.wrapping_sub(uy);
+                    if !$BInt::from_bits(i).is_negative() {
+                        uxi = i;
+                        q += $BUint::ONE;
+                    }
+                    if uxi.is_zero() {
+                        //ex = $BInt::TWO - $BInt::from(Self::BITS);
+                        ex = $BInt::from(-60i8);
+                    } else {
+                        while (uxi >> Self::MB).is_zero() {
+                            uxi <<= 1u8;
+                            ex -= $BInt::ONE;
+                        }
+                    }
                 }
-            }
-        }
-    
-        /* scale result and decide between |x| and |x|-|y| */
-        if ex.is_positive() {
-            uxi -= BUint::ONE << Self::MB;
-            uxi |= ex.to_bits() << Self::MB;
-        } else {
-            uxi >>= -ex + Bint::ONE;
-        }
-        self = Self::from_bits(uxi);
-        if sy {
-            y = -y;
-        }
-        if ex == ey || (ex + Bint::ONE == ey && (Self::TWO * self > y || (Self::TWO * self == y && !(q % BUint::TWO).is_zero()))) {
-            self = self - y;
-            q += BUint::ONE;
-        }
-        q &= BUint::MAX >> 1u8;
-        let quo = if sx ^ sy { -Bint::from_bits(q) } else { Bint::from_bits(q) };
-        if sx {
-            //(-self, quo)
-            -self
-        } else {
-            //(self, quo)
-            self
+
+                /* scale result and decide between |x| and |x|-|y| */
+                if ex.is_positive() {
+                    uxi -= $BUint::ONE << Self::MB;
+                    uxi |= ex.to_bits() << Self::MB;
+                } else {
+                    uxi >>= -ex + $BInt::ONE;
+                }
+                self = Self::from_bits(uxi);
+                if sy {
+                    y = -y;
+                }
+                if ex == ey || (ex + $BInt::ONE == ey && (Self::TWO * self > y || (Self::TWO * self == y && !(q % $BUint::TWO).is_zero()))) {
+                    self = self - y;
+                    q += $BUint::ONE;
+                }
+                q &= $BUint::MAX >> 1u8;
+                let quo = if sx ^ sy { -$BInt::from_bits(q) } else { $BInt::from_bits(q) };
+                if sx {
+                    //(-self, quo)
+                    -self
+                } else {
+                    //(self, quo)
+                    self
+                }
+            }*/
         }
-    }*/
+    };
 }
 
+super::float_impl!(math);
+
 #[cfg(test)]
 mod tests {
-	use crate::test::test_bignum;
+    use crate::{F32, F64};
+	use crate::test::{test_bignum, test_float};
 
     test_bignum! {
         function: <f64>::abs(f: f64)
     }
 
-    test_bignum! {
+    test_float! {
         function: <f64>::sqrt(f: f64)
     }
 
+    test_float! {
+        function: <f32>::sqrt(f: f32)
+    }
+
     test_bignum! {
         function: <f64>::ceil(f: f64)
     }
@@ -585,27 +624,76 @@ mod tests {
         function: <f64>::trunc(f: f64)
     }
 
-    test_bignum! {
+    test_float! {
         function: <f64>::fract(f: f64)
     }
 
-    test_bignum! {
+    test_float! {
         function: <f64>::div_euclid(f1: f64, f2: f64)
     }
 
-    test_bignum! {
+    test_float! {
         function: <f64>::rem_euclid(f1: f64, f2: f64)
     }
 
+    test_bignum! {
+        function: <f64>::next_up(f: f64)
+    }
+
+    test_bignum! {
+        function: <f64>::next_down(f: f64)
+    }
+
+    #[test]
+    fn frexp_ldexp() {
+        let (m, e) = F64::from(48.0f64).frexp();
+        assert_eq!(m.to_bits(), 0.75f64.to_bits().into());
+        assert_eq!(e, 6);
+
+        let sub = F64::MIN_POSITIVE_SUBNORMAL;
+        let (m, e) = sub.frexp();
+        assert_eq!(m.to_bits(), 0.5f64.to_bits().into());
+        assert_eq!(e, -1073);
+        assert_eq!(m.ldexp(e).to_bits(), sub.to_bits());
+
+        assert_eq!(F64::ONE.scalbn(-1074).to_bits(), sub.to_bits());
+        assert_eq!(F64::ONE.scalbn(-1076).to_bits(), 0u64.into());
+        assert_eq!(F64::ONE.scalbn(1024).to_bits(), f64::INFINITY.to_bits().into());
+        assert_eq!(F64::from(1.5f64).scalbn(-1074).to_bits(), 2u64.into());
+    }
+
+    #[test]
+    fn ulp_ilogb_logb() {
+        assert_eq!(F64::ONE.ulp().to_bits(), f64::EPSILON.to_bits().into());
+        assert_eq!(F64::ZERO.ulp().to_bits(), 1u64.into());
+        assert_eq!(F64::MAX.ulp().to_bits(), (2.0f64).powi(971).to_bits().into());
+
+        assert_eq!(F64::from(-0.1f64).ilogb(), -4);
+        assert_eq!(F64::MIN_POSITIVE_SUBNORMAL.ilogb(), -1074);
+        assert_eq!(F64::ZERO.ilogb(), i32::MIN);
+        assert_eq!(F64::from(1000.0f64).logb().to_bits(), 9.0f64.to_bits().into());
+        assert_eq!(F64::from(0.25f64).logb().to_bits(), (-2.0f64).to_bits().into());
+    }
+
+    #[test]
+    fn nextafter_and_parts() {
+        let one = F64::ONE;
+        assert_eq!(one.nextafter(F64::TWO).to_bits(), (1.0f64 + f64::EPSILON).to_bits().into());
+        assert_eq!(F64::ZERO.nextafter(-one).to_bits(), (-F64::MIN_POSITIVE_SUBNORMAL).to_bits());
+
+        let f = F64::from(-6.5f64);
+        let (negative, exp, sig) = f.to_parts();
+        assert!(negative);
+        assert_eq!(F64::from_parts_rounded(negative, exp, sig).to_bits(), f.to_bits());
+        assert_eq!(F64::from_parts_rounded(false, crate::BInt::from(-2), crate::BUint::from(13u8)).to_bits(), 3.25f64.to_bits().into());
+    }
+
     #[test]
     fn fmod() {
         let f1 = 0.0;
         let f2 = f64::INFINITY;
-        //println!("{:064b}", ((-0.0f64).div_euclid(f2)).to_bits());
-        let a = (crate::F64::from(f1) * (crate::F64::from(f2))).to_bits();
+        let a = (F64::from(f1) * (F64::from(f2))).to_bits();
         let b = (f1 * (f2)).to_bits();
-        /*println!("{:064b}", a);
-        println!("{:064b}", b);*/
         assert!(a == b.into());
     }
 }
\ No newline at end of file
@@ -1,9 +1,7 @@
 use crate::ExpType;
-use crate::uint::BUint;
-use crate::int::Bint;
-use crate::digit::{Digit, self};
-//use crate::ExpType;
-use crate::As;
+use crate::bigints::*;
+use crate::cast::As;
+use crate::digit;
 
 macro_rules! handle_nan {
     ($ret: expr; $($n: expr), +) => {
@@ -13,6 +11,17 @@ macro_rules! handle_nan {
     };
 }
 
+macro_rules! float_impl {
+    ($name: ident) => {
+        $name!(Float, BUint, BInt, u64);
+        $name!(FloatD32, BUintD32, BIntD32, u32);
+        $name!(FloatD16, BUintD16, BIntD16, u16);
+        $name!(FloatD8, BUintD8, BIntD8, u8);
+    };
+}
+
+pub(crate) use float_impl;
+
 mod cast;
 mod classify;
 mod cmp;
@@ -20,143 +29,296 @@ mod consts;
 mod convert;
 mod math;
 mod ops;
+mod transcendental;
 
-#[cfg(feature = "serde_all")]
-use serde::{Serialize, Deserialize};
-
-#[derive(Clone, Copy, Debug)]
-#[cfg_attr(feature = "serde_all", derive(Serialize, Deserialize))]
-pub struct Float<const W: usize, const MB: usize> {
-    bits: BUint<W>,
-}
+#[cfg(feature = "numtraits")]
+mod numtraits;
 
-// TODO: implement rand traits
+#[cfg(feature = "rand")]
+mod random;
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    const MB: ExpType = MB as _;
-    const BITS: ExpType = BUint::<W>::BITS;
-    
-    const EXPONENT_BITS: ExpType = Self::BITS - Self::MB - 1;
+#[cfg(feature = "rand")]
+pub use random::UniformFloat;
 
-    /*const MANTISSA_WORDS: (usize, usize) = (MB / digit::BITS as usize, MB % digit::BITS as usize);
+#[cfg(feature = "serde_all")]
+use serde::{Serialize, Deserialize};
 
-    const EXPONENT_MASK: BUint<W> = BUint::MAX.wrapping_shl(Self::MB) ^ Bint::MIN.to_bits();*/
+/// Rounding direction used when a result cannot be represented exactly in the target format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    /// Round to the nearest representable value, with ties rounded to the value with an even least significant bit. This is the IEEE 754 default.
+    #[default]
+    NearestTiesEven,
+    /// Round to the nearest representable value, with ties rounded away from zero.
+    NearestTiesAway,
+    /// Round towards zero (truncate).
+    TowardZero,
+    /// Round towards positive infinity.
+    TowardPositive,
+    /// Round towards negative infinity.
+    TowardNegative,
 }
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
+impl RoundingMode {
     #[inline]
-    pub const fn from_parts(negative: bool, exponent: BUint<W>, mantissa: BUint<W>) -> Self {
-        let mut words = *(exponent | mantissa).digits();
-        if negative {
-            words[W - 1] |= 1 << (digit::BITS - 1);
+    pub(crate) const fn round_up(self, negative: bool, odd: bool, round: bool, sticky: bool) -> bool {
+        match self {
+            Self::NearestTiesEven => round && (sticky || odd),
+            Self::NearestTiesAway => round,
+            Self::TowardZero => false,
+            Self::TowardPositive => !negative && (round || sticky),
+            Self::TowardNegative => negative && (round || sticky),
         }
-        Self::from_words(words)
-    }
-
-    #[inline(always)]
-    const fn from_words(words: [Digit; W]) -> Self {
-        Self::from_bits(BUint::from_digits(words))
-    }
-
-    #[inline(always)]
-    const fn words(&self) -> &[Digit; W] {
-        &self.bits.digits
     }
 
     #[inline]
-    const fn exponent(self) -> Bint<W> {
-        let u: BUint<W> = (self.to_bits() & Bint::MAX.to_bits()).wrapping_shr(Self::MB);
-        Bint::from_bits(u)
-    }
-    
-    /*const fn actual_exponent(self) -> Bint<W> {
-        self.exponent() - Self::EXP_BIAS
-    }
-    const fn unshifted_exponent(self) -> Bint<W> {
-        Bint::from_bits(self.to_bits() & Self::EXPONENT_MASK)
-    }*/
-    const MANTISSA_MASK: BUint<W> = BUint::MAX.wrapping_shr(Self::EXPONENT_BITS + 1);
-    /*const fn mantissa(self) -> BUint<W> {
-        self.to_bits() & Self::MANTISSA_MASK
-    }
-    const fn actual_mantissa(self) -> BUint<W> {
-        if self.is_subnormal() {
-            self.mantissa()
-        } else {
-            self.mantissa() | (BUint::ONE.wrapping_shl(MB))
+    pub(crate) const fn overflows_to_infinity(self, negative: bool) -> bool {
+        match self {
+            Self::NearestTiesEven | Self::NearestTiesAway => true,
+            Self::TowardZero => false,
+            Self::TowardPositive => !negative,
+            Self::TowardNegative => negative,
         }
-    }*/
-    #[inline(always)]
-    const fn to_int(self) -> Bint<W> {
-        Bint::from_bits(self.to_bits())
     }
+}
 
-    #[inline]
-    pub const fn copysign(self, sign: Self) -> Self {
-        let mut self_words = *self.words();
-        if sign.is_sign_negative() {
-            self_words[W - 1] |= 1 << (digit::BITS - 1);
-        } else {
-            self_words[W - 1] &= (!0) >> 1;
+macro_rules! mod_impl {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        /// Floating point type with `W` digits of storage and `MB` explicitly stored mantissa bits, stored as
+        #[doc = concat!("`", stringify!($Digit), "`")]
+        /// digits.
+        ///
+        /// The sign is stored in the most significant bit and the exponent in the `W * BITS - MB - 1` bits below it (where `BITS` is the bit width of the digit type), following the IEEE 754 binary interchange formats. The digit type only determines how the bits are stored, so the total bit width must be a multiple of the digit's bit width: for example, binary16 is `FloatD16<1, 10>` or `FloatD8<2, 10>`, bfloat16 is `FloatD16<1, 7>` and binary32 is `FloatD32<1, 23>`.
+        #[derive(Clone, Copy, Debug)]
+        #[cfg_attr(feature = "serde_all", derive(Serialize, Deserialize))]
+        pub struct $Float<const W: usize, const MB: usize> {
+            bits: $BUint<W>,
         }
-        Self::from_bits(BUint::from_digits(self_words))
-    }
 
-    #[inline]
-    pub const fn signum(self) -> Self {
-        handle_nan!(Self::NAN; self);
-        Self::ONE.copysign(self)
-    }
-}
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            const MB: ExpType = MB as _;
+            const BITS: ExpType = $BUint::<W>::BITS;
 
-impl<const W: usize, const MB: usize> Default for Float<W, MB> {
-    #[inline]
-    fn default() -> Self {
-        Self::ZERO
-    }
-}
+            const EXPONENT_BITS: ExpType = Self::BITS - Self::MB - 1;
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    #[inline]
-    const fn exp_mant(&self) -> (BUint<W>, BUint<W>) {
-        let bits = self.bits;
-        let exp = (bits << 1u8) >> (Self::MB + 1);
-        let mant = bits & Self::MANTISSA_MASK;
-        
-        if exp.is_zero() {
-            (BUint::ONE, mant)
-        } else {
-            (exp, mant | (BUint::ONE << Self::MB))
+            const MANTISSA_MASK: $BUint<W> = Self::bit_range(0, Self::MB);
+
+            /// Returns the integer with the bits in the range `start..end` set. Shifts are not `const` on stable, so this is used to build the constants of the format.
+            const fn bit_range(start: ExpType, end: ExpType) -> $BUint<W> {
+                let mut digits = [0; W];
+                let mut i = start;
+                while i < end {
+                    digits[(i >> digit::$Digit::BIT_SHIFT) as usize] |= 1 << (i & digit::$Digit::BITS_MINUS_1);
+                    i += 1;
+                }
+                $BUint::from_digits(digits)
+            }
+
+            /// Returns `u << rhs`, for use in constant expressions.
+            const fn shl_const(u: $BUint<W>, rhs: ExpType) -> $BUint<W> {
+                let mut digits = [0; W];
+                let mut i = 0;
+                while i + rhs < Self::BITS {
+                    if u.bit(i) {
+                        let j = i + rhs;
+                        digits[(j >> digit::$Digit::BIT_SHIFT) as usize] |= 1 << (j & digit::$Digit::BITS_MINUS_1);
+                    }
+                    i += 1;
+                }
+                $BUint::from_digits(digits)
+            }
+
+            /// Converts `n` to an integer of this width, for use in constant expressions. Bits which do not fit are discarded.
+            const fn uint_from_exp_type(n: ExpType) -> $BUint<W> {
+                let mut digits = [0; W];
+                let mut i = 0;
+                while i < ExpType::BITS && i < Self::BITS {
+                    if n & (1 << i) != 0 {
+                        digits[(i >> digit::$Digit::BIT_SHIFT) as usize] |= 1 << (i & digit::$Digit::BITS_MINUS_1);
+                    }
+                    i += 1;
+                }
+                $BUint::from_digits(digits)
+            }
+
+            /// Converts `n` to the exponent type, saturating at `$BInt::MAX` if it does not fit.
+            #[inline]
+            fn exp_from(n: ExpType) -> $BInt<W> {
+                let e: $BInt<W> = n.as_();
+                if e.is_negative() || e.as_::<ExpType>() != n {
+                    $BInt::MAX
+                } else {
+                    e
+                }
+            }
         }
-    }
 
-    #[inline]
-    pub(super) const fn decode(self) -> (BUint<W>, Bint<W>) {
-        let bits = self.bits;
-        let exp = (bits << 1u8) >> (Self::MB + 1);
-        let mant = if exp.is_zero() {
-            (bits & Self::MANTISSA_MASK) << 1 as ExpType
-        } else {
-            (bits & Self::MANTISSA_MASK) | (BUint::power_of_two(MB as ExpType))
-        };
-        let exp = Bint::from_bits(exp) - Self::EXP_BIAS + MB.as_::<Bint<W>>();
-        (mant, exp)
-    }
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            /// Creates a float from its sign, its exponent field (already shifted into position above the mantissa) and its stored mantissa bits.
+            ///
+            /// No rounding or normalisation is performed: the bits of `exponent` and `mantissa` are combined as they are. This is therefore not the inverse of [`to_parts`](Self::to_parts), which returns the unbiased exponent and the significand with its implicit bit: to create a float from those parts, use [`from_parts_rounded`](Self::from_parts_rounded).
+            #[inline]
+            pub const fn from_parts(negative: bool, exponent: $BUint<W>, mantissa: $BUint<W>) -> Self {
+                let mut words = *exponent.digits();
+                let mut i = 0;
+                while i < W {
+                    words[i] |= mantissa.digits()[i];
+                    i += 1;
+                }
+                if negative {
+                    words[W - 1] |= 1 << (digit::$Digit::BITS - 1);
+                }
+                Self::from_words(words)
+            }
 
-    #[inline]
-    const fn from_exp_mant(negative: bool, exp: BUint<W>, mant: BUint<W>) -> Self {
-        let mut bits = (exp << Self::MB) | mant;
-        if negative {
-            bits = bits | Bint::MIN.to_bits();
+            /// Returns the sign, exponent and integer significand of `self`, such that `self` is equal to `(-1)^sign * significand * 2^exponent`.
+            ///
+            /// The significand includes the implicit leading bit for normal values. For zero, the significand is zero. For infinite and NaN values, the parts are taken directly from the bit fields and do not round trip through [`from_parts_rounded`](Self::from_parts_rounded).
+            ///
+            /// The inverse of this method is [`from_parts_rounded`](Self::from_parts_rounded), not [`from_parts`](Self::from_parts), which takes the raw exponent and mantissa bit fields instead.
+            #[inline]
+            pub fn to_parts(self) -> (bool, $BInt<W>, $BUint<W>) {
+                let (mant, exp) = self.decode();
+                (self.is_sign_negative(), exp, mant)
+            }
+
+            /// Creates a float equal to `(-1)^negative * significand * 2^exponent`, rounded to the nearest representable value with ties to even. This is the inverse of [`to_parts`](Self::to_parts) for finite values.
+            ///
+            /// The result overflows to infinity and underflows to zero or a subnormal value in the same way as arithmetic operations do.
+            #[inline]
+            pub fn from_parts_rounded(negative: bool, exponent: $BInt<W>, significand: $BUint<W>) -> Self {
+                Self::round_parts(negative, exponent, significand, false, RoundingMode::NearestTiesEven)
+            }
+
+            #[inline(always)]
+            const fn from_words(words: [$Digit; W]) -> Self {
+                Self::from_bits($BUint::from_digits(words))
+            }
+
+            #[inline(always)]
+            const fn words(&self) -> &[$Digit; W] {
+                &self.bits.digits
+            }
+
+            #[inline(always)]
+            const fn to_int(self) -> $BInt<W> {
+                $BInt::from_bits(self.to_bits())
+            }
+
+            /// Flips the sign bit of `self`.
+            #[inline]
+            const fn negate(self) -> Self {
+                let mut words = *self.words();
+                words[W - 1] ^= 1 << (digit::$Digit::BITS - 1);
+                Self::from_words(words)
+            }
+
+            #[inline]
+            pub const fn copysign(self, sign: Self) -> Self {
+                let mut self_words = *self.words();
+                if sign.is_sign_negative() {
+                    self_words[W - 1] |= 1 << (digit::$Digit::BITS - 1);
+                } else {
+                    self_words[W - 1] &= (!0) >> 1;
+                }
+                Self::from_bits($BUint::from_digits(self_words))
+            }
+
+            #[inline]
+            pub fn signum(self) -> Self {
+                handle_nan!(Self::NAN; self);
+                Self::ONE.copysign(self)
+            }
         }
-        let f = Self::from_bits(bits);
-        if negative {
-            assert!(f.is_sign_negative());
+
+        impl<const W: usize, const MB: usize> Default for $Float<W, MB> {
+            #[inline]
+            fn default() -> Self {
+                Self::ZERO
+            }
         }
-        f
-    }
+
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            #[inline]
+            pub(super) fn decode(self) -> ($BUint<W>, $BInt<W>) {
+                let bits = self.bits;
+                let exp = (bits << 1u8) >> (Self::MB + 1);
+                let mant = if exp.is_zero() {
+                    (bits & Self::MANTISSA_MASK) << 1u8
+                } else {
+                    (bits & Self::MANTISSA_MASK) | $BUint::power_of_two(Self::MB)
+                };
+                let exp = $BInt::from_bits(exp) - Self::EXP_BIAS - Self::MB.as_::<$BInt<W>>();
+                (mant, exp)
+            }
+
+            /// Rounds `(-1)^negative * (significand + f) * 2^exponent` to this format, where `f` is some value in the range `(0, 1)` if `sticky` is true and zero otherwise.
+            ///
+            /// `sticky` must be false if `significand` is zero. Exponents which are out of range saturate, which does not affect the result as the value is then far outside the range of the format.
+            pub(crate) fn round_parts<const M: usize>(negative: bool, exponent: $BInt<W>, significand: $BUint<M>, sticky: bool, rounding: RoundingMode) -> Self {
+                let with_sign = |f: Self| if negative { -f } else { f };
+                if significand.is_zero() {
+                    return with_sign(Self::ZERO);
+                }
+                // Bring the significand down to the width of this format, keeping track of any nonzero bits shifted out.
+                let excess = significand.bits().saturating_sub(Self::BITS);
+                let sticky = sticky || significand.trailing_zeros() < excess;
+                let significand: $BUint<W> = (significand >> excess).as_();
+                let exponent = exponent.saturating_add(Self::exp_from(excess));
+
+                let overflow = with_sign(if rounding.overflows_to_infinity(negative) {
+                    Self::INFINITY
+                } else {
+                    Self::MAX
+                });
+                let sig_bits = significand.bits();
+
+                // Unbiased exponent of the most significant bit of the significand.
+                let e = exponent.saturating_add(Self::exp_from(sig_bits - 1));
+                if e > Self::EXP_BIAS {
+                    return overflow;
+                }
+                let biased_exp = e + Self::EXP_BIAS;
+
+                // Number of significant bits that can be represented at this exponent, which is less than `MB + 1` for subnormals.
+                let keep = if biased_exp.is_positive() {
+                    Self::exp_from(Self::MB + 1)
+                } else {
+                    Self::exp_from(Self::MB) + biased_exp
+                };
+                let (mut mant, round, sticky) = match Self::exp_from(sig_bits).checked_sub(keep) {
+                    Some(drop) if !drop.is_positive() => {
+                        (significand << (-drop).as_::<ExpType>(), false, sticky)
+                    },
+                    Some(drop) if drop <= Self::exp_from(sig_bits) => {
+                        let drop: ExpType = drop.as_();
+                        let q = significand.checked_shr(drop).unwrap_or($BUint::ZERO);
+                        (q, significand.bit(drop - 1), sticky || significand.trailing_zeros() < drop - 1)
+                    },
+                    _ => ($BUint::ZERO, false, true),
+                };
+                if rounding.round_up(negative, mant.bit(0), round, sticky) {
+                    mant += $BUint::ONE;
+                }
+
+                // If rounding carries into the next power of two, adding the mantissa (including its implicit bit) to the shifted exponent increments the exponent, and produces infinity if the largest exponent is exceeded.
+                let bits = if biased_exp.is_positive() {
+                    ((biased_exp - $BInt::ONE).to_bits() << Self::MB) + mant
+                } else {
+                    mant
+                };
+                let f = Self::from_bits(bits);
+                if f.is_infinite() {
+                    return overflow;
+                }
+                with_sign(f)
+            }
+        }
+    };
 }
 
+float_impl!(mod_impl);
+
 #[cfg(test)]
 impl From<f64> for crate::F64 {
     #[inline]
@@ -165,8 +327,17 @@ impl From<f64> for crate::F64 {
     }
 }
 
+#[cfg(test)]
+impl From<f32> for crate::F32 {
+    #[inline]
+    fn from(f: f32) -> Self {
+        Self::from_bits(f.to_bits().into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::F64;
 	use crate::test::test_bignum;
 
     test_bignum! {
@@ -177,9 +348,29 @@ mod tests {
         function: <f64>::signum(f: f64)
     }
 
+    quickcheck::quickcheck! {
+        fn quickcheck_to_parts_round_trip(f: f64) -> quickcheck::TestResult {
+            if !f.is_finite() {
+                return quickcheck::TestResult::discard();
+            }
+            let f = F64::from(f);
+            let (negative, exponent, significand) = f.to_parts();
+            quickcheck::TestResult::from_bool(F64::from_parts_rounded(negative, exponent, significand).to_bits() == f.to_bits())
+        }
+    }
+
+    #[test]
+    fn to_parts_round_trip() {
+        for f in [0.0f64, -0.0, 1.0, -6.5, f64::MAX, f64::MIN_POSITIVE, 5e-324, -1e-310] {
+            let f = F64::from(f);
+            let (negative, exponent, significand) = f.to_parts();
+            assert_eq!(F64::from_parts_rounded(negative, exponent, significand).to_bits(), f.to_bits());
+        }
+    }
+
     #[test]
-    fn test_from_exp_mant() {
-        let f = crate::F64::from_exp_mant(true, crate::BUint::ZERO, crate::BUint::ZERO);
+    fn test_from_parts() {
+        let f = F64::from_parts(true, crate::BUint::ZERO, crate::BUint::ZERO);
         assert!(f.is_sign_negative());
     }
 }
\ No newline at end of file
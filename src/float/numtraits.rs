@@ -0,0 +1,687 @@
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use super::RoundingMode;
+use crate::bigints::*;
+use crate::cast::As;
+use crate::int::radix::assert_range;
+use core::num::FpCategory;
+use num_traits::{Bounded, FloatConst, FloatErrorKind, FromPrimitive, Num, NumCast, One, ParseFloatError, Signed, ToPrimitive, Zero};
+
+macro_rules! to_int {
+    ($($name: ident -> $int: ty), *) => {
+        $(
+            #[inline]
+            fn $name(&self) -> Option<$int> {
+                if self.is_nan() {
+                    return None;
+                }
+                match self.int_parts() {
+                    (false, Some(m)) => <$int>::try_from(m).ok(),
+                    (true, Some(m)) if m <= (<$int>::MIN as i128).unsigned_abs() => Some((m as i128).wrapping_neg() as $int),
+                    _ => None,
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! from_primitive {
+    ($($name: ident <- $ty: ty), *) => {
+        $(
+            #[inline]
+            fn $name(n: $ty) -> Option<Self> {
+                Some(n.as_())
+            }
+        )*
+    };
+}
+
+macro_rules! float_const {
+    ($($name: ident), *) => {
+        $(
+            #[inline]
+            fn $name() -> Self {
+                Self::$name
+            }
+        )*
+    };
+}
+
+macro_rules! numtraits {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const W: usize, const MB: usize> Bounded for $Float<W, MB> {
+            #[inline]
+            fn min_value() -> Self {
+                Self::MIN
+            }
+
+            #[inline]
+            fn max_value() -> Self {
+                Self::MAX
+            }
+        }
+
+        impl<const W: usize, const MB: usize> Zero for $Float<W, MB> {
+            #[inline]
+            fn zero() -> Self {
+                Self::ZERO
+            }
+
+            #[inline]
+            fn is_zero(&self) -> bool {
+                Self::is_zero(self)
+            }
+        }
+
+        impl<const W: usize, const MB: usize> One for $Float<W, MB> {
+            #[inline]
+            fn one() -> Self {
+                Self::ONE
+            }
+
+            #[inline]
+            fn is_one(&self) -> bool {
+                *self == Self::ONE
+            }
+        }
+
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            /// Parses a string of digits in the given radix, with an optional fractional part and exponent, into a positive float.
+            fn parse_finite(src: &str, radix: u32) -> Result<Self, ParseFloatError> {
+                const INVALID: ParseFloatError = ParseFloatError {
+                    kind: FloatErrorKind::Invalid,
+                };
+                // The exponent marker must not be a digit in the given radix.
+                let exp_marker = match radix {
+                    2..=14 => Some('e'),
+                    15..=25 => Some('p'),
+                    _ => None,
+                };
+                let (mantissa, exp) = match exp_marker.and_then(|m| src.find(|c: char| c.to_ascii_lowercase() == m)) {
+                    Some(i) => {
+                        let exp = &src[i + 1..];
+                        let (negative, digits) = match exp.as_bytes().first() {
+                            Some(b'-') => (true, &exp[1..]),
+                            Some(b'+') => (false, &exp[1..]),
+                            _ => (false, exp),
+                        };
+                        if digits.is_empty() {
+                            return Err(INVALID);
+                        }
+                        let mut e = 0i64;
+                        for c in digits.chars() {
+                            let d = c.to_digit(10).ok_or(INVALID)?;
+                            e = e.saturating_mul(10).saturating_add(d as i64);
+                        }
+                        (&src[..i], if negative { -e } else { e })
+                    },
+                    None => (src, 0),
+                };
+
+                let mut sig = $BUint::<W>::ZERO;
+                let mut exp = exp;
+                // Whether any nonzero digits were dropped because the significand couldn't hold them.
+                let mut sticky = false;
+                let mut seen_digit = false;
+                let mut seen_point = false;
+                for c in mantissa.chars() {
+                    if c == '.' {
+                        if seen_point {
+                            return Err(INVALID);
+                        }
+                        seen_point = true;
+                        continue;
+                    }
+                    let d = c.to_digit(radix).ok_or(INVALID)?;
+                    seen_digit = true;
+                    match sig.checked_mul($BUint::from_digit(radix as $Digit)).and_then(|s| s.checked_add($BUint::from_digit(d as $Digit))) {
+                        Some(s) => {
+                            sig = s;
+                            if seen_point {
+                                exp = exp.saturating_sub(1);
+                            }
+                        },
+                        // The significand can't hold any more digits, so the remaining ones only affect the exponent.
+                        None => {
+                            sticky |= d != 0;
+                            if !seen_point {
+                                exp = exp.saturating_add(1);
+                            }
+                        },
+                    }
+                }
+                if !seen_digit {
+                    return Err(INVALID);
+                }
+                if sig.is_zero() {
+                    return Ok(Self::ZERO);
+                }
+
+                if radix.is_power_of_two() {
+                    // The value is `sig * 2^(exp * log2(radix))`, which can be rounded exactly.
+                    let exp2 = exp.saturating_mul(radix.trailing_zeros() as i64);
+                    let e: $BInt<W> = exp2.as_();
+                    if e.as_::<i64>() != exp2 {
+                        return Ok(if exp2.is_negative() { Self::ZERO } else { Self::INFINITY });
+                    }
+                    return Ok(Self::round_parts(false, e, sig, sticky, RoundingMode::NearestTiesEven));
+                }
+
+                // Scale by the power of the radix in steps, so that an intermediate power can't overflow when the result is in range.
+                let radix: Self = radix.as_();
+                let mut value = Self::round_parts(false, $BInt::ZERO, sig, sticky, RoundingMode::NearestTiesEven);
+                let mut remaining = exp.unsigned_abs();
+                let mut step = remaining;
+                while remaining != 0 && value.is_finite() && !value.is_zero() {
+                    step = step.min(remaining);
+                    let power = radix.powi(step.min(i32::MAX as u64) as i32);
+                    if power.is_infinite() && step > 1 {
+                        step /= 2;
+                        continue;
+                    }
+                    value = if exp.is_negative() {
+                        value / power
+                    } else {
+                        value * power
+                    };
+                    remaining -= step.min(i32::MAX as u64);
+                }
+                Ok(value)
+            }
+        }
+
+        impl<const W: usize, const MB: usize> Num for $Float<W, MB> {
+            type FromStrRadixErr = ParseFloatError;
+
+            /// Parses a float from a string in the given radix.
+            ///
+            /// The string consists of an optional sign, followed by either `inf`, `infinity`, `nan`, or digits with an optional `.` and fractional part. An exponent, which is a decimal power of the radix, may follow, introduced by `e` or `E` for radices up to 14 and `p` or `P` for radices from 15 to 25. The result is not guaranteed to be correctly rounded unless the radix is a power of two.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 36 inclusive.
+            fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseFloatError> {
+                assert_range!(radix, 36);
+                let (negative, src) = match src.as_bytes().first() {
+                    Some(b'-') => (true, &src[1..]),
+                    Some(b'+') => (false, &src[1..]),
+                    _ => (false, src),
+                };
+                if src.is_empty() {
+                    return Err(ParseFloatError {
+                        kind: FloatErrorKind::Empty,
+                    });
+                }
+                let value = match src {
+                    "inf" | "infinity" => Self::INFINITY,
+                    "nan" => Self::NAN,
+                    _ => Self::parse_finite(src, radix)?,
+                };
+                Ok(if negative { -value } else { value })
+            }
+        }
+
+        impl<const W: usize, const MB: usize> Signed for $Float<W, MB> {
+            #[inline]
+            fn abs(&self) -> Self {
+                Self::abs(*self)
+            }
+
+            #[inline]
+            fn abs_sub(&self, other: &Self) -> Self {
+                if *self <= *other {
+                    Self::ZERO
+                } else {
+                    *self - *other
+                }
+            }
+
+            #[inline]
+            fn signum(&self) -> Self {
+                Self::signum(*self)
+            }
+
+            #[inline]
+            fn is_positive(&self) -> bool {
+                self.is_sign_positive()
+            }
+
+            #[inline]
+            fn is_negative(&self) -> bool {
+                self.is_sign_negative()
+            }
+        }
+
+        impl<const W: usize, const MB: usize> ToPrimitive for $Float<W, MB> {
+            to_int! {
+                to_i8 -> i8,
+                to_i16 -> i16,
+                to_i32 -> i32,
+                to_i64 -> i64,
+                to_i128 -> i128,
+                to_isize -> isize,
+                to_u8 -> u8,
+                to_u16 -> u16,
+                to_u32 -> u32,
+                to_u64 -> u64,
+                to_u128 -> u128,
+                to_usize -> usize
+            }
+
+            #[inline]
+            fn to_f32(&self) -> Option<f32> {
+                Some((*self).as_())
+            }
+
+            #[inline]
+            fn to_f64(&self) -> Option<f64> {
+                Some((*self).as_())
+            }
+        }
+
+        impl<const W: usize, const MB: usize> FromPrimitive for $Float<W, MB> {
+            from_primitive! {
+                from_i8 <- i8,
+                from_i16 <- i16,
+                from_i32 <- i32,
+                from_i64 <- i64,
+                from_i128 <- i128,
+                from_isize <- isize,
+                from_u8 <- u8,
+                from_u16 <- u16,
+                from_u32 <- u32,
+                from_u64 <- u64,
+                from_u128 <- u128,
+                from_usize <- usize,
+                from_f32 <- f32,
+                from_f64 <- f64
+            }
+        }
+
+        impl<const W: usize, const MB: usize> NumCast for $Float<W, MB> {
+            /// Converts integers exactly before rounding them to this format, and everything else through `f64`.
+            fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+                let f = n.to_f64();
+                match (n.to_u128(), n.to_i128()) {
+                    (Some(u), _) if f == Some(u as f64) => Some(u.as_()),
+                    (_, Some(i)) if f == Some(i as f64) => Some(i.as_()),
+                    _ => f.map(|f| f.as_()),
+                }
+            }
+        }
+
+        impl<const W: usize, const MB: usize> num_traits::Float for $Float<W, MB> {
+            #[inline]
+            fn nan() -> Self {
+                Self::NAN
+            }
+
+            #[inline]
+            fn infinity() -> Self {
+                Self::INFINITY
+            }
+
+            #[inline]
+            fn neg_infinity() -> Self {
+                Self::NEG_INFINITY
+            }
+
+            #[inline]
+            fn neg_zero() -> Self {
+                Self::NEG_ZERO
+            }
+
+            #[inline]
+            fn min_value() -> Self {
+                Self::MIN
+            }
+
+            #[inline]
+            fn min_positive_value() -> Self {
+                Self::MIN_POSITIVE
+            }
+
+            #[inline]
+            fn epsilon() -> Self {
+                Self::EPSILON
+            }
+
+            #[inline]
+            fn max_value() -> Self {
+                Self::MAX
+            }
+
+            #[inline]
+            fn is_nan(self) -> bool {
+                Self::is_nan(self)
+            }
+
+            #[inline]
+            fn is_infinite(self) -> bool {
+                Self::is_infinite(self)
+            }
+
+            #[inline]
+            fn is_finite(self) -> bool {
+                Self::is_finite(self)
+            }
+
+            #[inline]
+            fn is_normal(self) -> bool {
+                Self::is_normal(self)
+            }
+
+            #[inline]
+            fn is_subnormal(self) -> bool {
+                Self::is_subnormal(self)
+            }
+
+            #[inline]
+            fn classify(self) -> FpCategory {
+                Self::classify(self)
+            }
+
+            #[inline]
+            fn floor(self) -> Self {
+                Self::floor(self)
+            }
+
+            #[inline]
+            fn ceil(self) -> Self {
+                Self::ceil(self)
+            }
+
+            #[inline]
+            fn round(self) -> Self {
+                Self::round(self)
+            }
+
+            #[inline]
+            fn trunc(self) -> Self {
+                Self::trunc(self)
+            }
+
+            #[inline]
+            fn fract(self) -> Self {
+                Self::fract(self)
+            }
+
+            #[inline]
+            fn abs(self) -> Self {
+                Self::abs(self)
+            }
+
+            #[inline]
+            fn signum(self) -> Self {
+                Self::signum(self)
+            }
+
+            #[inline]
+            fn is_sign_positive(self) -> bool {
+                Self::is_sign_positive(self)
+            }
+
+            #[inline]
+            fn is_sign_negative(self) -> bool {
+                Self::is_sign_negative(self)
+            }
+
+            #[inline]
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                Self::mul_add(self, a, b)
+            }
+
+            #[inline]
+            fn recip(self) -> Self {
+                Self::recip(self)
+            }
+
+            #[inline]
+            fn powi(self, n: i32) -> Self {
+                Self::powi(self, n)
+            }
+
+            #[inline]
+            fn powf(self, n: Self) -> Self {
+                Self::powf(self, n)
+            }
+
+            #[inline]
+            fn sqrt(self) -> Self {
+                Self::sqrt(self)
+            }
+
+            #[inline]
+            fn exp(self) -> Self {
+                Self::exp(self)
+            }
+
+            #[inline]
+            fn exp2(self) -> Self {
+                Self::exp2(self)
+            }
+
+            #[inline]
+            fn ln(self) -> Self {
+                Self::ln(self)
+            }
+
+            #[inline]
+            fn log(self, base: Self) -> Self {
+                Self::log(self, base)
+            }
+
+            #[inline]
+            fn log2(self) -> Self {
+                Self::log2(self)
+            }
+
+            #[inline]
+            fn log10(self) -> Self {
+                Self::log10(self)
+            }
+
+            #[inline]
+            fn to_degrees(self) -> Self {
+                self * (180u8.as_::<Self>() / Self::PI)
+            }
+
+            #[inline]
+            fn to_radians(self) -> Self {
+                self * (Self::PI / 180u8.as_::<Self>())
+            }
+
+            #[inline]
+            fn max(self, other: Self) -> Self {
+                Self::max(self, other)
+            }
+
+            #[inline]
+            fn min(self, other: Self) -> Self {
+                Self::min(self, other)
+            }
+
+            #[inline]
+            fn abs_sub(self, other: Self) -> Self {
+                Signed::abs_sub(&self, &other)
+            }
+
+            #[inline]
+            fn cbrt(self) -> Self {
+                Self::cbrt(self)
+            }
+
+            #[inline]
+            fn hypot(self, other: Self) -> Self {
+                Self::hypot(self, other)
+            }
+
+            #[inline]
+            fn sin(self) -> Self {
+                Self::sin(self)
+            }
+
+            #[inline]
+            fn cos(self) -> Self {
+                Self::cos(self)
+            }
+
+            #[inline]
+            fn tan(self) -> Self {
+                Self::tan(self)
+            }
+
+            #[inline]
+            fn asin(self) -> Self {
+                Self::asin(self)
+            }
+
+            #[inline]
+            fn acos(self) -> Self {
+                Self::acos(self)
+            }
+
+            #[inline]
+            fn atan(self) -> Self {
+                Self::atan(self)
+            }
+
+            #[inline]
+            fn atan2(self, other: Self) -> Self {
+                Self::atan2(self, other)
+            }
+
+            #[inline]
+            fn sin_cos(self) -> (Self, Self) {
+                Self::sin_cos(self)
+            }
+
+            #[inline]
+            fn exp_m1(self) -> Self {
+                Self::exp_m1(self)
+            }
+
+            #[inline]
+            fn ln_1p(self) -> Self {
+                Self::ln_1p(self)
+            }
+
+            #[inline]
+            fn sinh(self) -> Self {
+                Self::sinh(self)
+            }
+
+            #[inline]
+            fn cosh(self) -> Self {
+                Self::cosh(self)
+            }
+
+            #[inline]
+            fn tanh(self) -> Self {
+                Self::tanh(self)
+            }
+
+            #[inline]
+            fn asinh(self) -> Self {
+                Self::asinh(self)
+            }
+
+            #[inline]
+            fn acosh(self) -> Self {
+                Self::acosh(self)
+            }
+
+            #[inline]
+            fn atanh(self) -> Self {
+                Self::atanh(self)
+            }
+
+            /// Returns the significand, exponent and sign of `self`, such that `self = sign * significand * 2^exponent`.
+            ///
+            /// For formats with more than 64 significand bits, the significand is truncated to its upper 64 bits, and for formats with a wider exponent range than `i16`, the exponent saturates, so the result is only approximate.
+            fn integer_decode(self) -> (u64, i16, i8) {
+                let sign = if self.is_sign_negative() { -1 } else { 1 };
+                let (mant, exp) = self.decode();
+                let shift = mant.bits().saturating_sub(u64::BITS as _);
+                let exp = exp.saturating_add(Self::exp_from(shift));
+                let exp = match exp.as_::<i16>() {
+                    e if e.as_::<$BInt<W>>() == exp => e,
+                    _ if exp.is_negative() => i16::MIN,
+                    _ => i16::MAX,
+                };
+                ((mant >> shift).as_(), exp, sign)
+            }
+
+            #[inline]
+            fn copysign(self, sign: Self) -> Self {
+                Self::copysign(self, sign)
+            }
+        }
+
+        impl<const W: usize, const MB: usize> FloatConst for $Float<W, MB> {
+            float_const!(E, FRAC_1_PI, FRAC_1_SQRT_2, FRAC_2_PI, FRAC_2_SQRT_PI, FRAC_PI_2, FRAC_PI_3, FRAC_PI_4, FRAC_PI_6, FRAC_PI_8, LN_10, LN_2, LOG10_E, LOG2_E, PI, SQRT_2, TAU, LOG10_2, LOG2_10);
+        }
+    };
+}
+
+super::float_impl!(numtraits);
+
+#[cfg(test)]
+mod tests {
+    use crate::F64;
+    use crate::cast::As;
+    use num_traits::{Float, FloatConst, FromPrimitive, Num, NumCast, ToPrimitive};
+
+    fn bits(f: F64) -> u64 {
+        f.to_bits().as_()
+    }
+
+    #[test]
+    fn from_str_radix() {
+        for s in ["0", "-0", "1.5", "-2.25", "1e10", "123.456e-7", "1e400", "0.1", "inf", "-inf", ".5", "5."] {
+            let expected = f64::from_str_radix(s, 10).unwrap();
+            assert_eq!(bits(F64::from_str_radix(s, 10).unwrap()), expected.to_bits(), "parsing {} failed", s);
+        }
+        assert!(F64::from_str_radix("nan", 10).unwrap().is_nan());
+        assert_eq!(bits(F64::from_str_radix("-ff.8p1", 16).unwrap()), (-4088.0f64).to_bits());
+        assert_eq!(bits(F64::from_str_radix("101.01", 2).unwrap()), 5.25f64.to_bits());
+
+        // The first 16 hex digits fill the 64 bit significand and end in an exact tie when rounded to 53 bits, so the digits after them decide the rounding.
+        let tie = 0x1000000000000080u64;
+        let (down, up) = (0x1000000000000000u64 as f64, 0x1000000000000100u64 as f64);
+        assert_eq!(bits(F64::from_str_radix("10000000000000800", 16).unwrap()), (down * 16.0).to_bits());
+        assert_eq!(bits(F64::from_str_radix("10000000000000801", 16).unwrap()), (up * 16.0).to_bits());
+        assert_eq!(bits(F64::from_str_radix("1000000000000080.01", 16).unwrap()), up.to_bits());
+        assert_eq!(bits(F64::from_str_radix(&format!("{:b}0001", tie), 2).unwrap()), (up * 16.0).to_bits());
+
+        for s in ["", "-", ".", "1e", "1.2.3", "12a", "e5"] {
+            assert!(F64::from_str_radix(s, 10).is_err(), "parsing {} should fail", s);
+        }
+    }
+
+    #[test]
+    fn primitive_conversions() {
+        let f = F64::from_f64(-3.75).unwrap();
+        assert_eq!(f.to_i32(), Some(-3));
+        assert_eq!(f.to_u32(), None);
+        assert_eq!(F64::from_f64(-0.5).unwrap().to_u8(), Some(0));
+        assert_eq!(F64::from_f64(256.0).unwrap().to_u8(), None);
+        assert_eq!(F64::NAN.to_i64(), None);
+        assert_eq!(f.to_f64(), Some(-3.75));
+
+        assert_eq!(bits(<F64 as NumCast>::from(u64::MAX).unwrap()), (u64::MAX as f64).to_bits());
+        assert_eq!(bits(<F64 as NumCast>::from(-2.5f32).unwrap()), (-2.5f64).to_bits());
+        assert_eq!(bits(F64::from_i128(i128::MIN).unwrap()), (i128::MIN as f64).to_bits());
+    }
+
+    #[test]
+    fn float_traits() {
+        assert_eq!(bits(<F64 as FloatConst>::PI()), core::f64::consts::PI.to_bits());
+        assert_eq!(bits(<F64 as FloatConst>::LOG2_10()), core::f64::consts::LOG2_10.to_bits());
+        assert_eq!(bits(<F64 as Float>::epsilon()), f64::EPSILON.to_bits());
+
+        for f in [1.0f64, -0.1, 1e300, 5e-324, 0.0, -0.0, f64::INFINITY] {
+            assert_eq!(Float::integer_decode(<F64 as From<f64>>::from(f)), Float::integer_decode(f));
+        }
+        assert_eq!(bits(Float::to_radians(<F64 as From<f64>>::from(180.0f64))), 180.0f64.to_radians().to_bits());
+    }
+}
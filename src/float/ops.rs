@@ -1,656 +1,400 @@
-use super::Float;
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use crate::bigints::*;
 use core::num::FpCategory;
 use core::ops::{Add, Sub, Mul, Div, Rem, Neg};
-use crate::{BUint, Bint, ExpType, digit};
+use crate::ExpType;
 use core::iter::{Product, Sum, Iterator};
 use crate::cast::As;
-
-// TODO: fix this function
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    #[inline]    
-    fn add_internal(mut self, mut rhs: Self, negative: bool) -> Self {
-        //println!("{:?} {:?}", self, rhs);
-        //debug_assert_eq!(self.is_sign_negative(), rhs.is_sign_negative());
-        if rhs.abs() > self.abs() {
-            // If b has a larger exponent than a, swap a and b so that a has the larger exponent
-            core::mem::swap(&mut self, &mut rhs);
-        }
-        let (mut a_exp, mut a_mant) = self.exp_mant();
-        let (b_exp, mut b_mant) = rhs.exp_mant();
-    
-        let exp_diff = a_exp - b_exp;
-    
-        let sticky_bit = BUint::from(b_mant.trailing_zeros() + 1) < exp_diff;
-    
-        // Append extra bits to the mantissas to ensure correct rounding
-        a_mant <<= 2 as ExpType;
-        b_mant <<= 2 as ExpType;
-    
-        // If the shift causes an overflow, the b_mant is too small so is set to 0
-        b_mant = match exp_diff.try_into().ok() {
-            Some(exp_diff) => b_mant.checked_shr(exp_diff).unwrap_or(BUint::ZERO),
-            None => BUint::ZERO,
-        };
-    
-        // If the shift causes an overflow, the b_mant is too small so is set to 0
-    
-        if sticky_bit {
-            b_mant |= BUint::ONE;
-        }
-    
-        let mut mant = a_mant + b_mant;
-    
-        let overflow = !(mant >> (MB + 3)).is_zero();
-        if !overflow {
-            if mant & BUint::from_digit(0b11) == BUint::from_digit(0b11) || mant & BUint::from_digit(0b110) == BUint::from_digit(0b110) {
-                mant += BUint::FOUR;
-                if !(mant >> (MB + 3)).is_zero() {
-                    mant >>= 1 as ExpType;
-                    a_exp += BUint::ONE;
+use super::RoundingMode;
+
+macro_rules! ops {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            /// Returns the correctly rounded sum of `self` and `rhs`, which must both be finite.
+            #[inline]
+            fn add_finite(self, rhs: Self) -> Self {
+                if rhs.is_zero() {
+                    return if self.is_zero() && self.is_sign_negative() != rhs.is_sign_negative() {
+                        Self::ZERO
+                    } else {
+                        self
+                    };
                 }
-            }
-        } else {
-            match (mant & BUint::from_digit(0b111)).digits()[0] {
-                0b111 | 0b110 | 0b101 => {
-                    mant += BUint::EIGHT;
-                },
-                0b100 => {
-                    if mant & BUint::from_digit(0b1000) == BUint::from_digit(0b1000) {
-                        mant += BUint::EIGHT;
-                    }
-                },
-                _ => {},
-            }
-            
-            mant >>= 1 as ExpType;
-            a_exp += BUint::ONE;
-        }
-        if a_exp > Self::MAX_UNBIASED_EXP {
-            return Self::INFINITY;
-        }
-    
-        mant >>= 2 as ExpType;
-    
-        if (mant >> Self::MB).is_zero() {
-            a_exp = BUint::ZERO;
-        } else {
-            mant ^= BUint::ONE << Self::MB;
-        }
-        //println!("{}", negative);
-        //assert!(negative);
-            let a = Self::from_exp_mant(false, a_exp, mant).neg();
-            assert!(a.is_sign_negative());
-            a
-    }
-}
-
-impl<const W: usize, const MB: usize> Add for Float<W, MB> {
-    type Output = Self;
-
-    #[inline]
-    fn add(self, rhs: Self) -> Self {
-        let self_negative = self.is_sign_negative();
-        let rhs_negative = rhs.is_sign_negative();
-        match (self.classify(), rhs.classify()) {
-            (FpCategory::Nan, _) => return self,
-            (_, FpCategory::Nan) => return rhs,
-            (FpCategory::Infinite, _) => return self,
-            (_, FpCategory::Infinite) => return rhs,
-            (FpCategory::Zero, FpCategory::Zero) => return if self_negative && rhs_negative {
-                Self::NEG_ZERO
-            } else {
-                Self::ZERO
-            },
-            (_, _) => {
-                if self_negative ^ rhs_negative {
-                    self.sub_internal(rhs, self_negative)
+                if self.is_zero() {
+                    return rhs;
+                }
+                // Order the operands so that `a` has the larger magnitude, and so also the larger (or equal) exponent.
+                let (a, b) = if self.abs().to_bits() >= rhs.abs().to_bits() {
+                    (self, rhs)
                 } else {
-                    let r = self.add_internal(rhs, self_negative);
-                    assert!(r.is_sign_negative());
-                    r
+                    (rhs, self)
+                };
+                let (negative, a_exp, a_mant) = a.to_parts();
+                let (b_negative, b_exp, b_mant) = b.to_parts();
+                let subtract = negative != b_negative;
+                let exp_diff = a_exp - b_exp;
+
+                // Shift `a_mant` as far left as possible while leaving one bit of headroom for the carry of the addition.
+                let headroom = Self::BITS - 1 - a_mant.bits();
+                if exp_diff <= Self::exp_from(headroom) {
+                    // The result is exact before rounding.
+                    let a_mant = a_mant << exp_diff.as_::<ExpType>();
+                    let sum = if subtract {
+                        a_mant - b_mant
+                    } else {
+                        a_mant + b_mant
+                    };
+                    if sum.is_zero() {
+                        return Self::ZERO;
+                    }
+                    return Self::round_parts(negative, b_exp, sum, false, RoundingMode::NearestTiesEven);
                 }
+                let a_mant = a_mant << headroom;
+                let shift = exp_diff - Self::exp_from(headroom);
+                let (b_mant, sticky) = if shift < Self::exp_from(Self::BITS) {
+                    let shift: ExpType = shift.as_();
+                    (b_mant >> shift, b_mant.trailing_zeros() < shift)
+                } else {
+                    ($BUint::<W>::ZERO, true)
+                };
+                // `b` is shifted right by at least one bit, so at most one bit cancels in a subtraction, and the result has enough bits for the bits shifted out of `b` to only affect the sticky bit.
+                let sum = if !subtract {
+                    a_mant + b_mant
+                } else if sticky {
+                    a_mant - b_mant - $BUint::ONE
+                } else {
+                    a_mant - b_mant
+                };
+                Self::round_parts(negative, a_exp - Self::exp_from(headroom), sum, sticky, RoundingMode::NearestTiesEven)
             }
         }
-    }
-}
-
-crate::errors::op_ref_impl!(Add<Float<N, MB>> for Float<N, MB>, add);
 
-impl<const W: usize, const MB: usize> Sum for Float<W, MB> {
-    #[inline]
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Self::ZERO, |a, b| a + b)
-    }
-}
-
-impl<'a, const W: usize, const MB: usize> Sum<&'a Self> for Float<W, MB> {
-    #[inline]
-    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
-        iter.fold(Self::ONE, |a, b| a + *b)
-    }
-}
+        impl<const W: usize, const MB: usize> Add for $Float<W, MB> {
+            type Output = Self;
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    #[inline]
-    fn sub_internal(mut self, mut rhs: Self, mut negative: bool) -> Self {
-        if rhs.abs() > self.abs() {
-            // If b has a larger exponent than a, swap a and b so that a has the larger exponent
-            negative = !negative;
-            core::mem::swap(&mut self, &mut rhs);
-        }
-        if self.abs() == rhs.abs() {
-            return Self::ZERO;
-        }
-    
-        let (a_exp, mut a_mant) = self.exp_mant();
-        let (b_exp, mut b_mant) = rhs.exp_mant();
-        let exp_diff = a_exp - b_exp;
-    
-        let mut a_exp = Bint::from_bits(a_exp);
-    
-        let sticky_bit2 = !exp_diff.is_zero() && exp_diff < BUint::<W>::BITS.into() && b_mant.bit(exp_diff.as_::<ExpType>() - 1);
-        let all_zeros = !exp_diff.is_zero() && b_mant.trailing_zeros() + 1 == exp_diff.as_();
-    
-    
-        // Append extra bits to the mantissas to ensure correct rounding
-        a_mant = a_mant << 1 as ExpType;
-        b_mant = b_mant << 1 as ExpType;
-    
-        let sticky_bit = b_mant.trailing_zeros() < exp_diff.as_();
-
-        // If the shift causes an overflow, the b_mant is too small so is set to 0
-        let shifted_b_mant = match exp_diff.try_into().ok() {
-            Some(exp_diff) => b_mant.checked_shr(exp_diff).unwrap_or(BUint::ZERO),
-            None => BUint::ZERO,
-        };
-    
-        // If the shift causes an overflow, the b_mant is too small so is set to 0
-    
-        if sticky_bit {
-            //b_mant |= 1;
-        }
-    
-        let mut mant = a_mant - shifted_b_mant;
-    
-        if mant.bits() == Self::MB + 2 {
-            if mant & BUint::from(0b10u8) == BUint::from(0b10u8) && !sticky_bit {
-                mant += BUint::ONE;
-            }
-    
-            mant >>= 1 as ExpType;
-        } else {
-            a_exp -= Bint::ONE;
-            a_mant <<= 1 as ExpType;
-            b_mant <<= 1 as ExpType;
-    
-            let sticky_bit = b_mant.trailing_zeros() < exp_diff.as_();
-    
-            // If the shift causes an overflow, the b_mant is too small so is set to 0
-            let shifted_b_mant = match exp_diff.try_into().ok() {
-                Some(exp_diff) => b_mant.checked_shr(exp_diff).unwrap_or(BUint::ZERO),
-                None => BUint::ZERO,
-            };
-    
-            // If the shift causes an overflow, the b_mant is too small so is set to 0
-    
-            if sticky_bit {
-                //b_mant |= 1;
-            }
-    
-            mant = a_mant - shifted_b_mant;
-
-            if mant.bits() == Self::MB + 2 {
-                if mant & BUint::from(0b10u8) == BUint::from(0b10u8) && !sticky_bit {
-                    mant += BUint::ONE;
-                }
-    
-                mant >>= 1 as ExpType;
-            } else {
-                
-                let _half_way = (); // TODO
-                //println!("sticky: {}", sticky_bit);
-                if sticky_bit2 && !all_zeros || (sticky_bit2 && all_zeros && b_mant & BUint::from(0b1u8) == BUint::from(0b1u8)) {
-                    //println!("sub");
-                    mant -= BUint::ONE;
-                }
-                let bits = mant.bits();
-                mant <<= Self::MB + 1 - bits;
-                a_exp -= Bint::from(MB as i64 + 2 - bits as i64);
-                if !a_exp.is_positive() {
-                    a_exp = Bint::ONE;
-                    mant >>= Bint::ONE - a_exp;
-                }
-            }
-        }
-    
-        if (mant >> Self::MB).is_zero() {
-            a_exp = Bint::ZERO;
-        } else {
-            mant ^= BUint::ONE << Self::MB;
-        }
-        
-        Self::from_exp_mant(negative, a_exp.to_bits(), mant)
-    }
-}
-
-impl<const W: usize, const MB: usize> Sub for Float<W, MB> {
-    type Output = Self;
-
-    #[inline]
-    fn sub(self, rhs: Self) -> Self {
-        match (self.classify(), rhs.classify()) {
-            (FpCategory::Nan, _) => return self,
-            (_, FpCategory::Nan) => rhs,
-            (FpCategory::Infinite, FpCategory::Infinite) => return Self::NEG_NAN,
-            (FpCategory::Infinite, _) => return self,
-            (_, FpCategory::Infinite) => return rhs.neg(),
-            (_, _) => {
-                let self_negative = self.is_sign_negative();
-                let rhs_negative = rhs.is_sign_negative();
-                if self_negative ^ rhs_negative {
-                    self.add_internal(rhs, self_negative)
-                } else {
-                    self.sub_internal(rhs, self_negative)
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                match (self.classify(), rhs.classify()) {
+                    (FpCategory::Nan, _) => self,
+                    (_, FpCategory::Nan) => rhs,
+                    (FpCategory::Infinite, FpCategory::Infinite) => if self.is_sign_negative() == rhs.is_sign_negative() {
+                        self
+                    } else {
+                        Self::NAN
+                    },
+                    (FpCategory::Infinite, _) => self,
+                    (_, FpCategory::Infinite) => rhs,
+                    (_, _) => self.add_finite(rhs),
                 }
             }
         }
-    }
-}
-
-crate::errors::op_ref_impl!(Sub<Float<N, MB>> for Float<N, MB>, sub);
 
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    #[inline]
-    fn mul_internal(self, rhs: Self, negative: bool) -> Self where [(); W * 2]:, {
-        let (a, b) = (self, rhs);
-        let (exp_a, mant_a) = a.exp_mant();
-        let (exp_b, mant_b) = b.exp_mant();
+        crate::int::ops::op_ref_impl!(Add<$Float<W, MB>> for $Float<W, MB>, add);
 
-        // TODO: make so as_ can infer type so can switch trait definition if needed
-        let mant_prod = mant_a.as_::<BUint<{W * 2}>>() * mant_b.as_::<BUint<{W * 2}>>();
-
-        let prod_bits = mant_prod.bits();
-
-        if prod_bits == 0 {
-            return if negative {
-                Self::NEG_ZERO
-            } else {
-                Self::ZERO
-            };
-        }
-
-        let extra_bits = if prod_bits > (Self::MB + 1) {
-            prod_bits - (Self::MB + 1)
-        } else {
-            0
-        };
-
-        let mut exp = Bint::from_bits(exp_a) + Bint::from_bits(exp_b) + Bint::from(extra_bits) - Self::EXP_BIAS - Bint::from(MB);
-
-        if exp > Self::MAX_EXP + Self::EXP_BIAS - Bint::ONE {
-            //println!("rhs: {}", rhs.to_bits());
-            return if negative {
-                Self::NEG_INFINITY
-            } else {
-                Self::INFINITY
-            };
-        }
-
-        let mut extra_shift = BUint::ZERO;
-        if !exp.is_positive() {
-            extra_shift = (Bint::ONE - exp).to_bits();
-            exp = Bint::ONE;
+        impl<const W: usize, const MB: usize> Sum for $Float<W, MB> {
+            #[inline]
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |a, b| a + b)
+            }
         }
-        let total_shift = BUint::from(extra_bits) + extra_shift;
 
-        let sticky_bit = BUint::from(mant_prod.trailing_zeros() + 1) < total_shift;
-        let mut mant = match (total_shift - BUint::ONE).to_exp_type() {
-            Some(sub) => mant_prod.checked_shr(sub).unwrap_or(BUint::ZERO),
-            None => BUint::ZERO,
-        };
-        if mant & BUint::ONE == BUint::ONE {
-            if sticky_bit || mant & BUint::from(0b11u8) == BUint::from(0b11u8) {
-                // Round up
-                mant += BUint::ONE;
+        impl<'a, const W: usize, const MB: usize> Sum<&'a Self> for $Float<W, MB> {
+            #[inline]
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |a, b| a + *b)
             }
         }
-        mant >>= 1 as ExpType;
 
-        if exp == Bint::ONE && mant.bits() < Self::MB + 1 {
-            return Self::from_exp_mant(negative, BUint::ZERO, mant.as_());
-        }
-        if mant >> Self::MB != BUint::ZERO {
-            mant ^= BUint::ONE << Self::MB as u32;
-        }
-        Self::from_exp_mant(negative, exp.to_bits(), mant.as_())
-    }
-}
+        impl<const W: usize, const MB: usize> Sub for $Float<W, MB> {
+            type Output = Self;
 
-impl<const W: usize, const MB: usize> Mul for Float<W, MB> where [(); W * 2]:, {
-    type Output = Self;
-    
-    #[inline]
-    fn mul(self, rhs: Self) -> Self {
-        let negative = self.is_sign_negative() ^ rhs.is_sign_negative();
-        match (self.classify(), rhs.classify()) {
-            (FpCategory::Nan, _) | (_, FpCategory::Nan) => return Self::NAN,
-            (FpCategory::Infinite, FpCategory::Zero) | (FpCategory::Zero, FpCategory::Infinite) => Self::NEG_NAN,
-            (FpCategory::Infinite, _) | (_, FpCategory::Infinite) => if negative {
-                Self::NEG_INFINITY
-            } else {
-                Self::INFINITY
-            },
-            (_, _) => {
-                self.mul_internal(rhs, negative)
-            },
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                Add::add(self, rhs.negate())
+            }
         }
-    }
-}
 
-impl<const W: usize, const MB: usize> Product for Float<W, MB>  where [(); W * 2]:, {
-    #[inline]
-    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Self::ONE, |a, b| a * b)
-    }
-}
+        crate::int::ops::op_ref_impl!(Sub<$Float<W, MB>> for $Float<W, MB>, sub);
 
-impl<'a, const W: usize, const MB: usize> Product<&'a Self> for Float<W, MB> where [(); W * 2]:, {
-    #[inline]
-    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
-        iter.fold(Self::ONE, |a, b| a * *b)
-    }
-}
-
-impl<const W: usize, const MB: usize> Float<W, MB> {
-    #[inline]
-    fn div_internal(self, rhs: Self, negative: bool) -> Self where [(); W * 2]:, {
-        let (a, b) = (self, rhs);
-        let (e1, s1) = a.exp_mant();
-        let (e2, s2) = b.exp_mant();
-    
-        let b1 = s1.bits();
-        let b2 = s2.bits();
-    
-        let mut e = Bint::from_bits(e1) - Bint::from_bits(e2) + Self::EXP_BIAS + Bint::from(b1) - Bint::from(b2) - Bint::ONE;
-    
-        let mut extra_shift = BUint::ZERO;
-        if !e.is_positive() {
-            extra_shift = (Bint::ONE - e).to_bits();
-            e = Bint::ONE;
-        }
-    
-        let total_shift = Bint::from(MB as i32 + 1 + b2 as i32 - b1 as i32) - Bint::from_bits(extra_shift);
-    
-        let large = if !total_shift.is_negative() {
-            (s1.as_::<BUint<{W * 2}>>()) << total_shift
-        } else {
-            (s1.as_::<BUint<{W * 2}>>()) >> (-total_shift)
-        };
-        let mut division = (large / (s2.as_::<BUint<{W * 2}>>())).as_::<BUint<W>>();
-    
-        let rem = if division.bits() != Self::MB + 2 {
-            let rem = (large % (s2.as_::<BUint<{W * 2}>>())).as_::<BUint<W>>();
-            rem
-        } else {
-            e += Bint::ONE;
-            division = ((large >> 1 as ExpType) / (s2.as_::<BUint<{W * 2}>>())).as_::<BUint<W>>();
-            //println!("div {} {}", large >> 1u8, s2);
-            let rem = ((large >> 1 as ExpType) % (s2.as_::<BUint<{W * 2}>>())).as_::<BUint<W>>();
-            rem
-        };
-        //println!("{}", rem);
-        if rem * BUint::TWO > s2 {
-            division += BUint::ONE;
-        } else if rem * BUint::TWO == s2 {
-            if (division & BUint::ONE) == BUint::ONE {
-                division += BUint::ONE;
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            /// Returns the correctly rounded product of `self` and `rhs`, which must both be finite and nonzero.
+            #[inline]
+            fn mul_internal(self, rhs: Self, negative: bool) -> Self {
+                let (_, a_exp, a_mant) = self.to_parts();
+                let (_, b_exp, b_mant) = rhs.to_parts();
+                let exp = a_exp.saturating_add(b_exp);
+                let (low, high) = a_mant.widening_mul(b_mant);
+                if high.is_zero() {
+                    return Self::round_parts(negative, exp, low, false, RoundingMode::NearestTiesEven);
+                }
+                // Keep the most significant `BITS` bits of the product, and fold the rest into the sticky bit.
+                let shift = high.bits();
+                let mant = (high << (Self::BITS - shift)) | low.checked_shr(shift).unwrap_or($BUint::ZERO);
+                let sticky = low.trailing_zeros() < shift;
+                Self::round_parts(negative, exp.saturating_add(Self::exp_from(shift)), mant, sticky, RoundingMode::NearestTiesEven)
             }
         }
-        if division.bits() == Self::MB + 2 {
-            e += Bint::ONE;
-            division >>= 1 as ExpType;
-        }
-    
-        if e > Self::MAX_EXP + Self::EXP_BIAS - Bint::ONE {
-            return Self::INFINITY;
-        }
 
-        //println!("{:032b}", division);
-    
-        if e == Bint::ONE && division.bits() < Self::MB + 1 {
-            return Self::from_exp_mant(negative, BUint::ZERO, division);
-        }
-    
-        if division >> Self::MB != BUint::ZERO {
-            division ^= BUint::ONE << Self::MB;
-        }
-        Self::from_exp_mant(negative, e.to_bits(), division)
-    }
-}
+        impl<const W: usize, const MB: usize> Mul for $Float<W, MB> {
+            type Output = Self;
 
-impl<const W: usize, const MB: usize> Div for Float<W, MB> where [(); W * 2]:, {
-    type Output = Self;
-    
-    #[inline]
-    fn div(self, rhs: Self) -> Self {
-        let negative = self.is_sign_negative() ^ rhs.is_sign_negative();
-        match (self.classify(), rhs.classify()) {
-            (FpCategory::Nan, _) | (_, FpCategory::Nan) => Self::NAN,
-            (FpCategory::Infinite, FpCategory::Infinite) => Self::NEG_NAN,
-            (FpCategory::Zero, FpCategory::Zero) => {
-                Self::NEG_NAN
-            },
-            (FpCategory::Infinite, _) | (_, FpCategory::Zero) => if negative {
-                Self::NEG_INFINITY
-            } else {
-                Self::INFINITY
-            },
-            (FpCategory::Zero, _) | (_, FpCategory::Infinite) => if negative {
-                Self::NEG_ZERO
-            } else {
-                Self::ZERO
-            },
-            (_, _) => {
-                self.div_internal(rhs, negative)
-            },
+            #[inline]
+            fn mul(self, rhs: Self) -> Self {
+                let negative = self.is_sign_negative() != rhs.is_sign_negative();
+                match (self.classify(), rhs.classify()) {
+                    (FpCategory::Nan, _) | (_, FpCategory::Nan) => Self::NAN,
+                    (FpCategory::Infinite, FpCategory::Zero) | (FpCategory::Zero, FpCategory::Infinite) => Self::NAN,
+                    (FpCategory::Infinite, _) | (_, FpCategory::Infinite) => if negative {
+                        Self::NEG_INFINITY
+                    } else {
+                        Self::INFINITY
+                    },
+                    (FpCategory::Zero, _) | (_, FpCategory::Zero) => if negative {
+                        Self::NEG_ZERO
+                    } else {
+                        Self::ZERO
+                    },
+                    (_, _) => self.mul_internal(rhs, negative),
+                }
+            }
         }
-    }
-}
-
-impl<const W: usize, const MB: usize> Rem for Float<W, MB> {
-    type Output = Self;
 
-    #[inline]
-    fn rem(self, y: Self) -> Self {
-        handle_nan!(self; self);
-        handle_nan!(y; y);
+        crate::int::ops::op_ref_impl!(Mul<$Float<W, MB>> for $Float<W, MB>, mul);
 
-        if y.is_zero() {
-            return Self::NAN;
-        }
-        if self.is_zero() {
-            return self;
-        }
-        if self.is_infinite() {
-            return Self::NAN;
-        }
-        if y.is_infinite() {
-            return self;
+        impl<const W: usize, const MB: usize> Product for $Float<W, MB> {
+            #[inline]
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ONE, |a, b| a * b)
+            }
         }
 
-        let mut uxi = self.to_bits();
-        let mut uyi = y.to_bits();
-        let mut ex = self.exponent();
-        let mut ey = y.exponent();
-        let mut i;
-
-        if uxi << 1 as ExpType <= uyi << 1 as ExpType {
-            if uxi << 1 as ExpType == uyi << 1 as ExpType {
-                return if self.is_sign_negative() {
-                    Self::NEG_ZERO
-                } else {
-                    Self::ZERO
-                };
+        impl<'a, const W: usize, const MB: usize> Product<&'a Self> for $Float<W, MB> {
+            #[inline]
+            fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self::ONE, |a, b| a * *b)
             }
-
-            return self;
         }
 
-        /* normalize x and y */
-        if ex.is_zero() {
-            i = uxi << (Self::BITS - Self::MB);
-            while !Bint::from_bits(i).is_negative() {
-                ex -= Bint::ONE;
-                i <<= 1 as ExpType;
-            }
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            /// Returns the correctly rounded quotient of `self` and `rhs`, which must both be finite and nonzero.
+            #[inline]
+            fn div_internal(self, rhs: Self, negative: bool) -> Self {
+                let (_, a_exp, a_mant) = self.to_parts();
+                let (_, b_exp, b_mant) = rhs.to_parts();
+
+                // Normalise both significands to `MB + 1` bits, then make the quotient of the significands lie in `[1, 2)`.
+                let a_shift = Self::MB + 1 - a_mant.bits();
+                let b_shift = Self::MB + 1 - b_mant.bits();
+                let mut a_mant = a_mant << a_shift;
+                let b_mant = b_mant << b_shift;
+                let mut exp = (a_exp - Self::exp_from(a_shift)).saturating_sub(b_exp - Self::exp_from(b_shift));
+                if a_mant < b_mant {
+                    a_mant <<= 1u8;
+                    exp = exp.saturating_sub($BInt::ONE);
+                }
 
-            uxi <<= -ex + Bint::ONE;
-        } else {
-            uxi &= BUint::MAX >> (Self::BITS - Self::MB);
-            uxi |= BUint::ONE << Self::MB;
+                // Long division, producing `MB + 3` bits of the quotient. The remainder is always less than `b_mant`, so doubling it cannot overflow.
+                let mut quotient = $BUint::<W>::ZERO;
+                let mut rem = a_mant;
+                let mut i = 0;
+                while i < Self::MB + 3 {
+                    quotient <<= 1u8;
+                    if rem >= b_mant {
+                        rem -= b_mant;
+                        quotient |= $BUint::ONE;
+                    }
+                    rem <<= 1u8;
+                    i += 1;
+                }
+                let exp = exp.saturating_sub(Self::exp_from(Self::MB + 2));
+                Self::round_parts(negative, exp, quotient, !rem.is_zero(), RoundingMode::NearestTiesEven)
+            }
         }
-        //println!("{}", i);
 
-        if ey.is_zero() {
-            i = uyi << (Self::BITS - Self::MB);
-            while !Bint::from_bits(i).is_negative() {
-                ey -= Bint::ONE;
-                i <<= 1 as ExpType;
-            }
+        impl<const W: usize, const MB: usize> Div for $Float<W, MB> {
+            type Output = Self;
 
-            uyi <<= -ey + Bint::ONE;
-        } else {
-            uyi &= BUint::MAX >> (Self::BITS - Self::MB);
-            uyi |= BUint::ONE << Self::MB;
-        }
-        /* x mod y */
-        while ex > ey {
-            i = uxi.wrapping_sub(uyi);
-            if !Bint::from_bits(i).is_negative() {
-                if i.is_zero() {
-                    return if self.is_sign_negative() {
+            #[inline]
+            fn div(self, rhs: Self) -> Self {
+                let negative = self.is_sign_negative() != rhs.is_sign_negative();
+                match (self.classify(), rhs.classify()) {
+                    (FpCategory::Nan, _) | (_, FpCategory::Nan) => Self::NAN,
+                    (FpCategory::Infinite, FpCategory::Infinite) | (FpCategory::Zero, FpCategory::Zero) => Self::NAN,
+                    (FpCategory::Infinite, _) | (_, FpCategory::Zero) => if negative {
+                        Self::NEG_INFINITY
+                    } else {
+                        Self::INFINITY
+                    },
+                    (FpCategory::Zero, _) | (_, FpCategory::Infinite) => if negative {
                         Self::NEG_ZERO
                     } else {
                         Self::ZERO
-                    };
+                    },
+                    (_, _) => self.div_internal(rhs, negative),
                 }
-                uxi = i;
             }
-            uxi <<= 1 as ExpType;
-
-            ex -= Bint::ONE;
         }
 
-        i = uxi.wrapping_sub(uyi);
-        if !Bint::from_bits(i).is_negative() {
-            if i.is_zero() {
-                return if self.is_sign_negative() {
-                    Self::NEG_ZERO
-                } else {
-                    Self::ZERO
-                };
-            }
-            uxi = i;
-        }
+        crate::int::ops::op_ref_impl!(Div<$Float<W, MB>> for $Float<W, MB>, div);
 
-        while (uxi >> Self::MB).is_zero() {
-            uxi <<= 1 as ExpType;
-            ex -= Bint::ONE;
-        }
+        impl<const W: usize, const MB: usize> Rem for $Float<W, MB> {
+            type Output = Self;
 
-        /* scale result up */
-        if ex.is_positive() {
-            uxi -= BUint::ONE << Self::MB;
-            uxi |= (ex.to_bits()) << Self::MB;
-        } else {
-            uxi >>= -ex + Bint::ONE;
+            #[inline]
+            fn rem(self, y: Self) -> Self {
+                handle_nan!(self; self);
+                handle_nan!(y; y);
+                if y.is_zero() || self.is_infinite() {
+                    return Self::NAN;
+                }
+                if self.is_zero() || y.is_infinite() || self.abs().to_bits() < y.abs().to_bits() {
+                    return self;
+                }
+                let (negative, x_exp, x_mant) = self.to_parts();
+                let (_, y_exp, y_mant) = y.to_parts();
+
+                // The remainder of the truncated division is exact and has the same sign as `self`. `self = x_mant * 2^x_exp` and `y = y_mant * 2^y_exp` with `x_exp >= y_exp`, so the remainder is `((x_mant * 2^(x_exp - y_exp)) mod y_mant) * 2^y_exp`. The doubling cannot overflow, as the remainder is always less than `y_mant`.
+                let mut rem = x_mant % y_mant;
+                let mut exp_diff = x_exp - y_exp;
+                while exp_diff.is_positive() {
+                    rem <<= 1u8;
+                    if rem >= y_mant {
+                        rem -= y_mant;
+                    }
+                    exp_diff -= $BInt::ONE;
+                }
+                Self::round_parts(negative, y_exp, rem, false, RoundingMode::NearestTiesEven)
+            }
         }
 
-        let f = Self::from_bits(uxi);
-        if self.is_sign_negative() {
-            -f
-        } else {
-            f
-        }
-    }
-}
+        crate::int::ops::op_ref_impl!(Rem<$Float<W, MB>> for $Float<W, MB>, rem);
 
-impl<const W: usize, const MB: usize> const Neg for Float<W, MB> {
-    type Output = Self;
+        impl<const W: usize, const MB: usize> Neg for $Float<W, MB> {
+            type Output = Self;
 
-    #[inline]
-    fn neg(self) -> Self {
-        let mut words = *self.words();
-        words[W - 1] ^= 1 << (digit::BITS - 1);
-        Self::from_words(words)
-    }
-}
+            #[inline]
+            fn neg(self) -> Self {
+                self.negate()
+            }
+        }
 
-impl<const W: usize, const MB: usize> const Neg for &Float<W, MB> {
-    type Output = Float<W, MB>;
+        impl<const W: usize, const MB: usize> Neg for &$Float<W, MB> {
+            type Output = $Float<W, MB>;
 
-    #[inline]
-    fn neg(self) -> Float<W, MB> {
-        (*self).neg()
-    }
+            #[inline]
+            fn neg(self) -> $Float<W, MB> {
+                self.negate()
+            }
+        }
+    };
 }
 
+super::float_impl!(ops);
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::F64;
-	use crate::test::test_bignum;
+    use crate::{F32, F64};
+	use crate::test::{test_bignum, test_float};
 
-    trait ToBits {
-        fn to_bits(self) -> u64;
-    }
-    impl ToBits for F64 {
-        fn to_bits(self) -> u64 {
-            self.to_bits().as_()
-        }
-    }
-    impl ToBits for f64 {
-        fn to_bits(self) -> u64 {
-            self.to_bits()
-        }
-    }
-    
-    test_bignum! {
-        function: <f64 as Add>::add(a: f64, b: f64),
-        skip: a.is_sign_positive() || b.is_sign_positive()
+    test_float! {
+        function: <f64 as Add>::add(a: f64, b: f64)
     }
-    
-    test_bignum! {
-        function: <f64 as Sub>::sub(a: f64, b: f64),
-        skip: a.is_sign_negative() != b.is_sign_negative()
+
+    test_float! {
+        function: <f64 as Sub>::sub(a: f64, b: f64)
     }
-    
-    test_bignum! {
+
+    test_float! {
         function: <f64 as Mul>::mul(a: f64, b: f64)
     }
-    
-    test_bignum! {
+
+    test_float! {
         function: <f64 as Div>::div(a: f64, b: f64)
     }
-    
-    test_bignum! {
+
+    test_float! {
         function: <f64 as Rem>::rem(a: f64, b: f64)
     }
-    
+
 	test_bignum! {
         function: <f64 as Neg>::neg(f: f64)
     }
 
+    test_float! {
+        function: <f32 as Add>::add(a: f32, b: f32)
+    }
+
+    test_float! {
+        function: <f32 as Sub>::sub(a: f32, b: f32)
+    }
+
+    test_float! {
+        function: <f32 as Mul>::mul(a: f32, b: f32)
+    }
+
+    test_float! {
+        function: <f32 as Div>::div(a: f32, b: f32)
+    }
+
+    test_float! {
+        function: <f32 as Rem>::rem(a: f32, b: f32)
+    }
+
     #[test]
     fn sub() {
         let f1 = f64::from_bits(0b1100001111100000000000000000000000000000000000000000000000000000);
         let f2 = f64::from_bits(0b1111111110001111001000000100000100100001110101001010110010110011);
-        //println!("{:064b}", ((-0.0f64).div_euclid(f2)).to_bits());
-        let a = (crate::F64::from(f1) + (crate::F64::from(f2))).to_bits();
+        let a = (F64::from(f1) + (F64::from(f2))).to_bits();
         let b = (f1 + (f2)).to_bits();
-        /*println!("{:064b}", a);
-        println!("{:064b}", b);*/
         assert!(a == b.into());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn subnormal_results() {
+        let cases = [
+            (f64::MIN_POSITIVE, -f64::MIN_POSITIVE * (1.0 - f64::EPSILON)),
+            (f64::MIN_POSITIVE, 0.75),
+            (3.0 * f64::MIN_POSITIVE, 1.0 / 3.0),
+            (f64::from_bits(3), 0.5),
+            (f64::from_bits(1), -0.5),
+        ];
+        for (a, b) in cases {
+            assert_eq!((F64::from(a) + F64::from(b)).to_bits(), (a + b).to_bits().into());
+            assert_eq!((F64::from(a) * F64::from(b)).to_bits(), (a * b).to_bits().into());
+            assert_eq!((F64::from(a) / F64::from(b)).to_bits(), (a / b).to_bits().into());
+        }
+    }
+
+    #[test]
+    fn sum_product() {
+        let values = [F64::from(0.5f64), F64::from(-3.0f64), F64::from(10.0f64)];
+        assert_eq!(values.iter().sum::<F64>().to_bits(), 7.5f64.to_bits().into());
+        assert_eq!(values.into_iter().sum::<F64>().to_bits(), 7.5f64.to_bits().into());
+        assert_eq!(values.iter().product::<F64>().to_bits(), (-15.0f64).to_bits().into());
+    }
+
+    fn arithmetic<T>(a: T, b: T) -> [T; 5]
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Rem<Output = T>,
+    {
+        [a + b, a - b, a * b, a / b, a % b]
+    }
+
+    macro_rules! test_digit_family {
+        ($name: ident, $Float: ident, $W: literal) => {
+            quickcheck::quickcheck! {
+                fn $name(a: f64, b: f64) -> bool {
+                    let from = |f: f64| super::$Float::<$W, 52>::from_bits(f.to_bits().as_());
+                    let results = arithmetic(from(a), from(b)).map(|f| f.to_bits().as_::<u64>());
+                    let expected = arithmetic(F64::from(a), F64::from(b)).map(|f| f.to_bits().as_::<u64>());
+                    results == expected
+                }
+            }
+        };
+    }
+
+    test_digit_family!(quickcheck_d32_arithmetic, FloatD32, 2);
+    test_digit_family!(quickcheck_d16_arithmetic, FloatD16, 4);
+    test_digit_family!(quickcheck_d8_arithmetic, FloatD8, 8);
+}
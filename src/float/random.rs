@@ -0,0 +1,115 @@
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use crate::bigints::*;
+use crate::cast::As;
+use crate::ExpType;
+use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformSampler};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+/// Used for generating random floats in a given range.
+///
+/// Implements the [`UniformSampler`](https://docs.rs/rand/latest/rand/distributions/uniform/trait.UniformSampler.html) trait from the [`rand`](https://docs.rs/rand/latest/rand/) crate. This struct should not be used directly; instead use the [`Uniform`](https://docs.rs/rand/latest/rand/distributions/struct.Uniform.html) struct from the [`rand`](https://docs.rs/rand/latest/rand/) crate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniformFloat<X> {
+    low: X,
+    scale: X,
+}
+
+macro_rules! random {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const W: usize, const MB: usize> Distribution<$Float<W, MB>> for Standard {
+            /// Generates a float uniformly distributed over the half-open interval `[0, 1)`, with `MB + 1` bits of precision.
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $Float<W, MB> {
+                let bits: $BUint<W> = rng.gen();
+                let precision = MB as ExpType + 1;
+                let sig = bits >> ($BUint::<W>::BITS - precision);
+                $Float::from_parts_rounded(false, -precision.as_::<$BInt<W>>(), sig)
+            }
+        }
+
+        impl<const W: usize, const MB: usize> SampleUniform for $Float<W, MB> {
+            type Sampler = UniformFloat<$Float<W, MB>>;
+        }
+
+        impl<const W: usize, const MB: usize> UniformSampler for UniformFloat<$Float<W, MB>> {
+            type X = $Float<W, MB>;
+
+            fn new<B1, B2>(low_b: B1, high_b: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let low = *low_b.borrow();
+                let high = *high_b.borrow();
+                assert!(low.is_finite() && high.is_finite(), "Uniform::new called with non-finite boundaries");
+                assert!(low < high, "Uniform::new called with `low >= high`");
+                let mut scale = high - low;
+                assert!(scale.is_finite(), "Uniform::new: range overflow");
+
+                // The largest value generated by `Standard` is `1 - 2^-(MB + 1)`, and the rounding of `low + scale * value` must not produce `high`.
+                let max_rand = Self::X::ONE.next_down();
+                while low + scale * max_rand >= high {
+                    scale = scale.next_down();
+                }
+                UniformFloat { low, scale }
+            }
+
+            fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Self
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                let low = *low_b.borrow();
+                let high = *high_b.borrow();
+                assert!(low.is_finite() && high.is_finite(), "Uniform::new_inclusive called with non-finite boundaries");
+                assert!(low <= high, "Uniform::new_inclusive called with `low > high`");
+                let max_rand = Self::X::ONE.next_down();
+                let mut scale = (high - low) / max_rand;
+                assert!(scale.is_finite(), "Uniform::new_inclusive: range overflow");
+
+                while low + scale * max_rand > high {
+                    scale = scale.next_down();
+                }
+                UniformFloat { low, scale }
+            }
+
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                let value: Self::X = rng.gen();
+                self.low + self.scale * value
+            }
+        }
+    };
+}
+
+super::float_impl!(random);
+
+#[cfg(test)]
+mod tests {
+    use crate::F64;
+    use rand::distributions::Uniform;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn standard() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let f: F64 = rng.gen();
+            assert!(f >= F64::ZERO && f < F64::ONE);
+        }
+    }
+
+    #[test]
+    fn uniform() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let (low, high) = (F64::from(-2.5f64), F64::from(10.0f64));
+        for _ in 0..1000 {
+            let f = rng.gen_range(low..high);
+            assert!(f >= low && f < high);
+            let f = rng.sample(Uniform::new_inclusive(low, low.next_up()));
+            assert!(f >= low && f <= low.next_up());
+        }
+    }
+}
@@ -0,0 +1,631 @@
+use super::{Float, FloatD32, FloatD16, FloatD8};
+use crate::cast::As;
+
+macro_rules! transcendental {
+    ($Float: ident, $BUint: ident, $BInt: ident, $Digit: ident) => {
+        /// Elementary functions.
+        ///
+        /// These are computed in the precision of the format itself, so unlike the arithmetic operations they are not correctly rounded, but are accurate to within a few ULPs for arguments of moderate magnitude. Argument reduction for `exp` and the trigonometric functions loses precision once the reduced multiple of ln(2) or π/2 has more than half as many bits as the significand.
+        impl<const W: usize, const MB: usize> $Float<W, MB> {
+            /// Returns `e^x - 1` for `x` of magnitude at most one half.
+            fn exp_m1_reduced(x: Self) -> Self {
+                // Halve the argument so the Taylor series converges quickly, then undo the halvings with `e^2x - 1 = (e^x - 1)(e^x - 1 + 2)`, which doesn't lose precision to cancellation.
+                const HALVINGS: i32 = 8;
+                let x = x.ldexp(-HALVINGS);
+                let mut term = x;
+                let mut sum = x;
+                let mut n = 2u32;
+                loop {
+                    term = term * x / n.as_::<Self>();
+                    let next = sum + term;
+                    if next == sum {
+                        break;
+                    }
+                    sum = next;
+                    n += 1;
+                }
+                for _ in 0..HALVINGS {
+                    sum = sum * (sum + Self::TWO);
+                }
+                sum
+            }
+
+            /// Returns `atanh(x) = x + x^3 / 3 + x^5 / 5 + ...` for `x` of magnitude much less than one.
+            fn atanh_series(x: Self) -> Self {
+                let x2 = x * x;
+                let mut power = x;
+                let mut sum = x;
+                let mut n = 3u32;
+                loop {
+                    power = power * x2;
+                    let next = sum + power / n.as_::<Self>();
+                    if next == sum {
+                        return sum;
+                    }
+                    sum = next;
+                    n += 2;
+                }
+            }
+
+            /// Returns `(sin(x), cos(x))` for `x` in `[-π/4, π/4]`.
+            fn sin_cos_reduced(x: Self) -> (Self, Self) {
+                let x2 = x * x;
+                let (mut sin, mut cos) = (x, Self::ONE);
+                let (mut sin_term, mut cos_term) = (x, Self::ONE);
+                let mut n = 1u32;
+                loop {
+                    sin_term = -sin_term * x2 / ((2 * n) * (2 * n + 1)).as_::<Self>();
+                    cos_term = -cos_term * x2 / ((2 * n - 1) * (2 * n)).as_::<Self>();
+                    let (s, c) = (sin + sin_term, cos + cos_term);
+                    if s == sin && c == cos {
+                        return (sin, cos);
+                    }
+                    sin = s;
+                    cos = c;
+                    n += 1;
+                }
+            }
+
+            /// Returns `r` in approximately `[-π/4, π/4]` and `q` in `0..4` such that `self = r + (4k + q) * π/2` for some integer `k`.
+            fn rem_pio2(self) -> (Self, u8) {
+                if self.abs() <= Self::FRAC_PI_4 {
+                    return (self, 0);
+                }
+                let q = (self * Self::FRAC_2_PI).round();
+                let (hi, lo) = Self::FRAC_PI_2_SPLIT;
+                let r = (self - q * hi) - q * lo;
+                let quadrant = q - (q * Self::QUARTER).floor() * 4u8.as_::<Self>();
+                (r, quadrant.as_())
+            }
+
+            /// The value above which `x^2` no longer affects `x` in expressions like `x + sqrt(x^2 + 1)`.
+            fn large_threshold() -> Self {
+                Self::ONE.ldexp(MB as i32 / 2 + 1)
+            }
+
+            /// Returns `(s, e)` such that `s = a + b` rounded and `s + e = a + b` exactly.
+            fn two_sum(a: Self, b: Self) -> (Self, Self) {
+                let s = a + b;
+                let b_virtual = s - a;
+                let a_virtual = s - b_virtual;
+                (s, (a - a_virtual) + (b - b_virtual))
+            }
+
+            /// Splits `a` into two halves, each with at most half as many significant bits, whose sum is `a`.
+            fn split(a: Self) -> (Self, Self) {
+                let c = (Self::ONE.ldexp(MB as i32 / 2 + 1) + Self::ONE) * a;
+                let hi = c - (c - a);
+                (hi, a - hi)
+            }
+
+            /// Returns `(p, e)` such that `p = a * b` rounded and `p + e = a * b` exactly, as long as no intermediate value overflows or underflows.
+            fn two_prod(a: Self, b: Self) -> (Self, Self) {
+                let p = a * b;
+                let (a_hi, a_lo) = Self::split(a);
+                let (b_hi, b_lo) = Self::split(b);
+                (p, ((a_hi * b_hi - p) + a_hi * b_lo + a_lo * b_hi) + a_lo * b_lo)
+            }
+
+            /// Adds two unevaluated sums `hi + lo`, giving roughly twice the precision of the format.
+            fn add_extended(a: (Self, Self), b: (Self, Self)) -> (Self, Self) {
+                let (s, e) = Self::two_sum(a.0, b.0);
+                Self::two_sum(s, e + (a.1 + b.1))
+            }
+
+            /// Multiplies two unevaluated sums `hi + lo`, giving roughly twice the precision of the format.
+            fn mul_extended(a: (Self, Self), b: (Self, Self)) -> (Self, Self) {
+                let (p, e) = Self::two_prod(a.0, b.0);
+                Self::two_sum(p, e + (a.0 * b.1 + a.1 * b.0))
+            }
+
+            /// Divides two unevaluated sums `hi + lo`, giving roughly twice the precision of the format.
+            fn div_extended(a: (Self, Self), b: (Self, Self)) -> (Self, Self) {
+                let q = a.0 / b.0;
+                let r = Self::add_extended(a, Self::mul_extended(b, (-q, Self::ZERO)));
+                Self::two_sum(q, r.0 / b.0)
+            }
+
+            /// Returns `ln(self)` as an unevaluated sum `hi + lo` with roughly twice the precision of the format. `self` must be positive and finite.
+            fn ln_extended(self) -> (Self, Self) {
+                let (m, e) = self.reduce_log();
+                // `m - 1` is exact, as `m` is in `[sqrt(2)/2, sqrt(2))`.
+                let t = Self::div_extended((m - Self::ONE, Self::ZERO), Self::two_sum(m, Self::ONE));
+                let t2 = Self::mul_extended(t, t);
+                let mut power = t;
+                let mut sum = t;
+                let mut n = 3u32;
+                loop {
+                    power = Self::mul_extended(power, t2);
+                    let next = Self::add_extended(sum, Self::div_extended(power, (n.as_(), Self::ZERO)));
+                    if next == sum {
+                        break;
+                    }
+                    sum = next;
+                    n += 2;
+                }
+                let ln_m = (sum.0.ldexp(1), sum.1.ldexp(1));
+                Self::add_extended(Self::mul_extended((e.as_(), Self::ZERO), Self::LN_2_SPLIT), ln_m)
+            }
+
+            /// Returns `e^self`.
+            pub fn exp(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_infinite() {
+                    return if self.is_sign_negative() { Self::ZERO } else { Self::INFINITY };
+                }
+                let k = (self * Self::LOG2_E).round();
+                let n: i32 = k.as_();
+                if n.unsigned_abs() > 1 << 30 {
+                    // `ldexp` can't scale by this much, and the result overflows or underflows in any realistic format.
+                    return if n.is_negative() { Self::ZERO } else { Self::INFINITY };
+                }
+                let (hi, lo) = Self::LN_2_SPLIT;
+                let r = (self - k * hi) - k * lo;
+                (Self::exp_m1_reduced(r) + Self::ONE).ldexp(n)
+            }
+
+            /// Returns `2^self`.
+            pub fn exp2(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_infinite() {
+                    return if self.is_sign_negative() { Self::ZERO } else { Self::INFINITY };
+                }
+                let k = self.round();
+                let n: i32 = k.as_();
+                if n.unsigned_abs() > 1 << 30 {
+                    return if n.is_negative() { Self::ZERO } else { Self::INFINITY };
+                }
+                // `self - k` is exact, so the only rounding errors are in the result of `exp`.
+                (Self::exp_m1_reduced((self - k) * Self::LN_2) + Self::ONE).ldexp(n)
+            }
+
+            /// Returns `e^self - 1`, accurately even if `self` is close to zero.
+            pub fn exp_m1(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_infinite() {
+                    return if self.is_sign_negative() { Self::NEG_ONE } else { Self::INFINITY };
+                }
+                if self.abs() <= Self::HALF {
+                    return Self::exp_m1_reduced(self);
+                }
+                self.exp() - Self::ONE
+            }
+
+            /// Returns the natural logarithm of `self`.
+            pub fn ln(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_zero() {
+                    return Self::NEG_INFINITY;
+                }
+                if self.is_sign_negative() {
+                    return Self::NAN;
+                }
+                if self.is_infinite() {
+                    return self;
+                }
+                let (m, e) = self.reduce_log();
+                let ln_m = Self::atanh_series((m - Self::ONE) / (m + Self::ONE)).ldexp(1);
+                if e == 0 {
+                    return ln_m;
+                }
+                let e: Self = e.as_();
+                let (hi, lo) = Self::LN_2_SPLIT;
+                e * hi + (e * lo + ln_m)
+            }
+
+            /// Returns `m` in `[sqrt(2)/2, sqrt(2))` and `e` such that `self = m * 2^e`. `self` must be positive and finite.
+            fn reduce_log(self) -> (Self, i32) {
+                let (m, e) = self.frexp();
+                if m < Self::FRAC_1_SQRT_2 {
+                    (m.ldexp(1), e - 1)
+                } else {
+                    (m, e)
+                }
+            }
+
+            /// Returns `ln(1 + self)`, accurately even if `self` is close to zero.
+            pub fn ln_1p(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_infinite() {
+                    return if self.is_sign_negative() { Self::NAN } else { self };
+                }
+                let u = Self::ONE + self;
+                if u == Self::ONE {
+                    return self;
+                }
+                // The rounding error in `u` is compensated for by dividing by `u - 1` rather than `self`.
+                u.ln() * (self / (u - Self::ONE))
+            }
+
+            /// Returns the logarithm of `self` with respect to an arbitrary base.
+            pub fn log(self, base: Self) -> Self {
+                self.ln() / base.ln()
+            }
+
+            /// Returns the base 2 logarithm of `self`. The result is exact for powers of two.
+            pub fn log2(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_zero() || self.is_sign_negative() || self.is_infinite() {
+                    return self.ln();
+                }
+                let (m, e) = self.reduce_log();
+                e.as_::<Self>() + m.ln() * Self::LOG2_E
+            }
+
+            /// Returns the base 10 logarithm of `self`.
+            pub fn log10(self) -> Self {
+                self.ln() * Self::LOG10_E
+            }
+
+            /// Raises `self` to an integer power, by repeated squaring.
+            ///
+            /// Each multiplication is rounded, so the error grows with the number of bits of `n`. [`powf`](Self::powf) is more accurate for large `n`.
+            pub fn powi(self, n: i32) -> Self {
+                let mut base = self;
+                let mut e = n.unsigned_abs();
+                let mut acc = Self::ONE;
+                while e != 0 {
+                    if e & 1 == 1 {
+                        acc = acc * base;
+                    }
+                    e >>= 1;
+                    if e != 0 {
+                        base = base * base;
+                    }
+                }
+                if n.is_negative() {
+                    acc.recip()
+                } else {
+                    acc
+                }
+            }
+
+            /// Raises `self` to a floating point power.
+            ///
+            /// Special values are handled as described for `pow` in the IEEE 754 standard. Integer powers of magnitude at most 64 are computed by [`powi`](Self::powi); otherwise the result is computed as `e^(n * ln(self))`, where `n * ln(self)` is computed with roughly twice the precision of the format, so that the result is accurate to within a few ULPs even for large `n`.
+            pub fn powf(self, n: Self) -> Self {
+                if n.is_zero() || self == Self::ONE {
+                    return Self::ONE;
+                }
+                handle_nan!(Self::NAN; self, n);
+                let abs = self.abs();
+                if n.is_infinite() {
+                    return if abs == Self::ONE {
+                        Self::ONE
+                    } else if (abs > Self::ONE) == n.is_sign_positive() {
+                        Self::INFINITY
+                    } else {
+                        Self::ZERO
+                    };
+                }
+                let is_integer = n.trunc() == n;
+                let is_odd = is_integer && (n * Self::HALF).trunc() != n * Self::HALF;
+                if self.is_sign_negative() && self.is_finite() && !self.is_zero() && !is_integer {
+                    return Self::NAN;
+                }
+                let pow = if abs.is_zero() || abs.is_infinite() {
+                    if abs.is_zero() == n.is_sign_positive() {
+                        Self::ZERO
+                    } else {
+                        Self::INFINITY
+                    }
+                } else if is_integer && n.abs() <= 64u8.as_::<Self>() {
+                    abs.powi(n.as_())
+                } else {
+                    let y = n * abs.ln();
+                    let pow = y.exp();
+                    if !pow.is_normal() {
+                        // The result overflows or underflows, so the extra precision isn't needed, and could overflow an intermediate value.
+                        pow
+                    } else {
+                        let (y_hi, y_lo) = Self::mul_extended((n, Self::ZERO), abs.ln_extended());
+                        // `e^(hi + lo) = e^hi * e^lo`, and `e^lo` is `1 + lo` to within the precision of the format, as `lo` is tiny.
+                        let pow = y_hi.exp();
+                        pow + pow * y_lo
+                    }
+                };
+                if self.is_sign_negative() && is_odd {
+                    -pow
+                } else {
+                    pow
+                }
+            }
+
+            /// Returns the cube root of `self`.
+            pub fn cbrt(self) -> Self {
+                if !self.is_finite() || self.is_zero() {
+                    return self;
+                }
+                let x = self.abs();
+                let three = 3u8.as_::<Self>();
+                let r = (x.ln() / three).exp();
+                // One step of Newton's method corrects the error of `exp` and `ln`.
+                let r = r - (r - x / (r * r)) / three;
+                r.copysign(self)
+            }
+
+            /// Returns `sqrt(self^2 + other^2)`, without intermediate overflow or underflow.
+            pub fn hypot(self, other: Self) -> Self {
+                if self.is_infinite() || other.is_infinite() {
+                    return Self::INFINITY;
+                }
+                handle_nan!(Self::NAN; self, other);
+                let (a, b) = (self.abs(), other.abs());
+                let (a, b) = if a < b { (b, a) } else { (a, b) };
+                if a.is_zero() {
+                    return Self::ZERO;
+                }
+                let r = b / a;
+                a * (Self::ONE + r * r).sqrt()
+            }
+
+            /// Returns `(self * a) + b`.
+            ///
+            /// This is currently not a fused operation: the product is rounded before the addition.
+            pub fn mul_add(self, a: Self, b: Self) -> Self {
+                self * a + b
+            }
+
+            /// Returns the sine and cosine of `self` (in radians).
+            pub fn sin_cos(self) -> (Self, Self) {
+                if !self.is_finite() {
+                    return (Self::NAN, Self::NAN);
+                }
+                let (r, q) = self.rem_pio2();
+                let (s, c) = Self::sin_cos_reduced(r);
+                match q {
+                    0 => (s, c),
+                    1 => (c, -s),
+                    2 => (-s, -c),
+                    _ => (-c, s),
+                }
+            }
+
+            /// Returns the sine of `self` (in radians).
+            pub fn sin(self) -> Self {
+                self.sin_cos().0
+            }
+
+            /// Returns the cosine of `self` (in radians).
+            pub fn cos(self) -> Self {
+                self.sin_cos().1
+            }
+
+            /// Returns the tangent of `self` (in radians).
+            pub fn tan(self) -> Self {
+                let (s, c) = self.sin_cos();
+                s / c
+            }
+
+            /// Returns the arctangent of `self`, in the range `[-π/2, π/2]`.
+            pub fn atan(self) -> Self {
+                handle_nan!(self; self);
+                if self.is_infinite() {
+                    return Self::FRAC_PI_2.copysign(self);
+                }
+                let x = self.abs();
+                let reciprocal = x > Self::ONE;
+                // `atan(x) = π/2 - atan(1/x)`, then halve the angle with `atan(x) = 2 atan(x / (1 + sqrt(1 + x^2)))` so the series converges quickly.
+                const HALVINGS: i32 = 3;
+                let mut y = if reciprocal { x.recip() } else { x };
+                for _ in 0..HALVINGS {
+                    y = y / (Self::ONE + (Self::ONE + y * y).sqrt());
+                }
+                let y2 = y * y;
+                let mut power = y;
+                let mut sum = y;
+                let mut n = 3u32;
+                loop {
+                    power = -power * y2;
+                    let next = sum + power / n.as_::<Self>();
+                    if next == sum {
+                        break;
+                    }
+                    sum = next;
+                    n += 2;
+                }
+                let mut r = sum.ldexp(HALVINGS);
+                if reciprocal {
+                    r = Self::FRAC_PI_2 - r;
+                }
+                r.copysign(self)
+            }
+
+            /// Returns the four quadrant arctangent of `self` (`y`) and `other` (`x`), in the range `[-π, π]`.
+            pub fn atan2(self, other: Self) -> Self {
+                handle_nan!(Self::NAN; self, other);
+                let (y, x) = (self, other);
+                if y.is_zero() {
+                    return if x.is_sign_positive() { y } else { Self::PI.copysign(y) };
+                }
+                if x.is_zero() {
+                    return Self::FRAC_PI_2.copysign(y);
+                }
+                if x.is_infinite() {
+                    let angle = if y.is_infinite() {
+                        if x.is_sign_positive() {
+                            Self::FRAC_PI_4
+                        } else {
+                            Self::FRAC_PI_4 * 3u8.as_::<Self>()
+                        }
+                    } else if x.is_sign_positive() {
+                        Self::ZERO
+                    } else {
+                        Self::PI
+                    };
+                    return angle.copysign(y);
+                }
+                if y.is_infinite() {
+                    return Self::FRAC_PI_2.copysign(y);
+                }
+                let a = (y / x).abs().atan();
+                if x.is_sign_positive() {
+                    a.copysign(y)
+                } else {
+                    (Self::PI - a).copysign(y)
+                }
+            }
+
+            /// Returns the arcsine of `self`, in the range `[-π/2, π/2]`, or NaN if `self` is outside `[-1, 1]`.
+            pub fn asin(self) -> Self {
+                if self.abs() > Self::ONE {
+                    return Self::NAN;
+                }
+                self.atan2(((Self::ONE - self) * (Self::ONE + self)).sqrt())
+            }
+
+            /// Returns the arccosine of `self`, in the range `[0, π]`, or NaN if `self` is outside `[-1, 1]`.
+            pub fn acos(self) -> Self {
+                if self.abs() > Self::ONE {
+                    return Self::NAN;
+                }
+                ((Self::ONE - self) * (Self::ONE + self)).sqrt().atan2(self)
+            }
+
+            /// Returns the hyperbolic sine of `self`.
+            pub fn sinh(self) -> Self {
+                handle_nan!(self; self);
+                let a = self.abs();
+                let e = a.exp_m1();
+                let r = if e.is_infinite() {
+                    // `e^a` overflows, but `e^a / 2` may not.
+                    (a - Self::LN_2).exp()
+                } else {
+                    (e + e / (e + Self::ONE)) * Self::HALF
+                };
+                r.copysign(self)
+            }
+
+            /// Returns the hyperbolic cosine of `self`.
+            pub fn cosh(self) -> Self {
+                handle_nan!(self; self);
+                let a = self.abs();
+                let t = a.exp();
+                if t.is_infinite() {
+                    (a - Self::LN_2).exp()
+                } else {
+                    (t + t.recip()) * Self::HALF
+                }
+            }
+
+            /// Returns the hyperbolic tangent of `self`.
+            pub fn tanh(self) -> Self {
+                handle_nan!(self; self);
+                let e = self.abs().ldexp(1).exp_m1();
+                let r = if e.is_infinite() {
+                    Self::ONE
+                } else {
+                    e / (e + Self::TWO)
+                };
+                r.copysign(self)
+            }
+
+            /// Returns the inverse hyperbolic sine of `self`.
+            pub fn asinh(self) -> Self {
+                handle_nan!(self; self);
+                let a = self.abs();
+                let r = if a > Self::large_threshold() {
+                    a.ln() + Self::LN_2
+                } else {
+                    let a2 = a * a;
+                    (a + a2 / (Self::ONE + (Self::ONE + a2).sqrt())).ln_1p()
+                };
+                r.copysign(self)
+            }
+
+            /// Returns the inverse hyperbolic cosine of `self`, or NaN if `self` is less than one.
+            pub fn acosh(self) -> Self {
+                handle_nan!(self; self);
+                if self < Self::ONE {
+                    return Self::NAN;
+                }
+                if self > Self::large_threshold() {
+                    return self.ln() + Self::LN_2;
+                }
+                let t = self - Self::ONE;
+                (t + (t.ldexp(1) + t * t).sqrt()).ln_1p()
+            }
+
+            /// Returns the inverse hyperbolic tangent of `self`, or NaN if `self` is outside `[-1, 1]`.
+            pub fn atanh(self) -> Self {
+                handle_nan!(self; self);
+                let a = self.abs();
+                if a > Self::ONE {
+                    return Self::NAN;
+                }
+                if a == Self::ONE {
+                    return Self::INFINITY.copysign(self);
+                }
+                ((a.ldexp(1) / (Self::ONE - a)).ln_1p() * Self::HALF).copysign(self)
+            }
+        }
+    };
+}
+
+super::float_impl!(transcendental);
+
+#[cfg(test)]
+mod tests {
+    use crate::F64;
+    use crate::cast::As;
+
+    /// Asserts that the result is within `ulps` ULPs of the primitive result.
+    fn assert_close(big: F64, primitive: f64, ulps: u64) {
+        let big = f64::from_bits(big.to_bits().as_());
+        if primitive.is_nan() {
+            assert!(big.is_nan(), "expected NaN, got {}", big);
+            return;
+        }
+        let diff = (big.to_bits() as i64).wrapping_sub(primitive.to_bits() as i64).unsigned_abs();
+        assert!(diff <= ulps, "{} differs from {} by {} ULPs", big, primitive, diff);
+    }
+
+    macro_rules! test_close {
+        ($($method: ident), *; $($f: expr), *) => {
+            let values = [$($f), *];
+            $(
+                for f in values {
+                    assert_close(F64::from(f).$method(), f.$method(), 2);
+                }
+            )*
+        };
+    }
+
+    #[test]
+    fn exp_ln() {
+        test_close!(exp, exp2, exp_m1, ln, ln_1p, log2, log10; 0.0f64, 1.0, -1.0, 0.5, 1e-10, -1e-10, 2.0, 10.0, 123.456, -700.0, 700.0, 1e-300, 1e300, f64::INFINITY, -f64::INFINITY);
+
+        assert_eq!(F64::from(1024.0f64).log2().to_bits(), 10.0f64.to_bits().into());
+        assert!(F64::from(-1.0f64).ln().is_nan());
+        assert_eq!(F64::ZERO.ln().to_bits(), f64::NEG_INFINITY.to_bits().into());
+        assert_eq!(F64::from(710.0f64).exp().to_bits(), f64::INFINITY.to_bits().into());
+        assert_eq!(F64::from(-746.0f64).exp().to_bits(), 0u64.into());
+    }
+
+    #[test]
+    fn trigonometric() {
+        test_close!(sin, cos, tan, atan, sinh, cosh, tanh, asinh, atanh; 0.0f64, -0.0, 0.1, -0.5, 1.0, 0.75, 3.0, -10.0, 100.0);
+        test_close!(asin, acos, acosh; 0.0f64, 0.5, -0.5, 1.0, -1.0, 2.0, 1e10);
+
+        for (y, x) in [(1.0f64, 1.0f64), (-1.0, -1.0), (0.0, -1.0), (-0.0, -1.0), (1.0, 0.0), (f64::INFINITY, -f64::INFINITY), (3.0, -4.0)] {
+            assert_close(F64::from(y).atan2(F64::from(x)), y.atan2(x), 1);
+        }
+    }
+
+    #[test]
+    fn powers() {
+        for (x, n) in [(2.0f64, 10.0f64), (2.0, -3.0), (-2.0, 3.0), (-2.0, 0.5), (0.5, 1.5), (10.0, 0.1), (0.0, -1.0), (-0.0, -3.0), (1.0, f64::NAN), (f64::NAN, 0.0), (-1.0, f64::INFINITY)] {
+            assert_close(F64::from(x).powf(F64::from(n)), x.powf(n), 2);
+        }
+        // Large powers, for which rounding errors in repeated squaring would compound.
+        for (x, n) in [(1.0000001f64, 1e9f64), (0.999999999, -5e8), (3.0, 600.0), (-1.5, 1001.0), (1.5, 1000.5), (0.9, 6000.0), (1.0 + f64::EPSILON, 2f64.powi(60)), (7.0, -300.0)] {
+            assert_close(F64::from(x).powf(F64::from(n)), x.powf(n), 2);
+        }
+        assert_eq!(F64::from(3.0f64).powi(5).to_bits(), 243.0f64.to_bits().into());
+        assert_eq!(F64::from(2.0f64).powi(-2).to_bits(), 0.25f64.to_bits().into());
+
+        test_close!(cbrt; 27.0f64, -8.0, 2.0, 1e-300, 1e300);
+        assert_close(F64::from(3.0f64).hypot(F64::from(4.0f64)), 5.0, 0);
+        assert_close(F64::from(1e300f64).hypot(F64::from(1e300f64)), 1e300f64.hypot(1e300), 1);
+    }
+}
@@ -0,0 +1,169 @@
+//! Adapters for customising how integers are formatted.
+
+use core::fmt::{self, Formatter, Write};
+
+/// Describes how the digits of a formatted integer are split into groups, such as thousands separated by commas.
+///
+/// Groups are counted from the least significant digit. The first group has its own size, so that for example the Indian numbering system, which writes one hundred thousand as `1,00,000`, can be represented.
+///
+/// # Examples
+///
+/// ```
+/// use bnum::fmt::Grouping;
+/// use bnum::types::U256;
+///
+/// let n = U256::from(123456789u32);
+/// assert_eq!(format!("{}", n.grouped_with(Grouping::new(3, '_'))), "123_456_789");
+/// assert_eq!(format!("{}", n.grouped_with(Grouping::indian(','))), "12,34,56,789");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Grouping {
+    separator: char,
+    first: usize,
+    size: usize,
+}
+
+impl Grouping {
+    /// Creates a grouping where every group has `size` digits, and groups are separated by `separator`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `size` is zero.
+    pub const fn new(size: usize, separator: char) -> Self {
+        if size == 0 {
+            panic!(crate::errors::err_msg!("group size must be nonzero"));
+        }
+        Self {
+            separator,
+            first: size,
+            size,
+        }
+    }
+
+    /// Creates a grouping which follows the Indian numbering system: the three least significant digits form the first group, and the other digits are in groups of two.
+    pub const fn indian(separator: char) -> Self {
+        Self::new(2, separator).with_first_group(3)
+    }
+
+    /// Returns a copy of the grouping where the group of the least significant digits has `first` digits.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `first` is zero.
+    pub const fn with_first_group(mut self, first: usize) -> Self {
+        if first == 0 {
+            panic!(crate::errors::err_msg!("group size must be nonzero"));
+        }
+        self.first = first;
+        self
+    }
+
+    /// Returns the separator written between groups.
+    #[inline]
+    pub const fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Returns the number of characters that `digits` digits take up once they are grouped.
+    pub(crate) const fn grouped_len(&self, digits: usize) -> usize {
+        if digits > self.first {
+            digits + 1 + (digits - self.first - 1) / self.size
+        } else {
+            digits
+        }
+    }
+
+    /// Returns the number of digits which should be written before the next separator, given the number of digits which are left to write.
+    const fn digits_until_separator(&self, remaining: usize) -> usize {
+        if remaining <= self.first {
+            remaining
+        } else {
+            match (remaining - self.first) % self.size {
+                0 => self.size,
+                r => r,
+            }
+        }
+    }
+}
+
+/// A wrapper around an integer which formats it with its digits grouped.
+///
+/// This is created by methods such as [`BUint::grouped`](crate::BUint::grouped) and [`BUint::grouped_with`](crate::BUint::grouped_with). It implements the [`Display`](core::fmt::Display), [`Binary`](core::fmt::Binary), [`Octal`](core::fmt::Octal), [`LowerHex`](core::fmt::LowerHex) and [`UpperHex`](core::fmt::UpperHex) traits, which format the integer in the same way as the integer itself does, except that separators are inserted between groups of digits. The width and fill of the formatter apply to the grouped output.
+///
+/// When the formatter pads with zeros (the `0` flag), the padding zeros are grouped like the other digits. If the width would otherwise start the output with a separator, an extra zero is written in front of it, so the output is one character wider than the width.
+///
+/// # Examples
+///
+/// ```
+/// use bnum::types::U256;
+///
+/// let n = U256::from(1234567u32);
+/// assert_eq!(format!("{:>12}", n.grouped(3, ',')), "   1,234,567");
+/// assert_eq!(format!("{:012}", n.grouped(3, ',')), "0,001,234,567");
+/// assert_eq!(format!("{:011}", n.grouped(3, ',')), "001,234,567");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Grouped<T> {
+    pub(crate) value: T,
+    pub(crate) grouping: Grouping,
+}
+
+/// Writes digits to a formatter, inserting separators according to a grouping.
+pub(crate) struct GroupWriter<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    remaining: usize,
+    grouping: Grouping,
+}
+
+impl<'a, 'b> GroupWriter<'a, 'b> {
+    /// Creates a writer which will be written exactly `digits` ASCII digits.
+    pub(crate) fn new(f: &'a mut Formatter<'b>, digits: usize, grouping: Grouping) -> Self {
+        Self {
+            f,
+            remaining: digits,
+            grouping,
+        }
+    }
+}
+
+impl Write for GroupWriter<'_, '_> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        while !s.is_empty() {
+            let until = self.grouping.digits_until_separator(self.remaining);
+            let n = until.min(s.len());
+            self.f.write_str(&s[..n])?;
+            self.remaining -= n;
+            s = &s[n..];
+            if n == until && self.remaining != 0 {
+                self.f.write_char(self.grouping.separator)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A wrapper around an unsigned integer which formats it in hexadecimal with every digit, including leading zeros.
+///
+/// This is created by the `to_hex_fixed` method of the integer types, e.g. [`BUint::to_hex_fixed`](crate::BUint::to_hex_fixed). It implements the [`Display`](core::fmt::Display) and [`LowerHex`](core::fmt::LowerHex) traits, which write lowercase digits, and the [`UpperHex`](core::fmt::UpperHex) trait, which writes uppercase digits. The `0x` prefix is written when the alternate flag `#` is used with [`LowerHex`](core::fmt::LowerHex) or [`UpperHex`](core::fmt::UpperHex).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedHex<T> {
+    pub(crate) value: T,
+}
+
+/// A wrapper around a signed integer which formats it in binary, octal or hexadecimal as a sign followed by its magnitude, rather than as its two's complement representation.
+///
+/// This is created by the `sign_magnitude` method of the signed integer types, e.g. [`BInt::sign_magnitude`](crate::BInt::sign_magnitude). It implements the [`Binary`](core::fmt::Binary), [`Octal`](core::fmt::Octal), [`LowerHex`](core::fmt::LowerHex) and [`UpperHex`](core::fmt::UpperHex) traits. The prefix (e.g. `0x`) is written after the sign when the alternate flag `#` is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignMagnitude<T> {
+    pub(crate) value: T,
+}
+
+/// A wrapper around an integer which formats it as a decimal number with a fixed number of fractional digits, where the integer is the number multiplied by a power of ten.
+///
+/// This is created by the `format_scaled` method of the integer types, e.g. [`BUint::format_scaled`](crate::BUint::format_scaled). It implements the [`Display`](core::fmt::Display) trait. The width, fill and sign flags of the formatter apply to the whole number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scaled<T> {
+    pub(crate) value: T,
+    pub(crate) decimals: u32,
+    pub(crate) precision: u32,
+}
@@ -1,8 +1,83 @@
+#[cfg(test)]
+use alloc::string::String;
+use core::fmt::{Alignment, Formatter, Write};
+
+/// The number of digits written to a formatter at a time. Integers with more digits than this are split into chunks, so that formatting only ever needs a stack buffer of this size.
+pub(crate) const CHUNK_DIGITS: usize = 128;
+
+/// Writes the output of `write_digits`, which must write exactly `len` bytes, padded according to the formatter's flags.
+///
+/// This follows the same rules as [`Formatter::pad_integral`], but doesn't require the digits to be stored in a single string slice.
+pub(crate) fn pad_integral<F>(
+    f: &mut Formatter,
+    is_nonnegative: bool,
+    prefix: &str,
+    len: usize,
+    write_digits: F,
+) -> core::fmt::Result
+where
+    F: FnOnce(&mut Formatter) -> core::fmt::Result,
+{
+    let sign = if !is_nonnegative {
+        Some('-')
+    } else if f.sign_plus() {
+        Some('+')
+    } else {
+        None
+    };
+    let prefix = if f.alternate() { prefix } else { "" };
+    let width = len + sign.is_some() as usize + prefix.len();
+
+    let write_prefix = |f: &mut Formatter| -> core::fmt::Result {
+        if let Some(sign) = sign {
+            f.write_char(sign)?;
+        }
+        f.write_str(prefix)
+    };
+    let padding = match f.width() {
+        Some(min) if min > width => min - width,
+        _ => {
+            write_prefix(f)?;
+            return write_digits(f);
+        }
+    };
+    if f.sign_aware_zero_pad() {
+        write_prefix(f)?;
+        write_repeated(f, '0', padding)?;
+        return write_digits(f);
+    }
+    let (pre, post) = match f.align() {
+        Some(Alignment::Left) => (0, padding),
+        Some(Alignment::Center) => (padding / 2, padding.div_ceil(2)),
+        Some(Alignment::Right) | None => (padding, 0),
+    };
+    let fill = f.fill();
+    write_repeated(f, fill, pre)?;
+    write_prefix(f)?;
+    write_digits(f)?;
+    write_repeated(f, fill, post)
+}
+
+pub(crate) fn write_repeated<W: Write>(f: &mut W, c: char, count: usize) -> core::fmt::Result {
+    for _ in 0..count {
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Returns the number of decimal digits of `n`.
+pub(crate) const fn decimal_len(mut n: usize) -> usize {
+    let mut len = 1;
+    while n >= 10 {
+        n /= 10;
+        len += 1;
+    }
+    len
+}
+
 #[cfg(test)]
 macro_rules! format_trait {
 	($($method: ident), *) => {
-		use alloc::string::String;
-
 		// This trait allows us to use the default tester macro instead of creating a custom one
 		pub trait Format {
 			$(
@@ -13,7 +88,7 @@ macro_rules! format_trait {
 }
 
 #[cfg(test)]
-format_trait!(binary, lower_hex, upper_hex, octal, display, debug, lower_exp, upper_exp);
+format_trait!(binary, lower_hex, upper_hex, octal, display, debug, lower_exp, upper_exp, display_sign_zero, display_align, debug_width, binary_alt_zero, lower_hex_alt_width, upper_hex_sign, octal_alt, lower_exp_precision, lower_exp_precision_long, upper_exp_precision_zero, lower_exp_sign_zero, upper_exp_align, lower_exp_round);
 
 #[cfg(test)]
 macro_rules! impl_format_method {
@@ -42,7 +117,20 @@ macro_rules! impl_format {
 					display: "",
 					debug: "?",
 					lower_exp: "e",
-					upper_exp: "E"
+					upper_exp: "E",
+					display_sign_zero: "+050",
+					display_align: "*^45",
+					debug_width: "<44?",
+					binary_alt_zero: "#0140b",
+					lower_hex_alt_width: "#40x",
+					upper_hex_sign: "+X",
+					octal_alt: "#o",
+					lower_exp_precision: ".3e",
+					lower_exp_precision_long: ".45e",
+					upper_exp_precision_zero: ".0E",
+					lower_exp_sign_zero: "+020.5e",
+					upper_exp_align: "_>30.2E",
+					lower_exp_round: ".1e"
 				}
 			}
 		)*
@@ -78,11 +166,60 @@ macro_rules! tests {
 		}
 
 		fmt::test_formats!($ty; binary, lower_hex, upper_hex, octal, display, debug, lower_exp, upper_exp);
+		fmt::test_formats!($ty; display_sign_zero, display_align, debug_width, binary_alt_zero, lower_hex_alt_width, upper_hex_sign, octal_alt);
+		fmt::test_formats!($ty; lower_exp_precision, lower_exp_precision_long, upper_exp_precision_zero, lower_exp_sign_zero, upper_exp_align);
+
+		paste::paste! {
+			quickcheck::quickcheck! {
+				fn quickcheck_grouped(a: $ty, size: u8) -> bool {
+					let size = size as usize % 8 + 1;
+					let big = [<$ty:upper>]::from(a);
+					let indian = crate::fmt::Grouping::indian('\u{2009}');
+					format!("{}", big.grouped(size, ',')) == fmt::group_digits(&format!("{}", a), size, size, ',')
+						&& format!("{:>60}", big.grouped(size, ',')) == format!("{:>60}", fmt::group_digits(&format!("{}", a), size, size, ','))
+						&& format!("{:#x}", big.grouped(size, '_')) == format!("0x{}", fmt::group_digits(&format!("{:x}", a), size, size, '_'))
+						&& format!("{:X}", big.grouped(size, ' ')) == fmt::group_digits(&format!("{:X}", a), size, size, ' ')
+						&& format!("{:b}", big.grouped(size, ' ')) == fmt::group_digits(&format!("{:b}", a), size, size, ' ')
+						&& format!("{:o}", big.grouped(size, ' ')) == fmt::group_digits(&format!("{:o}", a), size, size, ' ')
+						&& format!("{}", big.grouped_with(indian)) == fmt::group_digits(&format!("{}", a), 3, 2, '\u{2009}')
+				}
+			}
+		}
+
+		test_bignum! {
+			function: <$ty as Format>::lower_exp_round,
+			cases: [
+				(ref &0u8), (ref &5u8), (ref &95u8), (ref &99u8), (ref &125u8), (ref &135u8), (ref &1250u16),
+				(ref &1251u16), (ref &1350u16), (ref &9950u16), (ref &12500u16), (ref &99999u32), (ref &u32::MAX)
+			]
+		}
 	};
 }
 
 #[cfg(test)]
 pub(crate) use tests;
 
+/// Groups the digits of a formatted primitive integer, to test the output of `Grouped`.
+#[cfg(test)]
+pub(crate) fn group_digits(s: &str, first: usize, size: usize, separator: char) -> String {
+    use alloc::vec::Vec;
+
+    let (sign, digits) = match s.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", s),
+    };
+    let mut groups = Vec::new();
+    let mut end = digits.len();
+    let mut len = first;
+    while end > len {
+        groups.push(&digits[end - len..end]);
+        end -= len;
+        len = size;
+    }
+    groups.push(&digits[..end]);
+    groups.reverse();
+    format!("{}{}", sign, groups.join(separator.encode_utf8(&mut [0; 4])))
+}
+
 #[cfg(test)]
 crate::int::fmt::impl_format!(u128, i128, u64, i64);
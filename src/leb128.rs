@@ -0,0 +1,366 @@
+//! LEB128 variable-length encoding of integers, as used by WebAssembly, DWARF and Protocol Buffers.
+//!
+//! An integer is encoded as groups of 7 bits, least significant group first, where every byte except the last has its most significant bit set. Unsigned integers are encoded with [`write_uleb128`](crate::BUint::write_uleb128), and signed integers are encoded either as two's complement with [`write_sleb128`](crate::BInt::write_sleb128), or after zigzag encoding with [`write_zigzag`](crate::BInt::write_zigzag), which maps integers with small magnitudes to small unsigned integers.
+//!
+//! Decoding accepts encodings which are padded with redundant groups, as long as the encoded integer is in range of the target type.
+
+use crate::doc;
+use crate::errors::{Leb128DecodeError, Leb128ErrorKind};
+use crate::ExpType;
+
+/// The bit which is set on every byte of an encoding except the last.
+const CONTINUATION_BIT: u8 = 0x80;
+
+macro_rules! leb128 {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> $BUint<N> {
+            /// Writes the `len` bytes of the LEB128 encoding of `self`, which is shifted right by 7 bits by `shr` after each byte.
+            #[inline]
+            fn write_leb128(self, len: usize, out: &mut impl Extend<u8>, shr: impl Fn(Self) -> Self) {
+                let mut value = self;
+                out.extend((0..len).map(move |i| {
+                    let group = value.byte(0) & !CONTINUATION_BIT;
+                    value = shr(value);
+                    if i + 1 < len {
+                        group | CONTINUATION_BIT
+                    } else {
+                        group
+                    }
+                }));
+            }
+
+            /// Returns the length in bytes of the unsigned LEB128 encoding of the integer.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// assert_eq!(U256::ZERO.uleb128_len(), 1);
+            /// assert_eq!(U256::from(127u8).uleb128_len(), 1);
+            /// assert_eq!(U256::from(128u8).uleb128_len(), 2);
+            /// assert_eq!(U256::MAX.uleb128_len(), 37);
+            /// ```
+            #[must_use]
+            #[inline]
+            pub const fn uleb128_len(&self) -> usize {
+                if self.is_zero() {
+                    1
+                } else {
+                    self.bits().div_ceil(7) as usize
+                }
+            }
+
+            /// Appends the unsigned LEB128 encoding of the integer to `out`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// let mut out = Vec::new();
+            /// U256::from(624485u32).write_uleb128(&mut out);
+            /// assert_eq!(out, [0xe5, 0x8e, 0x26]);
+            /// ```
+            #[inline]
+            pub fn write_uleb128(&self, out: &mut impl Extend<u8>) {
+                self.write_leb128(self.uleb128_len(), out, |value| value >> 7u32);
+            }
+
+            /// Decodes an unsigned LEB128-encoded integer from the start of `src`, and returns it along with the number of bytes it took up.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`Leb128DecodeError`](crate::errors::Leb128DecodeError) if `src` ends before the last byte of the encoding, or if the integer is too large to be represented by `Self`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::{U256, U128};
+            /// use bnum::errors::Leb128ErrorKind;
+            ///
+            /// assert_eq!(U256::read_uleb128(&[0xe5, 0x8e, 0x26, 0xff]), Ok((U256::from(624485u32), 3)));
+            /// assert_eq!(U256::read_uleb128(&[0x80, 0x80, 0x00]), Ok((U256::ZERO, 3)));
+            /// assert_eq!(U256::read_uleb128(&[0xe5, 0x8e]).unwrap_err().kind(), &Leb128ErrorKind::InputTooShort);
+            ///
+            /// let mut out = Vec::new();
+            /// U256::MAX.write_uleb128(&mut out);
+            /// assert_eq!(U128::read_uleb128(&out).unwrap_err().kind(), &Leb128ErrorKind::Overflow);
+            /// ```
+            pub fn read_uleb128(src: &[u8]) -> Result<(Self, usize), Leb128DecodeError> {
+                let mut out = Self::ZERO;
+                let mut shift: ExpType = 0;
+                for (i, &byte) in src.iter().enumerate() {
+                    let group = byte & !CONTINUATION_BIT;
+                    if group != 0 {
+                        if shift >= Self::BITS || Self::BITS - shift < u8::BITS as ExpType - group.leading_zeros() as ExpType {
+                            return Err(Leb128DecodeError::new(Leb128ErrorKind::Overflow));
+                        }
+                        out |= Self::from(group) << shift;
+                    }
+                    if byte & CONTINUATION_BIT == 0 {
+                        return Ok((out, i + 1));
+                    }
+                    shift = shift.saturating_add(7);
+                }
+                Err(Leb128DecodeError::new(Leb128ErrorKind::InputTooShort))
+            }
+        }
+
+        impl<const N: usize> $BInt<N> {
+            /// Returns the length in bytes of the signed LEB128 encoding of the integer.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// assert_eq!(I256::from(63i8).sleb128_len(), 1);
+            /// assert_eq!(I256::from(64i8).sleb128_len(), 2);
+            /// assert_eq!(I256::from(-64i8).sleb128_len(), 1);
+            /// assert_eq!(I256::MIN.sleb128_len(), 37);
+            /// ```
+            #[must_use]
+            #[inline]
+            pub const fn sleb128_len(&self) -> usize {
+                let redundant = if self.is_negative() { self.leading_ones() } else { self.leading_zeros() };
+                // the sign bit must be included in the encoding
+                (Self::BITS - redundant + 1).div_ceil(7) as usize
+            }
+
+            /// Appends the signed LEB128 encoding of the integer to `out`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// let mut out = Vec::new();
+            /// I256::from(-123456i32).write_sleb128(&mut out);
+            /// assert_eq!(out, [0xc0, 0xbb, 0x78]);
+            /// ```
+            #[inline]
+            pub fn write_sleb128(&self, out: &mut impl Extend<u8>) {
+                self.bits.write_leb128(self.sleb128_len(), out, |bits| (Self::from_bits(bits) >> 7u32).to_bits());
+            }
+
+            /// Decodes a signed LEB128-encoded integer from the start of `src`, and returns it along with the number of bytes it took up.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`Leb128DecodeError`](crate::errors::Leb128DecodeError) if `src` ends before the last byte of the encoding, or if the integer is out of range of `Self`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::{I256, I128};
+            /// use bnum::errors::Leb128ErrorKind;
+            ///
+            /// assert_eq!(I256::read_sleb128(&[0xc0, 0xbb, 0x78]), Ok((I256::from(-123456i32), 3)));
+            /// assert_eq!(I256::read_sleb128(&[0xff, 0x7f]), Ok((I256::NEG_ONE, 2)));
+            ///
+            /// let mut out = Vec::new();
+            /// I256::MIN.write_sleb128(&mut out);
+            /// assert_eq!(I128::read_sleb128(&out).unwrap_err().kind(), &Leb128ErrorKind::Overflow);
+            /// ```
+            pub fn read_sleb128(src: &[u8]) -> Result<(Self, usize), Leb128DecodeError> {
+                let mut out = $BUint::ZERO;
+                let mut shift: ExpType = 0;
+                // whether the encoded bits from the sign bit of `Self` upwards are all zeros or all ones
+                let (mut high_zeros, mut high_ones) = (true, true);
+                for (i, &byte) in src.iter().enumerate() {
+                    let group = byte & !CONTINUATION_BIT;
+                    if shift < Self::BITS {
+                        out |= $BUint::from(group) << shift;
+                    }
+                    if shift.saturating_add(7) > Self::BITS - 1 {
+                        let low_bits = (Self::BITS - 1).saturating_sub(shift);
+                        let high = group >> low_bits;
+                        high_zeros &= high == 0;
+                        high_ones &= high == !CONTINUATION_BIT >> low_bits;
+                    }
+                    if byte & CONTINUATION_BIT == 0 {
+                        let negative = group & 0x40 != 0;
+                        if (negative && !high_ones) || (!negative && !high_zeros) {
+                            return Err(Leb128DecodeError::new(Leb128ErrorKind::Overflow));
+                        }
+                        let len = shift.saturating_add(7);
+                        if negative && len < Self::BITS {
+                            out |= $BUint::MAX << len;
+                        }
+                        return Ok((Self::from_bits(out), i + 1));
+                    }
+                    shift = shift.saturating_add(7);
+                }
+                Err(Leb128DecodeError::new(Leb128ErrorKind::InputTooShort))
+            }
+
+            /// Returns the zigzag encoding of the integer, which maps `0, -1, 1, -2, 2, ...` to `0, 1, 2, 3, 4, ...`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::{I256, U256};
+            ///
+            /// assert_eq!(I256::from(-1i8).to_zigzag(), U256::ONE);
+            /// assert_eq!(I256::from(2i8).to_zigzag(), U256::from(4u8));
+            /// assert_eq!(I256::MIN.to_zigzag(), U256::MAX);
+            /// ```
+            #[must_use = doc::must_use_op!()]
+            #[inline]
+            pub fn to_zigzag(self) -> $BUint<N> {
+                let sign = if self.is_negative() { $BUint::MAX } else { $BUint::ZERO };
+                (self.bits << 1u32) ^ sign
+            }
+
+            /// Returns the integer whose zigzag encoding is `zigzag`. This is the inverse of [`to_zigzag`](#method.to_zigzag).
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::{I256, U256};
+            ///
+            /// assert_eq!(I256::from_zigzag(U256::from(3u8)), I256::from(-2i8));
+            /// assert_eq!(I256::from_zigzag(U256::MAX - U256::ONE), I256::MAX);
+            /// ```
+            #[must_use]
+            #[inline]
+            pub fn from_zigzag(zigzag: $BUint<N>) -> Self {
+                let sign = if zigzag.digits[0] & 1 == 1 { $BUint::MAX } else { $BUint::ZERO };
+                Self::from_bits((zigzag >> 1u32) ^ sign)
+            }
+
+            /// Appends the unsigned LEB128 encoding of the zigzag encoding of the integer to `out`, as used by the `sint` types of Protocol Buffers.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// let mut out = Vec::new();
+            /// I256::from(-65i8).write_zigzag(&mut out);
+            /// assert_eq!(out, [0x81, 0x01]);
+            /// ```
+            #[inline]
+            pub fn write_zigzag(&self, out: &mut impl Extend<u8>) {
+                self.to_zigzag().write_uleb128(out)
+            }
+
+            /// Decodes an integer which was encoded with [`write_zigzag`](#method.write_zigzag) from the start of `src`, and returns it along with the number of bytes it took up.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`Leb128DecodeError`](crate::errors::Leb128DecodeError) in the same cases as the [`read_uleb128`](crate::BUint::read_uleb128) method of the unsigned integer with the same bit width.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::I256;
+            ///
+            /// assert_eq!(I256::read_zigzag(&[0x81, 0x01]), Ok((I256::from(-65i8), 2)));
+            /// ```
+            #[inline]
+            pub fn read_zigzag(src: &[u8]) -> Result<(Self, usize), Leb128DecodeError> {
+                $BUint::read_uleb128(src).map(|(zigzag, len)| (Self::from_zigzag(zigzag), len))
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::errors::Leb128ErrorKind;
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+                use alloc::vec::Vec;
+
+                fn uleb128_utest(mut u: utest) -> Vec<u8> {
+                    let mut out = Vec::new();
+                    loop {
+                        let group = (u & 0x7f) as u8;
+                        u >>= 7;
+                        if u == 0 {
+                            out.push(group);
+                            return out;
+                        }
+                        out.push(group | 0x80);
+                    }
+                }
+
+                fn sleb128_itest(mut i: itest) -> Vec<u8> {
+                    let mut out = Vec::new();
+                    loop {
+                        let group = (i & 0x7f) as u8;
+                        i >>= 7;
+                        if (i == 0 && group & 0x40 == 0) || (i == -1 && group & 0x40 != 0) {
+                            out.push(group);
+                            return out;
+                        }
+                        out.push(group | 0x80);
+                    }
+                }
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_uleb128(u: utest) -> bool {
+                        let big_u = UTEST::from(u);
+                        let encoded = uleb128_utest(u);
+                        let mut out = Vec::new();
+                        big_u.write_uleb128(&mut out);
+                        out == encoded
+                            && big_u.uleb128_len() == encoded.len()
+                            && UTEST::read_uleb128(&encoded) == Ok((big_u, encoded.len()))
+                    }
+
+                    fn quickcheck_sleb128(i: itest) -> bool {
+                        let big_i = ITEST::from(i);
+                        let encoded = sleb128_itest(i);
+                        let mut out = Vec::new();
+                        big_i.write_sleb128(&mut out);
+                        out == encoded
+                            && big_i.sleb128_len() == encoded.len()
+                            && ITEST::read_sleb128(&encoded) == Ok((big_i, encoded.len()))
+                    }
+
+                    fn quickcheck_zigzag(i: itest) -> bool {
+                        let big_i = ITEST::from(i);
+                        let zigzag = ((i << 1) ^ (i >> 127)) as utest;
+                        let mut out = Vec::new();
+                        big_i.write_zigzag(&mut out);
+                        big_i.to_zigzag() == UTEST::from(zigzag)
+                            && ITEST::from_zigzag(UTEST::from(zigzag)) == big_i
+                            && out == uleb128_utest(zigzag)
+                            && ITEST::read_zigzag(&out) == Ok((big_i, out.len()))
+                    }
+                }
+
+                #[test]
+                fn leb128() {
+                    // 2^128 and 2^127 don't fit in the unsigned and signed 128 bit types respectively
+                    let mut too_large = [0x80; 19];
+                    too_large[18] = 0x04;
+                    assert_eq!(UTEST::read_uleb128(&too_large).unwrap_err().kind(), &Leb128ErrorKind::Overflow);
+                    too_large[18] = 0x03;
+                    assert_eq!(UTEST::read_uleb128(&too_large).unwrap().0, UTEST::from(3u8) << 126u32);
+                    too_large[18] = 0x02;
+                    assert_eq!(ITEST::read_sleb128(&too_large).unwrap_err().kind(), &Leb128ErrorKind::Overflow);
+                    too_large[18] = 0x7e;
+                    assert_eq!(ITEST::read_sleb128(&too_large).unwrap().0, ITEST::MIN);
+                    too_large[18] = 0x7c;
+                    assert_eq!(ITEST::read_sleb128(&too_large).unwrap_err().kind(), &Leb128ErrorKind::Overflow);
+
+                    // redundant padding is accepted
+                    assert_eq!(UTEST::read_uleb128(&[0xff, 0x80, 0x80, 0x00]), Ok((UTEST::from(0x7fu8), 4)));
+                    assert_eq!(ITEST::read_sleb128(&[0xff, 0xff, 0x7f]), Ok((ITEST::NEG_ONE, 3)));
+                    let mut padded = [0x80; 40];
+                    padded[39] = 0;
+                    assert_eq!(UTEST::read_uleb128(&padded), Ok((UTEST::ZERO, 40)));
+                    assert_eq!(ITEST::read_sleb128(&padded), Ok((ITEST::ZERO, 40)));
+
+                    assert_eq!(UTEST::read_uleb128(&[]).unwrap_err().kind(), &Leb128ErrorKind::InputTooShort);
+                    assert_eq!(ITEST::read_sleb128(&[0xff]).unwrap_err().kind(), &Leb128ErrorKind::InputTooShort);
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(leb128);
@@ -24,25 +24,64 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
 
+#[cfg(any(feature = "alloc", test))]
 #[macro_use]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod bint;
 mod buint;
 
+pub mod abi;
+pub mod alphabet;
+
+#[cfg(feature = "borsh")]
+mod borsh;
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+
+#[cfg(feature = "bytes")]
+pub mod bytes;
+
 pub mod cast;
+pub mod der;
 mod digit;
 mod doc;
 pub mod errors;
+
+#[cfg(feature = "float")]
+pub mod float;
+
+pub mod fmt;
 mod int;
+pub mod leb128;
+#[doc(hidden)]
+pub mod literal;
 mod nightly;
+
+#[cfg(feature = "num-bigint")]
+mod num_bigint;
+
 pub mod prelude;
+pub mod rlp;
 
 #[cfg(feature = "rand")]
 pub mod random;
 
+#[cfg(feature = "scale")]
+pub mod scale;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
 pub mod types;
 
+#[cfg(all(test, feature = "zerocopy"))]
+mod zerocopy;
+
 #[cfg(test)]
 mod test;
 
@@ -98,4 +137,6 @@ mod bigints {
 	pub use crate::buint::{BUint, BUintD16, BUintD32, BUintD8};
 }
 
-pub use bigints::*;
\ No newline at end of file
+pub use bigints::*;
+#[cfg(feature = "float")]
+pub use float::{Float, FloatD16, FloatD32, FloatD8};
@@ -0,0 +1,69 @@
+use crate::errors::ParseIntError;
+
+/// Creates an unsigned integer of the given type from a string slice, which is parsed at compile time.
+///
+/// The string is parsed with the `from_str_prefixed` method of the given type (e.g. [`BUint::from_str_prefixed`](crate::BUint::from_str_prefixed)), so it can have a `0x`, `0o` or `0b` prefix, `_` separators and a type suffix, as in a Rust integer literal. If the string is invalid or the value it represents is too large for the type, compilation fails.
+///
+/// # Examples
+///
+/// ```
+/// use bnum::types::U256;
+/// use bnum::uint;
+///
+/// const ORDER: U256 = uint!(U256, "0xffffffff_ffffffff_ffffffff_fffffffe_baaedce6_af48a03b_bfd25e8c_d0364141");
+/// let hex = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+/// assert_eq!(ORDER, U256::from_str_radix(hex, 16).unwrap());
+/// assert_eq!(uint!(U256, "1_000_000u256"), U256::from(1000000u32));
+/// ```
+///
+/// ```compile_fail
+/// use bnum::types::U128;
+///
+/// let n = bnum::uint!(U128, "0x1_00000000_00000000_00000000_00000000");
+/// ```
+#[macro_export]
+macro_rules! uint {
+    ($ty: ty, $src: expr) => {{
+        const VALUE: $ty = match <$ty>::from_str_prefixed($src) {
+            Ok(value) => value,
+            Err(err) => $crate::literal::invalid_literal(&err),
+        };
+        VALUE
+    }};
+}
+
+/// Creates a signed integer of the given type from a string slice, which is parsed at compile time.
+///
+/// The string is parsed with the `from_str_prefixed` method of the given type (e.g. [`BInt::from_str_prefixed`](crate::BInt::from_str_prefixed)), so it can have a sign, a `0x`, `0o` or `0b` prefix, `_` separators and a type suffix, as in a Rust integer literal. If the string is invalid or the value it represents is out of range for the type, compilation fails.
+///
+/// # Examples
+///
+/// ```
+/// use bnum::types::I512;
+/// use bnum::int;
+///
+/// const N: I512 = int!(I512, "-123_456_789_012_345_678_901_234_567_890");
+/// assert_eq!(N, I512::from(-123456789012345678901234567890i128));
+/// assert_eq!(int!(I512, "-0x8000"), I512::from(-0x8000i32));
+/// ```
+///
+/// ```compile_fail
+/// use bnum::types::I128;
+///
+/// let n = bnum::int!(I128, "12z");
+/// ```
+#[macro_export]
+macro_rules! int {
+    ($ty: ty, $src: expr) => {{
+        const VALUE: $ty = match <$ty>::from_str_prefixed($src) {
+            Ok(value) => value,
+            Err(err) => $crate::literal::invalid_literal(&err),
+        };
+        VALUE
+    }};
+}
+
+#[doc(hidden)]
+pub const fn invalid_literal(err: &ParseIntError) -> ! {
+    panic!("{}", err.description())
+}
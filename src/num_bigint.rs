@@ -0,0 +1,100 @@
+use crate::errors::TryFromIntError;
+use ::num_bigint::{BigInt, BigUint};
+
+macro_rules! num_bigint {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> From<$BUint<N>> for BigUint {
+            #[inline]
+            fn from(value: $BUint<N>) -> Self {
+                Self::from_bytes_le(&value.to_le_bytes_trimmed())
+            }
+        }
+
+        impl<const N: usize> TryFrom<&BigUint> for $BUint<N> {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(value: &BigUint) -> Result<Self, Self::Error> {
+                Self::from_le_slice(&value.to_bytes_le()).ok_or(TryFromIntError::new(Self::BITS))
+            }
+        }
+
+        impl<const N: usize> TryFrom<BigUint> for $BUint<N> {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+                Self::try_from(&value)
+            }
+        }
+
+        impl<const N: usize> From<$BInt<N>> for BigInt {
+            #[inline]
+            fn from(value: $BInt<N>) -> Self {
+                Self::from_signed_bytes_le(&value.to_signed_bytes_le())
+            }
+        }
+
+        impl<const N: usize> TryFrom<&BigInt> for $BInt<N> {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+                Self::from_signed_bytes_le(&value.to_signed_bytes_le()).ok_or(TryFromIntError::new(Self::BITS))
+            }
+        }
+
+        impl<const N: usize> TryFrom<BigInt> for $BInt<N> {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+                Self::try_from(&value)
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+                use ::num_bigint::{BigInt, BigUint};
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_num_bigint_uint(u: utest) -> bool {
+                        let big_u = UTEST::from(u);
+                        let expected = BigUint::from(u);
+                        BigUint::from(big_u) == expected
+                            && UTEST::try_from(&expected) == Ok(big_u)
+                            && UTEST::try_from(expected) == Ok(big_u)
+                    }
+
+                    fn quickcheck_num_bigint_int(i: itest) -> bool {
+                        let big_i = ITEST::from(i);
+                        let expected = BigInt::from(i);
+                        BigInt::from(big_i) == expected
+                            && ITEST::try_from(&expected) == Ok(big_i)
+                            && ITEST::try_from(expected) == Ok(big_i)
+                    }
+                }
+
+                #[test]
+                fn num_bigint_out_of_range() {
+                    let max = BigUint::from(u128::MAX);
+                    assert_eq!(UTEST::try_from(&(&max + 1u8)).unwrap_err().bits(), Some(128));
+                    assert_eq!(UTEST::try_from(max), Ok(UTEST::MAX));
+                    assert_eq!(UTEST::try_from(BigUint::from(0u8)), Ok(UTEST::ZERO));
+
+                    let min = BigInt::from(i128::MIN);
+                    let max = BigInt::from(i128::MAX);
+                    assert_eq!(ITEST::try_from(&min), Ok(ITEST::MIN));
+                    assert_eq!(ITEST::try_from(&max), Ok(ITEST::MAX));
+                    assert_eq!(ITEST::try_from(&(min - 1)).unwrap_err().bits(), Some(128));
+                    assert_eq!(ITEST::try_from(&(max + 1)).unwrap_err().bits(), Some(128));
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(num_bigint);
@@ -0,0 +1,292 @@
+//! RLP (Recursive Length Prefix) encoding of unsigned integers, as used by Ethereum.
+//!
+//! An integer is encoded as the RLP string of its big-endian bytes without leading zeros, so zero is encoded as the empty string `0x80`, an integer less than `0x80` is encoded as the single byte which is its value, and any other integer is encoded as its bytes after a length prefix. Decoding only accepts this canonical encoding.
+//!
+//! The encoding and decoding methods are [`rlp_len`](crate::BUint::rlp_len), [`write_rlp`](crate::BUint::write_rlp), [`to_rlp`](crate::BUint::to_rlp), [`decode_rlp`](crate::BUint::decode_rlp) and [`from_rlp`](crate::BUint::from_rlp), which are defined on each unsigned integer type.
+
+use crate::errors::{BufferTooSmallError, RlpDecodeError, RlpErrorKind};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The first byte of the encoding of a string of fewer than 56 bytes is this offset plus the length.
+const SHORT_STRING_OFFSET: u8 = 0x80;
+
+/// The first byte of the encoding of a string of at least 56 bytes is this offset plus the number of bytes of the length.
+const LONG_STRING_OFFSET: u8 = 0xb7;
+
+/// The first byte of the encoding of a list is at least this value.
+const LIST_OFFSET: u8 = 0xc0;
+
+/// The number of bytes of the big-endian representation of `len` without leading zeros.
+const fn len_of_len(len: usize) -> usize {
+    (usize::BITS - len.leading_zeros()).div_ceil(8) as usize
+}
+
+macro_rules! rlp {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> $BUint<N> {
+            /// Returns the number of bytes of the integer's big-endian representation without leading zeros.
+            #[inline]
+            const fn rlp_payload_len(&self) -> usize {
+                self.bits().div_ceil(8) as usize
+            }
+
+            /// Returns the length in bytes of the RLP encoding of the integer.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// assert_eq!(U256::ZERO.rlp_len(), 1);
+            /// assert_eq!(U256::from(0x7fu8).rlp_len(), 1);
+            /// assert_eq!(U256::from(0x80u8).rlp_len(), 2);
+            /// assert_eq!(U256::MAX.rlp_len(), 33);
+            /// ```
+            #[must_use]
+            #[inline]
+            pub const fn rlp_len(&self) -> usize {
+                let len = self.rlp_payload_len();
+                if len == 1 && self.digits[0] < SHORT_STRING_OFFSET as $Digit {
+                    1
+                } else if len < 56 {
+                    1 + len
+                } else {
+                    1 + len_of_len(len) + len
+                }
+            }
+
+            /// Writes the RLP encoding of the integer into the start of `buf`, and returns the written bytes.
+            ///
+            /// # Errors
+            ///
+            /// Returns a [`BufferTooSmallError`](crate::errors::BufferTooSmallError) if `buf` is shorter than [`rlp_len`](#method.rlp_len).
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            ///
+            /// let mut buf = [0; 33];
+            /// assert_eq!(U256::from(1024u16).write_rlp(&mut buf), Ok(&[0x82, 0x04, 0x00][..]));
+            /// assert_eq!(U256::from(15u8).write_rlp(&mut buf), Ok(&[0x0f][..]));
+            /// assert_eq!(U256::ZERO.write_rlp(&mut buf), Ok(&[0x80][..]));
+            /// ```
+            pub fn write_rlp<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], BufferTooSmallError> {
+                let required = self.rlp_len();
+                if buf.len() < required {
+                    return Err(BufferTooSmallError { required });
+                }
+                let buf = &mut buf[..required];
+                let len = self.rlp_payload_len();
+                let start = if required == 1 && len == 1 {
+                    0
+                } else if len < 56 {
+                    buf[0] = SHORT_STRING_OFFSET + len as u8;
+                    1
+                } else {
+                    let len_of_len = len_of_len(len);
+                    buf[0] = LONG_STRING_OFFSET + len_of_len as u8;
+                    buf[1..=len_of_len].copy_from_slice(&len.to_be_bytes()[core::mem::size_of::<usize>() - len_of_len..]);
+                    1 + len_of_len
+                };
+                for (i, byte) in buf[start..].iter_mut().rev().enumerate() {
+                    *byte = self.byte(i);
+                }
+                Ok(buf)
+            }
+
+            /// Returns the RLP encoding of the integer.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U512;
+            ///
+            /// let n = U512::ONE << 447u32;
+            /// let encoded = n.to_rlp();
+            /// assert_eq!(&encoded[..3], &[0xb8, 56, 0x80]);
+            /// assert_eq!(U512::from_rlp(&encoded), Ok(n));
+            /// ```
+            #[cfg(feature = "alloc")]
+            #[inline]
+            pub fn to_rlp(&self) -> Vec<u8> {
+                let mut out = vec![0; self.rlp_len()];
+                self.write_rlp(&mut out).unwrap();
+                out
+            }
+
+            /// Decodes an RLP-encoded integer from the start of `buf`, and advances `buf` past the encoding.
+            ///
+            /// Only the canonical encoding of an integer is accepted. The slice is only advanced if decoding succeeds.
+            ///
+            /// # Errors
+            ///
+            /// Returns an [`RlpDecodeError`](crate::errors::RlpDecodeError) if `buf` doesn't start with the canonical encoding of an integer, or if the integer is too large to be represented by `Self`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            /// use bnum::errors::RlpErrorKind;
+            ///
+            /// let mut buf = &[0x82, 0x04, 0x00, 0x0f][..];
+            /// assert_eq!(U256::decode_rlp(&mut buf), Ok(U256::from(1024u16)));
+            /// assert_eq!(U256::decode_rlp(&mut buf), Ok(U256::from(15u8)));
+            /// assert!(buf.is_empty());
+            ///
+            /// assert_eq!(U256::decode_rlp(&mut &[0x82, 0x00, 0x0f][..]).unwrap_err().kind(), &RlpErrorKind::NonCanonical);
+            /// ```
+            pub fn decode_rlp(buf: &mut &[u8]) -> Result<Self, RlpDecodeError> {
+                let src = *buf;
+                let first = match src.first() {
+                    Some(&first) => first,
+                    None => return Err(RlpDecodeError::new(RlpErrorKind::InputTooShort)),
+                };
+                let (start, len) = if first < SHORT_STRING_OFFSET {
+                    // a zero byte would be a leading zero, as zero is encoded as the empty string
+                    if first == 0 {
+                        return Err(RlpDecodeError::new(RlpErrorKind::NonCanonical));
+                    }
+                    (0, 1)
+                } else if first <= LONG_STRING_OFFSET {
+                    let len = (first - SHORT_STRING_OFFSET) as usize;
+                    if len == 1 && src.get(1).is_some_and(|&byte| byte < SHORT_STRING_OFFSET) {
+                        return Err(RlpDecodeError::new(RlpErrorKind::NonCanonical));
+                    }
+                    (1, len)
+                } else if first < LIST_OFFSET {
+                    let len_of_len = (first - LONG_STRING_OFFSET) as usize;
+                    let len_bytes = match src.get(1..1 + len_of_len) {
+                        Some(len_bytes) => len_bytes,
+                        None => return Err(RlpDecodeError::new(RlpErrorKind::InputTooShort)),
+                    };
+                    if len_bytes[0] == 0 {
+                        return Err(RlpDecodeError::new(RlpErrorKind::NonCanonical));
+                    }
+                    if len_of_len > core::mem::size_of::<usize>() {
+                        return Err(RlpDecodeError::new(RlpErrorKind::InputTooShort));
+                    }
+                    let len = len_bytes.iter().fold(0, |len, &byte| (len << 8) | byte as usize);
+                    if len < 56 {
+                        return Err(RlpDecodeError::new(RlpErrorKind::NonCanonical));
+                    }
+                    (1 + len_of_len, len)
+                } else {
+                    return Err(RlpDecodeError::new(RlpErrorKind::UnexpectedList));
+                };
+                let payload = match src.get(start..).and_then(|rest| rest.get(..len)) {
+                    Some(payload) => payload,
+                    None => return Err(RlpDecodeError::new(RlpErrorKind::InputTooShort)),
+                };
+                if payload.first() == Some(&0) {
+                    return Err(RlpDecodeError::new(RlpErrorKind::NonCanonical));
+                }
+                let out = match Self::from_be_slice(payload) {
+                    Some(out) => out,
+                    None => return Err(RlpDecodeError::new(RlpErrorKind::Overflow)),
+                };
+                *buf = &src[start + len..];
+                Ok(out)
+            }
+
+            /// Decodes an RLP-encoded integer which takes up the whole of `src`.
+            ///
+            /// # Errors
+            ///
+            /// Returns an [`RlpDecodeError`](crate::errors::RlpDecodeError) in the same cases as [`decode_rlp`](#method.decode_rlp), and also if `src` has bytes after the end of the encoding.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use bnum::types::U256;
+            /// use bnum::errors::RlpErrorKind;
+            ///
+            /// assert_eq!(U256::from_rlp(&[0x80]), Ok(U256::ZERO));
+            /// assert_eq!(U256::from_rlp(&[0x80, 0x80]).unwrap_err().kind(), &RlpErrorKind::TrailingBytes);
+            /// assert_eq!(U256::from_rlp(&[0xc0]).unwrap_err().kind(), &RlpErrorKind::UnexpectedList);
+            /// ```
+            pub fn from_rlp(mut src: &[u8]) -> Result<Self, RlpDecodeError> {
+                let out = Self::decode_rlp(&mut src)?;
+                if !src.is_empty() {
+                    return Err(RlpDecodeError::new(RlpErrorKind::TrailingBytes));
+                }
+                Ok(out)
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::errors::RlpErrorKind;
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::utest;
+                use crate::types::U1024;
+                use alloc::vec::Vec;
+
+                /// The RLP encoding of a primitive integer, for comparison.
+                fn rlp_utest(u: utest) -> Vec<u8> {
+                    let bytes = u.to_be_bytes();
+                    let trimmed = &bytes[(u.leading_zeros() / 8) as usize..];
+                    match trimmed {
+                        [byte] if *byte < 0x80 => vec![*byte],
+                        _ => [&[0x80 + trimmed.len() as u8], trimmed].concat(),
+                    }
+                }
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_rlp(u: utest) -> bool {
+                        let big_u = UTEST::from(u);
+                        let encoded = rlp_utest(u);
+                        big_u.to_rlp() == encoded
+                            && big_u.rlp_len() == encoded.len()
+                            && UTEST::from_rlp(&encoded) == Ok(big_u)
+                    }
+                }
+
+                #[test]
+                fn rlp() {
+                    assert_eq!(UTEST::ZERO.to_rlp(), [0x80]);
+                    assert_eq!(UTEST::from(0x7fu8).to_rlp(), [0x7f]);
+                    assert_eq!(UTEST::from(0x80u8).to_rlp(), [0x81, 0x80]);
+                    assert_eq!(UTEST::from(0x0400u16).to_rlp(), [0x82, 0x04, 0x00]);
+
+                    let mut buf = [0; 16];
+                    assert_eq!(UTEST::MAX.write_rlp(&mut buf).unwrap_err().required(), 17);
+
+                    let cases: [(&[u8], RlpErrorKind); 10] = [
+                        (&[], RlpErrorKind::InputTooShort),
+                        (&[0x00], RlpErrorKind::NonCanonical),
+                        (&[0x81, 0x7f], RlpErrorKind::NonCanonical),
+                        (&[0x82, 0x00, 0xff], RlpErrorKind::NonCanonical),
+                        (&[0x82, 0xff], RlpErrorKind::InputTooShort),
+                        (&[0xb8, 0x01, 0xff], RlpErrorKind::NonCanonical),
+                        (&[0xb9, 0x00, 0x38], RlpErrorKind::NonCanonical),
+                        (&[0xb8, 0x38], RlpErrorKind::InputTooShort),
+                        (&[0xc0], RlpErrorKind::UnexpectedList),
+                        (&[0x0f, 0x0f], RlpErrorKind::TrailingBytes),
+                    ];
+                    for (src, kind) in cases {
+                        assert_eq!(UTEST::from_rlp(src).unwrap_err().kind(), &kind);
+                    }
+                    let too_large = [&[0x91, 0x01][..], &[0; 16]].concat();
+                    assert_eq!(UTEST::from_rlp(&too_large).unwrap_err().kind(), &RlpErrorKind::Overflow);
+
+                    let mut buf = &[0x05, 0x81, 0x80, 0xc0][..];
+                    assert_eq!(UTEST::decode_rlp(&mut buf), Ok(UTEST::from(5u8)));
+                    assert_eq!(UTEST::decode_rlp(&mut buf), Ok(UTEST::from(0x80u8)));
+                    assert!(UTEST::decode_rlp(&mut buf).is_err());
+                    assert_eq!(buf, [0xc0]);
+
+                    let long = U1024::MAX >> 200u32;
+                    let encoded = long.to_rlp();
+                    assert_eq!(&encoded[..2], &[0xb8, 103]);
+                    assert_eq!(encoded.len(), 105);
+                    assert_eq!(U1024::from_rlp(&encoded), Ok(long));
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(rlp);
@@ -0,0 +1,333 @@
+//! Support for the SCALE codec of the [`parity-scale-codec`](https://docs.rs/parity-scale-codec/latest/parity_scale_codec/) crate.
+//!
+//! The `scale` feature must be enabled to use items from this module.
+//!
+//! Every integer type implements [`Encode`], [`Decode`] and [`MaxEncodedLen`], with integers encoded as their `Self::BITS / 8` little-endian bytes, in the same way as the primitive integers.
+//!
+//! Unsigned integers can also be encoded with the SCALE compact encoding by wrapping them in this module's [`Compact`] type. The `parity_scale_codec::Compact` type can't be used, since it only supports types which are represented by a primitive integer, so instead fields should use the `encoded_as` attribute of the derive macros:
+//!
+//! ```ignore
+//! use bnum::types::U256;
+//! use parity_scale_codec::{Decode, Encode};
+//!
+//! #[derive(Encode, Decode)]
+//! struct Account {
+//!     #[codec(encoded_as = "bnum::scale::Compact<U256>")]
+//!     balance: U256,
+//! }
+//! ```
+
+use parity_scale_codec::{CompactLen, ConstEncodedLen, Decode, Encode, EncodeAsRef, EncodeLike, Error, Input, MaxEncodedLen, Output};
+
+/// The largest number of bytes that the value of a compact encoded integer can have, which is the number that can be stored in the six bits of the prefix byte, plus four.
+const MAX_COMPACT_BYTES: usize = 67;
+
+/// A wrapper around an unsigned integer which is encoded with the SCALE compact encoding.
+///
+/// The encoding is the same as that of `parity_scale_codec::Compact` for the primitive integers: values less than `2^30` are encoded in one, two or four bytes, and larger values are encoded as a prefix byte followed by the fewest little-endian bytes which represent them, but at least four. Only values less than `2^536` can be encoded, so for integers with more than 536 bits, encoding larger values panics. Decoding rejects encodings which are not the shortest possible.
+///
+/// # Examples
+///
+/// ```
+/// use bnum::scale::Compact;
+/// use bnum::types::U256;
+/// use parity_scale_codec::{Decode, Encode};
+///
+/// let n = U256::from(1u8) << 100u32;
+/// let encoded = Compact(n).encode();
+/// assert_eq!(encoded.len(), 14);
+/// assert_eq!(Compact::<U256>::decode(&mut &encoded[..]).unwrap().0, n);
+/// assert_eq!(Compact(U256::from(42u8)).encode(), [42 << 2]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Compact<T>(pub T);
+
+/// A reference to an unsigned integer which is encoded with the SCALE compact encoding.
+///
+/// This is the type which [`Compact`] uses to encode a field with the `encoded_as` attribute, and is encoded in the same way as [`Compact`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompactRef<'a, T>(pub &'a T);
+
+impl<T> From<T> for Compact<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<'a, T> From<&'a T> for CompactRef<'a, T> {
+    #[inline]
+    fn from(value: &'a T) -> Self {
+        Self(value)
+    }
+}
+
+macro_rules! scale {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> Encode for $BUint<N> {
+            #[inline]
+            fn size_hint(&self) -> usize {
+                Self::BITS as usize / 8
+            }
+
+            // The digits are stored least significant first and each is encoded in little endian, so this encodes the integer's little-endian bytes.
+            #[inline]
+            fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+                self.digits.encode_to(dest)
+            }
+
+            #[inline]
+            fn encoded_size(&self) -> usize {
+                Self::BITS as usize / 8
+            }
+        }
+
+        impl<const N: usize> EncodeLike for $BUint<N> {}
+
+        impl<const N: usize> Decode for $BUint<N> {
+            #[inline]
+            fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                <[$Digit; N]>::decode(input).map(Self::from_digits)
+            }
+
+            #[inline]
+            fn encoded_fixed_size() -> Option<usize> {
+                Some(Self::BITS as usize / 8)
+            }
+        }
+
+        impl<const N: usize> MaxEncodedLen for $BUint<N> {
+            #[inline]
+            fn max_encoded_len() -> usize {
+                Self::BITS as usize / 8
+            }
+        }
+
+        impl<const N: usize> ConstEncodedLen for $BUint<N> {}
+
+        impl<const N: usize> Encode for $BInt<N> {
+            #[inline]
+            fn size_hint(&self) -> usize {
+                self.bits.size_hint()
+            }
+
+            #[inline]
+            fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+                self.bits.encode_to(dest)
+            }
+
+            #[inline]
+            fn encoded_size(&self) -> usize {
+                self.bits.encoded_size()
+            }
+        }
+
+        impl<const N: usize> EncodeLike for $BInt<N> {}
+
+        impl<const N: usize> Decode for $BInt<N> {
+            #[inline]
+            fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                $BUint::decode(input).map(Self::from_bits)
+            }
+
+            #[inline]
+            fn encoded_fixed_size() -> Option<usize> {
+                $BUint::<N>::encoded_fixed_size()
+            }
+        }
+
+        impl<const N: usize> MaxEncodedLen for $BInt<N> {
+            #[inline]
+            fn max_encoded_len() -> usize {
+                $BUint::<N>::max_encoded_len()
+            }
+        }
+
+        impl<const N: usize> ConstEncodedLen for $BInt<N> {}
+
+        impl<const N: usize> $BUint<N> {
+            /// Returns the integer's low 32 bits, or fewer if it is narrower.
+            fn low_u32(&self) -> u32 {
+                let bytes = (Self::BITS as usize / 8).min(4);
+                (0..bytes).fold(0, |out, i| out | (self.byte(i) as u32) << (i * 8))
+            }
+
+            /// Returns the number of bytes that the value of the integer has in the big integer mode of the compact encoding.
+            fn compact_value_len(&self) -> usize {
+                (self.bits() as usize).div_ceil(8)
+            }
+        }
+
+        impl<const N: usize> CompactLen<$BUint<N>> for Compact<$BUint<N>> {
+            fn compact_len(value: &$BUint<N>) -> usize {
+                match value.bits() {
+                    0..=6 => 1,
+                    7..=14 => 2,
+                    15..=30 => 4,
+                    _ => 1 + value.compact_value_len(),
+                }
+            }
+        }
+
+        impl<const N: usize> Encode for CompactRef<'_, $BUint<N>> {
+            #[inline]
+            fn size_hint(&self) -> usize {
+                Compact::compact_len(self.0)
+            }
+
+            fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+                let value = self.0;
+                match value.bits() {
+                    0..=6 => dest.push_byte((value.low_u32() << 2) as u8),
+                    7..=14 => dest.write(&((value.low_u32() << 2) as u16 | 0b01).to_le_bytes()),
+                    15..=30 => dest.write(&((value.low_u32() << 2) | 0b10).to_le_bytes()),
+                    _ => {
+                        let len = value.compact_value_len();
+                        assert!(len <= MAX_COMPACT_BYTES, "{}", crate::errors::err_msg!("integer is too large for the SCALE compact encoding"));
+                        dest.push_byte((((len - 4) << 2) | 0b11) as u8);
+                        for i in 0..len {
+                            dest.push_byte(value.byte(i));
+                        }
+                    }
+                }
+            }
+        }
+
+        impl<const N: usize> Encode for Compact<$BUint<N>> {
+            #[inline]
+            fn size_hint(&self) -> usize {
+                CompactRef(&self.0).size_hint()
+            }
+
+            #[inline]
+            fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+                CompactRef(&self.0).encode_to(dest)
+            }
+        }
+
+        impl<const N: usize> EncodeLike for Compact<$BUint<N>> {}
+
+        impl<const N: usize> EncodeLike<$BUint<N>> for Compact<$BUint<N>> {}
+
+        impl<'a, const N: usize> EncodeAsRef<'a, $BUint<N>> for Compact<$BUint<N>> {
+            type RefType = CompactRef<'a, $BUint<N>>;
+        }
+
+        impl<const N: usize> Decode for Compact<$BUint<N>> {
+            fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                const OUT_OF_RANGE: &str = "out of range decoding compact bnum integer";
+
+                let prefix = input.read_byte()?;
+                let (small, min) = match prefix & 0b11 {
+                    0b00 => (prefix as u32 >> 2, 0),
+                    0b01 => {
+                        let high = input.read_byte()?;
+                        (u16::from_le_bytes([prefix, high]) as u32 >> 2, 1 << 6)
+                    }
+                    0b10 => {
+                        let mut rest = [0; 3];
+                        input.read(&mut rest)?;
+                        (u32::from_le_bytes([prefix, rest[0], rest[1], rest[2]]) >> 2, 1 << 14)
+                    }
+                    _ => {
+                        let len = (prefix >> 2) as usize + 4;
+                        let mut bytes = [0; MAX_COMPACT_BYTES];
+                        input.read(&mut bytes[..len])?;
+                        let canonical = if len == 4 {
+                            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) >> 30 != 0
+                        } else {
+                            bytes[len - 1] != 0
+                        };
+                        if !canonical {
+                            return Err(OUT_OF_RANGE.into());
+                        }
+                        return $BUint::from_le_slice(&bytes[..len]).map(Compact).ok_or(OUT_OF_RANGE.into());
+                    }
+                };
+                if small < min {
+                    return Err(OUT_OF_RANGE.into());
+                }
+                $BUint::from_le_slice(&small.to_le_bytes()).map(Compact).ok_or(OUT_OF_RANGE.into())
+            }
+        }
+
+        impl<const N: usize> MaxEncodedLen for Compact<$BUint<N>> {
+            #[inline]
+            fn max_encoded_len() -> usize {
+                match $BUint::<N>::BITS {
+                    0..=6 => 1,
+                    7..=14 => 2,
+                    15..=30 => 4,
+                    bits => 1 + (bits as usize / 8).clamp(4, MAX_COMPACT_BYTES),
+                }
+            }
+        }
+
+        impl<const N: usize> From<Compact<$BUint<N>>> for $BUint<N> {
+            #[inline]
+            fn from(compact: Compact<$BUint<N>>) -> Self {
+                compact.0
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::scale::Compact;
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+                use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_scale(u: utest, i: itest) -> bool {
+                        let (big_u, big_i) = (UTEST::from(u), ITEST::from(i));
+                        let (u_bytes, i_bytes) = (u.encode(), i.encode());
+                        big_u.encode() == u_bytes
+                            && big_i.encode() == i_bytes
+                            && UTEST::decode(&mut &u_bytes[..]) == Ok(big_u)
+                            && ITEST::decode(&mut &i_bytes[..]) == Ok(big_i)
+                    }
+
+                    fn quickcheck_scale_compact(u: utest) -> bool {
+                        let big_u = UTEST::from(u);
+                        let bytes = parity_scale_codec::Compact(u).encode();
+                        Compact(big_u).encode() == bytes
+                            && Compact(big_u).size_hint() == bytes.len()
+                            && Compact::<UTEST>::decode(&mut &bytes[..]) == Ok(Compact(big_u))
+                    }
+                }
+
+                #[test]
+                fn scale_compact() {
+                    let values: [utest; 8] = [0, 63, 64, (1 << 14) - 1, 1 << 14, (1 << 30) - 1, 1 << 30, utest::MAX];
+                    for u in values {
+                        let bytes = parity_scale_codec::Compact(u).encode();
+                        assert_eq!(Compact(UTEST::from(u)).encode(), bytes);
+                        assert_eq!(Compact::<UTEST>::decode(&mut &bytes[..]), Ok(Compact(UTEST::from(u))));
+                    }
+                    assert_eq!(UTEST::max_encoded_len(), 16);
+                    assert_eq!(Compact::<UTEST>::max_encoded_len(), 17);
+                    // a single digit has the same maximum length as the primitive integer of the same width
+                    type Single = crate::$BUint<1>;
+                    assert_eq!(Compact::<Single>::max_encoded_len(), parity_scale_codec::Compact::<$Digit>::max_encoded_len());
+                    assert_eq!(Compact::<Single>::max_encoded_len(), Compact(Single::MAX).encode().len());
+
+                    // encodings which are not the shortest possible
+                    assert!(Compact::<UTEST>::decode(&mut &[0b01, 0][..]).is_err());
+                    assert!(Compact::<UTEST>::decode(&mut &[0b10, 1, 0, 0][..]).is_err());
+                    assert!(Compact::<UTEST>::decode(&mut &[0b11, 0, 0, 0, 0x3f][..]).is_err());
+                    assert!(Compact::<UTEST>::decode(&mut &[0b111, 0, 0, 0, 0, 0][..]).is_err());
+
+                    // values which are too large for the type
+                    let mut too_large = vec![0b110011, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+                    assert!(Compact::<UTEST>::decode(&mut &too_large[..]).is_err());
+                    too_large[17] = 0;
+                    assert!(Compact::<UTEST>::decode(&mut &too_large[..]).is_err());
+                    assert!(UTEST::decode(&mut &[0; 15][..]).is_err());
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(scale);
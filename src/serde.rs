@@ -0,0 +1,705 @@
+//! Serialization and deserialization of bnum integers with [`serde`](https://docs.rs/serde/latest/serde/).
+//!
+//! The `serde` feature must be enabled to use items from this module.
+//!
+//! By default, integers are serialized as decimal strings, e.g. `"-12345"`, by human-readable formats such as JSON, and as `Self::BITS / 8` little-endian bytes by binary formats such as bincode. The bytes are serialized as a tuple, so that their number isn't written. When deserializing, human-readable formats also accept hexadecimal strings prefixed with `0x`.
+//!
+//! Previous versions of this crate serialized the array of digits that an integer is stored as. This representation is still accepted when deserializing from self-describing formats. Formats that aren't self-describing can only read it if digits are written as their little-endian bytes, as is the case for bincode's default fixed-size integer encoding, since the representation is then the same as the default one.
+//!
+//! The submodules of this module select a representation for any format, and are intended for use with serde's `with` attribute:
+//!
+//! ```
+//! use bnum::types::{I256, U256};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Transfer {
+//!     #[serde(with = "bnum::serde::decimal")]
+//!     amount: U256,
+//!     #[serde(with = "bnum::serde::hex")]
+//!     id: U256,
+//!     #[serde(with = "bnum::serde::compact")]
+//!     delta: I256,
+//! }
+//! ```
+
+use crate::errors::ParseIntError;
+use crate::ExpType;
+use ::serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Unexpected, Visitor};
+use ::serde::ser::{SerializeTuple, Serializer};
+use ::serde::{Deserialize, Serialize};
+use core::fmt::{self, Display, Formatter};
+use core::marker::PhantomData;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Implemented by every integer type of this crate, and used as a bound by the functions in the submodules of this module.
+///
+/// This trait is sealed, so can't be implemented outside of this crate.
+pub trait Integer: sealed::Sealed + Copy + Default + Display {
+    #[doc(hidden)]
+    const BYTES: usize;
+    #[doc(hidden)]
+    const DIGITS: usize;
+    #[doc(hidden)]
+    const DIGIT_BITS: ExpType;
+
+    #[doc(hidden)]
+    fn fmt_hex(&self, f: &mut Formatter) -> fmt::Result;
+
+    #[doc(hidden)]
+    fn parse_str(src: &str, hex: bool) -> Result<Self, ParseIntError>;
+
+    /// Calls `f` with the bytes of the integer, in big-endian order if `big_endian` is true, and in little-endian order otherwise.
+    #[doc(hidden)]
+    fn with_bytes<R, F: FnOnce(&[u8]) -> R>(&self, big_endian: bool, f: F) -> R;
+
+    /// Removes the leading bytes from the big-endian bytes of an integer which don't change its value, keeping at least one byte.
+    #[doc(hidden)]
+    fn trim_be_bytes(bytes: &[u8]) -> &[u8];
+
+    #[doc(hidden)]
+    fn from_be_bytes_slice(bytes: &[u8]) -> Option<Self>;
+
+    #[doc(hidden)]
+    fn from_le_bytes_slice(bytes: &[u8]) -> Option<Self>;
+
+    /// Sets the `index`-th group of `bits` bits of the integer to `value`, counting from the least significant group. The group must previously be zero.
+    #[doc(hidden)]
+    fn set_bits_group(&mut self, index: usize, bits: ExpType, value: u64);
+
+    /// Shifts the bits of the integer left by one byte, and sets the least significant byte to `byte`.
+    #[doc(hidden)]
+    fn push_byte(&mut self, byte: u8);
+}
+
+struct Hex<'a, T>(&'a T);
+
+impl<T: Integer> Display for Hex<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt_hex(f)
+    }
+}
+
+fn serialize_bytes_tuple<T: Integer, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    value.with_bytes(false, |bytes| {
+        let mut tuple = serializer.serialize_tuple(bytes.len())?;
+        for byte in bytes {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    })
+}
+
+/// A digit of the representation used by previous versions of this crate, or a byte.
+struct Element(u64);
+
+impl<'de> Deserialize<'de> for Element {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ElementVisitor;
+
+        impl<'de> Visitor<'de> for ElementVisitor {
+            type Value = Element;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("an unsigned integer")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Element, E> {
+                Ok(Element(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Element, E> {
+                u64::try_from(v).map(Element).map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+            }
+        }
+
+        // formats which aren't self-describing read a byte, while self-describing formats may provide a digit of any size
+        deserializer.deserialize_u8(ElementVisitor)
+    }
+}
+
+/// The field name of the struct that previous versions of this crate serialized integers as.
+struct Field;
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        const FIELDS: &[&str] = &["digits", "bits"];
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("`digits` or `bits`")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Field, E> {
+                match v {
+                    0 => Ok(Field),
+                    _ => Err(E::invalid_value(Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Field, E> {
+                match v {
+                    "digits" | "bits" => Ok(Field),
+                    _ => Err(E::unknown_field(v, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+/// Deserializes any of the representations accepted by the default `Deserialize` implementations.
+struct DefaultVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Integer> DeserializeSeed<'de> for DefaultVisitor<T> {
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, T: Integer> Visitor<'de> for DefaultVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "a {}-bit integer as a string or {} little-endian bytes", T::BYTES * 8, T::BYTES)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        let hex = v.starts_with("0x") || v.starts_with("-0x") || v.starts_with("+0x");
+        T::parse_str(v, hex).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        if v.len() != T::BYTES {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        Ok(T::from_le_bytes_slice(v).unwrap())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        // the sequence is either the bytes of the integer, or the digits of the previous representation, which can only be told apart by their number
+        let mut from_bytes = T::default();
+        let mut from_digits = T::default();
+        let (mut bytes_valid, mut digits_valid) = (true, true);
+        let mut len = 0;
+        while let Some(Element(value)) = seq.next_element()? {
+            if len < T::BYTES && value <= u8::MAX as u64 {
+                from_bytes.set_bits_group(len, 8, value);
+            } else {
+                bytes_valid = false;
+            }
+            if len < T::DIGITS && (T::DIGIT_BITS == 64 || value >> T::DIGIT_BITS == 0) {
+                from_digits.set_bits_group(len, T::DIGIT_BITS, value);
+            } else {
+                digits_valid = false;
+            }
+            len += 1;
+            if !bytes_valid && !digits_valid {
+                return Err(if len > T::BYTES.max(T::DIGITS) {
+                    de::Error::invalid_length(len, &self)
+                } else {
+                    de::Error::invalid_value(Unexpected::Unsigned(value), &self)
+                });
+            }
+        }
+        if len == T::BYTES && bytes_valid {
+            Ok(from_bytes)
+        } else if len == T::DIGITS && digits_valid {
+            Ok(from_digits)
+        } else {
+            Err(de::Error::invalid_length(len, &self))
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        // the previous representation of unsigned integers is a struct with a `digits` field, and of signed integers is a struct with a `bits` field containing an unsigned integer
+        if map.next_key::<Field>()?.is_none() {
+            return Err(de::Error::missing_field("digits"));
+        }
+        let value = map.next_value_seed(self)?;
+        if map.next_key::<Field>()?.is_some() {
+            return Err(de::Error::duplicate_field("digits"));
+        }
+        Ok(value)
+    }
+}
+
+fn deserialize_default<'de, T: Integer, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    let visitor = DefaultVisitor(PhantomData);
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_tuple(T::BYTES, visitor)
+    }
+}
+
+struct StrVisitor<T> {
+    hex: bool,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Integer> Visitor<'de> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        if self.hex {
+            write!(f, "a {}-bit integer as a hexadecimal string", T::BYTES * 8)
+        } else {
+            write!(f, "a {}-bit integer as a decimal string", T::BYTES * 8)
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        T::parse_str(v, self.hex).map_err(E::custom)
+    }
+}
+
+struct BytesVisitor<T> {
+    big_endian: bool,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Integer> Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        let endian = if self.big_endian { "big" } else { "little" };
+        write!(f, "{} {}-endian bytes", T::BYTES, endian)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        if v.len() != T::BYTES {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        let value = if self.big_endian {
+            T::from_be_bytes_slice(v)
+        } else {
+            T::from_le_bytes_slice(v)
+        };
+        Ok(value.unwrap())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut out = T::default();
+        let mut len = 0;
+        while let Some(byte) = seq.next_element::<u8>()? {
+            if len == T::BYTES {
+                return Err(de::Error::invalid_length(len + 1, &self));
+            }
+            let index = if self.big_endian { T::BYTES - 1 - len } else { len };
+            out.set_bits_group(index, 8, byte as u64);
+            len += 1;
+        }
+        if len != T::BYTES {
+            return Err(de::Error::invalid_length(len, &self));
+        }
+        Ok(out)
+    }
+}
+
+struct CompactVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Integer> Visitor<'de> for CompactVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "at most {} big-endian bytes", T::BYTES)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        if v.len() > T::BYTES {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        Ok(T::from_be_bytes_slice(v).unwrap())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut bits = T::default();
+        let mut len = 0;
+        while let Some(byte) = seq.next_element::<u8>()? {
+            if len == T::BYTES {
+                return Err(de::Error::invalid_length(len + 1, &self));
+            }
+            bits.push_byte(byte);
+            len += 1;
+        }
+        // the last `len` bytes of `bits` are the bytes that were read, which are then sign extended if necessary
+        Ok(bits.with_bytes(true, |bytes| T::from_be_bytes_slice(&bytes[T::BYTES - len..]).unwrap()))
+    }
+}
+
+/// Serializes an integer as a decimal string, e.g. `"-12345"`, for any format.
+pub mod decimal {
+    use super::{Integer, StrVisitor};
+    use ::serde::{Deserializer, Serializer};
+    use core::marker::PhantomData;
+
+    /// Serializes `value` as a decimal string.
+    pub fn serialize<T: Integer, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    /// Deserializes an integer from a decimal string, with an optional sign.
+    pub fn deserialize<'de, T: Integer, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_str(StrVisitor {
+            hex: false,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// Serializes an integer as a hexadecimal string prefixed with `0x`, e.g. `"0xff"`, for any format.
+///
+/// Signed integers are written as a sign followed by their magnitude, e.g. `"-0x1f"`, rather than as their two's complement representation.
+pub mod hex {
+    use super::{Hex, Integer, StrVisitor};
+    use ::serde::{Deserializer, Serializer};
+    use core::marker::PhantomData;
+
+    /// Serializes `value` as a hexadecimal string prefixed with `0x`, using lowercase digits.
+    pub fn serialize<T: Integer, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&Hex(value))
+    }
+
+    /// Deserializes an integer from a hexadecimal string, in which the `0x` or `0X` prefix is optional and digits may have either case. Signed integers may have a sign before the prefix.
+    pub fn deserialize<'de, T: Integer, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_str(StrVisitor {
+            hex: true,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// Serializes an integer as `Self::BITS / 8` big-endian bytes, for any format.
+///
+/// Signed integers are written as their two's complement representation. The bytes are serialized with [`Serializer::serialize_bytes`](::serde::Serializer::serialize_bytes), so formats which aren't self-describing write their length.
+pub mod be_bytes {
+    use super::{BytesVisitor, Integer};
+    use ::serde::{Deserializer, Serializer};
+    use core::marker::PhantomData;
+
+    /// Serializes `value` as its big-endian bytes.
+    pub fn serialize<T: Integer, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.with_bytes(true, |bytes| serializer.serialize_bytes(bytes))
+    }
+
+    /// Deserializes an integer from exactly `Self::BITS / 8` big-endian bytes.
+    pub fn deserialize<'de, T: Integer, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor {
+            big_endian: true,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// Serializes an integer as `Self::BITS / 8` little-endian bytes, for any format.
+///
+/// Signed integers are written as their two's complement representation. The bytes are serialized with [`Serializer::serialize_bytes`](::serde::Serializer::serialize_bytes), so formats which aren't self-describing write their length.
+pub mod le_bytes {
+    use super::{BytesVisitor, Integer};
+    use ::serde::{Deserializer, Serializer};
+    use core::marker::PhantomData;
+
+    /// Serializes `value` as its little-endian bytes.
+    pub fn serialize<T: Integer, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.with_bytes(false, |bytes| serializer.serialize_bytes(bytes))
+    }
+
+    /// Deserializes an integer from exactly `Self::BITS / 8` little-endian bytes.
+    pub fn deserialize<'de, T: Integer, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor {
+            big_endian: false,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// Serializes an integer as the fewest big-endian bytes that represent it, for any format.
+///
+/// Unsigned integers are written without leading zero bytes, and signed integers as the shortest two's complement representation, which is sign extended when deserializing. At least one byte is always written, so zero is written as a single zero byte. This suits integers which are usually much smaller than the maximum value of their type.
+pub mod compact {
+    use super::{CompactVisitor, Integer};
+    use ::serde::{Deserializer, Serializer};
+    use core::marker::PhantomData;
+
+    /// Serializes `value` as its shortest big-endian bytes.
+    pub fn serialize<T: Integer, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.with_bytes(true, |bytes| serializer.serialize_bytes(T::trim_be_bytes(bytes)))
+    }
+
+    /// Deserializes an integer from at most `Self::BITS / 8` big-endian bytes.
+    pub fn deserialize<'de, T: Integer, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_bytes(CompactVisitor(PhantomData))
+    }
+}
+
+macro_rules! serde {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        impl<const N: usize> sealed::Sealed for $BUint<N> {}
+
+        impl<const N: usize> Integer for $BUint<N> {
+            const BYTES: usize = Self::BITS as usize / 8;
+            const DIGITS: usize = N;
+            const DIGIT_BITS: ExpType = $Digit::BITS as ExpType;
+
+            fn fmt_hex(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "{:#x}", self)
+            }
+
+            fn parse_str(src: &str, hex: bool) -> Result<Self, ParseIntError> {
+                if hex {
+                    Self::from_hex(src)
+                } else {
+                    Self::from_str_radix(src, 10)
+                }
+            }
+
+            fn with_bytes<R, F: FnOnce(&[u8]) -> R>(&self, big_endian: bool, f: F) -> R {
+                let mut digits = self.digits;
+                if big_endian {
+                    digits.reverse();
+                    for digit in digits.iter_mut() {
+                        *digit = digit.to_be();
+                    }
+                } else {
+                    for digit in digits.iter_mut() {
+                        *digit = digit.to_le();
+                    }
+                }
+                // SAFETY: the digits are primitive integers, which have no padding, and are now stored in memory in the byte order of the requested bytes
+                let bytes = unsafe {
+                    core::slice::from_raw_parts(digits.as_ptr() as *const u8, Self::BITS as usize / 8)
+                };
+                f(bytes)
+            }
+
+            fn trim_be_bytes(bytes: &[u8]) -> &[u8] {
+                let mut start = 0;
+                while start < bytes.len() - 1 && bytes[start] == 0 {
+                    start += 1;
+                }
+                &bytes[start..]
+            }
+
+            fn from_be_bytes_slice(bytes: &[u8]) -> Option<Self> {
+                Self::from_be_slice(bytes)
+            }
+
+            fn from_le_bytes_slice(bytes: &[u8]) -> Option<Self> {
+                Self::from_le_slice(bytes)
+            }
+
+            fn set_bits_group(&mut self, index: usize, bits: ExpType, value: u64) {
+                let bit = index * bits as usize;
+                self.digits[bit / $Digit::BITS as usize] |= (value as $Digit) << (bit % $Digit::BITS as usize);
+            }
+
+            fn push_byte(&mut self, byte: u8) {
+                *self = self.wrapping_shl(8) | Self::from(byte);
+            }
+        }
+
+        impl<const N: usize> Serialize for $BUint<N> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    decimal::serialize(self, serializer)
+                } else {
+                    serialize_bytes_tuple(self, serializer)
+                }
+            }
+        }
+
+        impl<'de, const N: usize> Deserialize<'de> for $BUint<N> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_default(deserializer)
+            }
+        }
+
+        impl<const N: usize> sealed::Sealed for $BInt<N> {}
+
+        impl<const N: usize> Integer for $BInt<N> {
+            const BYTES: usize = Self::BITS as usize / 8;
+            const DIGITS: usize = N;
+            const DIGIT_BITS: ExpType = $Digit::BITS as ExpType;
+
+            fn fmt_hex(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "{:#x}", self.sign_magnitude())
+            }
+
+            fn parse_str(src: &str, hex: bool) -> Result<Self, ParseIntError> {
+                if hex {
+                    Self::from_hex(src)
+                } else {
+                    Self::from_str_radix(src, 10)
+                }
+            }
+
+            fn with_bytes<R, F: FnOnce(&[u8]) -> R>(&self, big_endian: bool, f: F) -> R {
+                self.bits.with_bytes(big_endian, f)
+            }
+
+            fn trim_be_bytes(bytes: &[u8]) -> &[u8] {
+                let mut start = 0;
+                // a leading byte is redundant if it only repeats the sign bit of the next byte
+                while start < bytes.len() - 1 && (bytes[start] == 0 || bytes[start] == u8::MAX) && (bytes[start] >> 7 == bytes[start + 1] >> 7) {
+                    start += 1;
+                }
+                &bytes[start..]
+            }
+
+            fn from_be_bytes_slice(bytes: &[u8]) -> Option<Self> {
+                Self::from_be_slice(bytes)
+            }
+
+            fn from_le_bytes_slice(bytes: &[u8]) -> Option<Self> {
+                Self::from_le_slice(bytes)
+            }
+
+            fn set_bits_group(&mut self, index: usize, bits: ExpType, value: u64) {
+                self.bits.set_bits_group(index, bits, value);
+            }
+
+            fn push_byte(&mut self, byte: u8) {
+                self.bits.push_byte(byte);
+            }
+        }
+
+        impl<const N: usize> Serialize for $BInt<N> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    decimal::serialize(self, serializer)
+                } else {
+                    serialize_bytes_tuple(self, serializer)
+                }
+            }
+        }
+
+        impl<'de, const N: usize> Deserialize<'de> for $BInt<N> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_default(deserializer)
+            }
+        }
+
+        #[cfg(test)]
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+                use ::serde::{Deserialize, Serialize};
+
+                #[derive(Serialize, Deserialize, PartialEq, Debug)]
+                struct Reprs {
+                    #[serde(with = "crate::serde::decimal")]
+                    decimal: ITEST,
+                    #[serde(with = "crate::serde::hex")]
+                    hex: ITEST,
+                    #[serde(with = "crate::serde::be_bytes")]
+                    be_bytes: UTEST,
+                    #[serde(with = "crate::serde::le_bytes")]
+                    le_bytes: ITEST,
+                    #[serde(with = "crate::serde::compact")]
+                    compact_unsigned: UTEST,
+                    #[serde(with = "crate::serde::compact")]
+                    compact_signed: ITEST,
+                }
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_default(u: utest, i: itest) -> bool {
+                        let (big_u, big_i) = (UTEST::from(u), ITEST::from(i));
+                        serde_json::to_string(&big_u).unwrap() == format!("\"{}\"", u)
+                            && serde_json::to_string(&big_i).unwrap() == format!("\"{}\"", i)
+                            && bincode::serialize(&big_u).unwrap() == u.to_le_bytes()
+                            && bincode::serialize(&big_i).unwrap() == i.to_le_bytes()
+                            && serde_json::from_str::<UTEST>(&format!("\"{}\"", u)).unwrap() == big_u
+                            && serde_json::from_str::<UTEST>(&format!("\"{:#x}\"", u)).unwrap() == big_u
+                            && serde_json::from_str::<ITEST>(&format!("\"{}\"", i)).unwrap() == big_i
+                            && bincode::deserialize::<UTEST>(&u.to_le_bytes()).unwrap() == big_u
+                            && bincode::deserialize::<ITEST>(&i.to_le_bytes()).unwrap() == big_i
+                    }
+
+                    fn quickcheck_with(a: itest, b: utest, c: utest, d: i8) -> bool {
+                        let reprs = Reprs {
+                            decimal: ITEST::from(a),
+                            hex: ITEST::from(a),
+                            be_bytes: UTEST::from(b),
+                            le_bytes: ITEST::from(a),
+                            compact_unsigned: UTEST::from(c) >> (c % 128) as u32,
+                            compact_signed: ITEST::from(a) >> (c % 128) as u32 | ITEST::from(d),
+                        };
+                        let json = serde_json::to_string(&reprs).unwrap();
+                        let bincode = bincode::serialize(&reprs).unwrap();
+                        serde_json::from_str::<Reprs>(&json).unwrap() == reprs && bincode::deserialize::<Reprs>(&bincode).unwrap() == reprs
+                    }
+                }
+
+                #[test]
+                fn old_representation() {
+                    let u = UTEST::from(0x0123456789abcdef_fedcba9876543210u128);
+                    let json = format!("{{\"digits\":{:?}}}", u.digits);
+                    assert_eq!(serde_json::from_str::<UTEST>(&json).unwrap(), u);
+                    let i = ITEST::from(-0x0123456789abcdef_fedcba9876543210i128);
+                    let json = format!("{{\"bits\":{{\"digits\":{:?}}}}}", i.bits.digits);
+                    assert_eq!(serde_json::from_str::<ITEST>(&json).unwrap(), i);
+                    assert_eq!(serde_json::from_str::<UTEST>(&format!("{:?}", u.digits)).unwrap(), u);
+
+                    // bincode writes digits as fixed-size little-endian integers, so the old representation is the same as the new one
+                    assert_eq!(bincode::serialize(&u.digits).unwrap(), bincode::serialize(&u).unwrap());
+
+                    assert!(serde_json::from_str::<UTEST>("{\"limbs\":[0,0]}").is_err());
+                    assert!(serde_json::from_str::<UTEST>("[1,2,3]").is_err());
+                }
+
+                #[test]
+                fn representations() {
+                    let n = ITEST::from(-0x1f);
+                    let reprs = Reprs {
+                        decimal: n,
+                        hex: n,
+                        be_bytes: UTEST::from(0x0102u16),
+                        le_bytes: n,
+                        compact_unsigned: UTEST::from(0x0102u16),
+                        compact_signed: n,
+                    };
+                    let json = serde_json::to_value(&reprs).unwrap();
+                    assert_eq!(json["decimal"], "-31");
+                    assert_eq!(json["hex"], "-0x1f");
+                    assert_eq!(json["be_bytes"], serde_json::json!(0x0102u128.to_be_bytes()));
+                    assert_eq!(json["le_bytes"], serde_json::json!((-0x1fi128).to_le_bytes()));
+                    assert_eq!(json["compact_unsigned"], serde_json::json!([1, 2]));
+                    assert_eq!(json["compact_signed"], serde_json::json!([0xe1]));
+
+                    #[derive(Serialize)]
+                    struct Compact(#[serde(with = "crate::serde::compact")] ITEST);
+
+                    let compact = |i: ITEST| serde_json::to_value(Compact(i)).unwrap();
+                    assert_eq!(compact(ITEST::ZERO), serde_json::json!([0]));
+                    assert_eq!(compact(ITEST::from(128)), serde_json::json!([0, 128]));
+                    assert_eq!(compact(ITEST::from(-129)), serde_json::json!([0xff, 0x7f]));
+                    assert_eq!(compact(ITEST::MIN), serde_json::json!(i128::MIN.to_be_bytes()));
+
+                    let mut invalid = json.clone();
+                    invalid["be_bytes"] = serde_json::json!([1, 2]);
+                    assert!(serde_json::from_value::<Reprs>(invalid).is_err());
+                    let mut invalid = json.clone();
+                    invalid["compact_unsigned"] = serde_json::json!([1u8; 17].to_vec());
+                    assert!(serde_json::from_value::<Reprs>(invalid).is_err());
+                    let mut invalid = json.clone();
+                    invalid["decimal"] = serde_json::json!("12a");
+                    assert!(format!("{}", serde_json::from_value::<Reprs>(invalid).unwrap_err()).contains("invalid digit 'a' at byte index 2"));
+                }
+            }
+        }
+    };
+}
+
+crate::macro_impl!(serde);
@@ -1,4 +1,6 @@
 use crate::cast::CastFrom;
+#[cfg(feature = "float")]
+use crate::cast::As;
 use crate::test::types::*;
 use crate::*;
 
@@ -64,6 +66,26 @@ impl TestConvert for f32 {
     }
 }
 
+#[cfg(feature = "float")]
+impl TestConvert for F64 {
+    type Output = u64;
+
+    #[inline]
+    fn into(self) -> Self::Output {
+        self.to_bits().as_()
+    }
+}
+
+#[cfg(feature = "float")]
+impl TestConvert for F32 {
+    type Output = u32;
+
+    #[inline]
+    fn into(self) -> Self::Output {
+        self.to_bits().as_()
+    }
+}
+
 impl<T: TestConvert, U: TestConvert> TestConvert for (T, U) {
     type Output = (<T as TestConvert>::Output, <U as TestConvert>::Output);
 
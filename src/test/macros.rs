@@ -56,6 +56,33 @@ macro_rules! test_bignum {
 
 pub(crate) use test_bignum;
 
+/// Like `test_bignum`, but for functions which return a float, and treats all NaNs as equal, since the sign and payload of a NaN result are not specified.
+#[cfg(feature = "float")]
+macro_rules! test_float {
+	{
+		function: <$primitive: ty $(as $Trait: ident)?> :: $function: ident ($($param: ident : $ty: ty), *)
+	} => {
+		paste::paste! {
+			quickcheck::quickcheck! {
+				#[allow(non_snake_case)]
+				fn [<quickcheck_ $primitive _ $($Trait _)? $function>]($($param : $ty), *) -> quickcheck::TestResult {
+					let (big, primitive) = crate::test::results!(<$primitive $(as $Trait)?>::$function ($(Into::into($param)), *));
+					let canonical = |bits| if <$primitive>::from_bits(bits).is_nan() {
+						<$primitive>::NAN.to_bits()
+					} else {
+						bits
+					};
+
+					quickcheck::TestResult::from_bool(canonical(big) == canonical(primitive))
+				}
+			}
+		}
+	};
+}
+
+#[cfg(feature = "float")]
+pub(crate) use test_float;
+
 macro_rules! results {
 	(<$primitive: ty $(as $Trait: ty)?> :: $function: ident ($($arg: expr), *)) => {
 		paste::paste! {
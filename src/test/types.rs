@@ -46,4 +46,10 @@ mod small_types {
 }
 
 pub use small_types::*;
-pub use core::primitive::*;
\ No newline at end of file
+pub use core::primitive::*;
+
+#[cfg(feature = "float")]
+pub type F64 = crate::float::Float<1, 52>;
+
+#[cfg(feature = "float")]
+pub type F32 = crate::float::FloatD32<1, 23>;
@@ -0,0 +1,35 @@
+// The `zerocopy` traits are derived on the integer types, so this module only contains tests of them.
+
+macro_rules! zerocopy {
+    ($BUint: ident, $BInt: ident, $Digit: ident) => {
+        paste::paste! {
+            mod [<$Digit _digit_tests>] {
+                use crate::test::types::big_types::$Digit::*;
+                use crate::test::types::{itest, utest};
+                use zerocopy::{FromBytes, FromZeros, IntoBytes};
+
+                quickcheck::quickcheck! {
+                    fn quickcheck_zerocopy(a: utest, b: itest) -> bool {
+                        let values = [ITEST::from(b), ITEST::from(a as itest)];
+                        let bytes = values.as_bytes();
+                        let unsigned = <[UTEST]>::ref_from_bytes(bytes).unwrap();
+                        bytes.len() == 32
+                            && bytes[..16] == *values[0].as_ne_bytes()
+                            && UTEST::from(a).as_bytes() == UTEST::from(a).as_ne_bytes()
+                            && unsigned[1] == UTEST::from(a)
+                            && ITEST::read_from_bytes(&bytes[..16]) == Ok(values[0])
+                            && UTEST::read_from_bytes(&bytes[1..]).is_err()
+                    }
+                }
+
+                #[test]
+                fn zerocopy_zeroed() {
+                    assert_eq!(UTEST::new_zeroed(), UTEST::ZERO);
+                    assert_eq!(ITEST::new_zeroed(), ITEST::ZERO);
+                }
+            }
+        }
+    };
+}
+
+crate::main_impl!(zerocopy);